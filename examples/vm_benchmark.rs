@@ -0,0 +1,32 @@
+//! Compares walking an `Operation` tree with running its bytecode.
+//!
+//! Run it with optimizations, otherwise the numbers say little:
+//!
+//! ```text
+//! cargo run --release --example vm_benchmark
+//! ```
+
+use learn_rust::calc::env::Env;
+use learn_rust::calc::{Mode, Operation, parse_as, vm};
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn main() {
+    let mut env: Env<i64> = Env::new();
+    env.set("x", 7);
+    env.set("y", -3);
+
+    for formula in [
+        "1 + 2",
+        "(x + 1) * (y - 2) / 3",
+        "x^3 - 2*x^2*y + 5*x*y^2 - y^3 % 7",
+    ] {
+        let operation: Operation<i64> = parse_as(formula).expect("the formulas parse");
+        println!("{}", formula);
+        println!("{}", vm::compile(&operation));
+        println!(
+            "{}\n",
+            vm::benchmark(&operation, &env, Mode::Checked, ITERATIONS)
+        );
+    }
+}
@@ -0,0 +1,18 @@
+// Exercise for the `add` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Return the product of x and y without using the `return` keyword
+fn multiply(x: i32, y: i32) -> i32 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_the_last_expression() {
+        assert_eq!(multiply(3, 4), 12);
+    }
+}
@@ -0,0 +1,26 @@
+// Exercise for the `borrow_example` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Append " World" through a mutable reference
+fn append_world(s: &mut String) {
+    todo!()
+}
+
+// Return the length of `s` by only borrowing it
+fn length(s: &String) -> usize {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrows() {
+        let mut s: String = String::from("Hello");
+        append_world(&mut s);
+        assert_eq!(length(&s), 11);
+        assert_eq!(s, "Hello World");
+    }
+}
@@ -0,0 +1,27 @@
+// Exercise for the `build_user` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+struct User {
+    username: String,
+    email: String,
+    active: bool,
+}
+
+// Build an active user using the field init shorthand
+fn build_user(email: String, username: String) -> User {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_init_shorthand() {
+        let user: User = build_user(String::from("jane@example.com"), String::from("Jane"));
+        assert_eq!(user.email, "jane@example.com");
+        assert_eq!(user.username, "Jane");
+        assert!(user.active);
+    }
+}
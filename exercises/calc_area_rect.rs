@@ -0,0 +1,28 @@
+// Exercise for the `calc_area_rect` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+struct Rect {
+    width: u32,
+    height: u32,
+}
+
+// Return the area of the rectangle, it is only borrowed
+fn area(rect: &Rect) -> u32 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn borrowed_struct() {
+        let rect: Rect = Rect {
+            width: 30,
+            height: 50,
+        };
+        assert_eq!(area(&rect), 1500);
+        assert_eq!(rect.width, 30);
+    }
+}
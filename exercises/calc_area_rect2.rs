@@ -0,0 +1,35 @@
+// Exercise for the `calc_area_rect2` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+struct Rect {
+    width: u32,
+    height: u32,
+}
+
+impl Rect {
+    // A method: whether `other` fits inside `self`
+    fn can_hold(&self, other: &Rect) -> bool {
+        todo!()
+    }
+
+    // An associated function: a square with sides of `size`
+    fn square(size: u32) -> Rect {
+        todo!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn methods() {
+        let big: Rect = Rect {
+            width: 40,
+            height: 60,
+        };
+        assert!(big.can_hold(&Rect::square(10)));
+        assert!(!Rect::square(10).can_hold(&big));
+    }
+}
@@ -0,0 +1,28 @@
+// Exercise for the `compound_data_types` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Destructure the tuple and return its parts in reverse order
+fn reverse(tuple: (i32, f64, &str)) -> (&str, f64, i32) {
+    todo!()
+}
+
+// Return the sum of the first and last element using indexing
+fn first_plus_last(array: [i32; 3]) -> i32 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tuples() {
+        assert_eq!(reverse((1, 2.5, "three")), ("three", 2.5, 1));
+    }
+
+    #[test]
+    fn arrays() {
+        assert_eq!(first_plus_last([1, 2, 3]), 4);
+    }
+}
@@ -0,0 +1,18 @@
+// Exercise for the `concatenation_example` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Join the two words with a space using the format! macro
+fn join(first: &str, second: &str) -> String {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_macro() {
+        assert_eq!(join("Hello", "world"), "Hello world");
+    }
+}
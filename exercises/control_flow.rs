@@ -0,0 +1,32 @@
+// Exercise for the `control_flow` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Return "small" below 10, "medium" below 20 and "large" otherwise
+fn size(number: i32) -> &'static str {
+    todo!("use if / else if / else")
+}
+
+// Return "One", "Two" or "Something else" using match
+fn name(number: i32) -> &'static str {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn if_else() {
+        assert_eq!(size(5), "small");
+        assert_eq!(size(15), "medium");
+        assert_eq!(size(25), "large");
+    }
+
+    #[test]
+    fn matching() {
+        assert_eq!(name(1), "One");
+        assert_eq!(name(2), "Two");
+        assert_eq!(name(7), "Something else");
+    }
+}
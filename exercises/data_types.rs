@@ -0,0 +1,28 @@
+// Exercise for the `data_types` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Write the same number, 255, as a hexadecimal, octal and binary literal
+fn literals() -> (i32, i32, i32) {
+    todo!("(0x.., 0o.., 0b..)")
+}
+
+// Return the byte value of the ASCII letter 'A' using a byte literal
+fn letter_a() -> u8 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_literals() {
+        assert_eq!(literals(), (255, 255, 255));
+    }
+
+    #[test]
+    fn byte_literal() {
+        assert_eq!(letter_a(), 65);
+    }
+}
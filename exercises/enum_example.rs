@@ -0,0 +1,24 @@
+// Exercise for the `enum_example` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+enum IpAddrKind {
+    V4(u8, u8, u8, u8),
+    V6(String),
+}
+
+// Format the address, e.g. "192.168.1.1" or "::1"
+fn format_ip(ip: &IpAddrKind) -> String {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enums_with_data() {
+        assert_eq!(format_ip(&IpAddrKind::V4(192, 168, 1, 1)), "192.168.1.1");
+        assert_eq!(format_ip(&IpAddrKind::V6(String::from("::1"))), "::1");
+    }
+}
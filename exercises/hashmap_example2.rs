@@ -0,0 +1,23 @@
+// Exercise for the `hashmap_example2` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+use std::collections::HashMap;
+
+// Insert `team` with `score` only when it is not in the map yet
+fn insert_if_missing(scores: &mut HashMap<String, i32>, team: &str, score: i32) {
+    todo!("use the entry API")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_or_insert() {
+        let mut scores: HashMap<String, i32> = HashMap::new();
+        insert_if_missing(&mut scores, "Red", 5);
+        insert_if_missing(&mut scores, "Red", 40);
+        assert_eq!(scores.get("Red"), Some(&5));
+    }
+}
@@ -0,0 +1,23 @@
+// Exercise for the `hashmap_example3` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+use std::collections::HashMap;
+
+// Count how many times each word appears in `text`
+fn word_count(text: &str) -> HashMap<String, i32> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts() {
+        let map: HashMap<String, i32> = word_count("hello world wonderful world");
+        assert_eq!(map.get("world"), Some(&2));
+        assert_eq!(map.get("hello"), Some(&1));
+        assert_eq!(map.len(), 3);
+    }
+}
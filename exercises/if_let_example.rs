@@ -0,0 +1,20 @@
+// Exercise for the `if_let_example` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Return true only when `value` is Some(5), using if let
+fn is_five(value: Option<i32>) -> bool {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn if_let() {
+        assert!(is_five(Some(5)));
+        assert!(!is_five(Some(6)));
+        assert!(!is_five(None));
+    }
+}
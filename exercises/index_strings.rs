@@ -0,0 +1,19 @@
+// Exercise for the `index_strings` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Return the first character of `s`
+// s[0] does not compile, use chars() instead
+fn first_char(s: &str) -> char {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_character() {
+        assert_eq!(first_char("🍕🍔"), '🍕');
+    }
+}
@@ -0,0 +1,28 @@
+// Exercise for the `loops` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Add the numbers from 1 to n (inclusive) with a for loop over a range
+fn sum_to(n: i32) -> i32 {
+    todo!()
+}
+
+// Count up with `loop` until the counter is greater than 5, and break with it
+fn break_with_value() -> i32 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn for_range() {
+        assert_eq!(sum_to(5), 15);
+    }
+
+    #[test]
+    fn loop_break() {
+        assert_eq!(break_with_value(), 6);
+    }
+}
@@ -0,0 +1,21 @@
+// Exercise for the `makes_copy` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Return the double of `some_integer`
+// The caller can still use its value afterwards, because i32 is Copy
+fn double(some_integer: i32) -> i32 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies() {
+        let x: i32 = 5;
+        assert_eq!(double(x), 10);
+        assert_eq!(x, 5);
+    }
+}
@@ -0,0 +1,20 @@
+// Exercise for the `moved` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Return both strings, `s2` has to be a copy of `s1` made with clone
+// so that s1 is still valid afterwards
+fn both() -> (String, String) {
+    let s1: String = String::from("Hello");
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clone_keeps_the_original() {
+        assert_eq!(both(), (String::from("Hello"), String::from("Hello")));
+    }
+}
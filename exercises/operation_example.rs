@@ -0,0 +1,37 @@
+// Exercise for the `operation_example` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+enum Operation {
+    Add(i32, i32),
+    Div { divident: i32, divisor: i32 },
+}
+
+// Run the operation, dividing by zero is an error
+fn execute(operation: Operation) -> Result<i32, String> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results() {
+        assert_eq!(execute(Operation::Add(1, 2)), Ok(3));
+        assert_eq!(
+            execute(Operation::Div {
+                divident: 20,
+                divisor: 4
+            }),
+            Ok(5)
+        );
+        assert!(
+            execute(Operation::Div {
+                divident: 20,
+                divisor: 0
+            })
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,19 @@
+// Exercise for the `option_example` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Return the value plus one, or None when there is no value
+fn plus_one(value: Option<i32>) -> Option<i32> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options() {
+        assert_eq!(plus_one(Some(5)), Some(6));
+        assert_eq!(plus_one(None), None);
+    }
+}
@@ -0,0 +1,20 @@
+// Exercise for the `owner` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Return a String built from the literal "World"
+// The literal lives in the binary, the String owns a copy on the heap
+fn to_heap(literal: &str) -> String {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heap_string() {
+        let s: String = to_heap("World");
+        assert_eq!(s, "World");
+    }
+}
@@ -0,0 +1,20 @@
+// Exercise for the `ownership_functions` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Take ownership of `s`, append " World" and give it back
+fn takes_and_gives_back(s: String) -> String {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gives_back() {
+        let s: String = String::from("Hello");
+        let s: String = takes_and_gives_back(s);
+        assert_eq!(s, "Hello World");
+    }
+}
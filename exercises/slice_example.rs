@@ -0,0 +1,18 @@
+// Exercise for the `slice_example` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Return the elements between the first and the last one
+fn middle(a: &[i32]) -> &[i32] {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slices() {
+        assert_eq!(middle(&[1, 2, 3, 4, 5]), &[2, 3, 4]);
+    }
+}
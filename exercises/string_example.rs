@@ -0,0 +1,19 @@
+// Exercise for the `string_example` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Turn "Foo" into "Foobar" with push_str and return it
+fn foobar() -> String {
+    let mut s: String = String::from("Foo");
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_str() {
+        assert_eq!(foobar(), "Foobar");
+    }
+}
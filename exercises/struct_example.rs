@@ -0,0 +1,29 @@
+// Exercise for the `struct_example` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+struct User {
+    username: String,
+    sign_in_count: u64,
+}
+
+// Return a copy of `user` with one more sign in, using the struct update syntax
+fn sign_in(user: User) -> User {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn struct_update() {
+        let user: User = User {
+            username: String::from("John Doe"),
+            sign_in_count: 1,
+        };
+        let user: User = sign_in(user);
+        assert_eq!(user.username, "John Doe");
+        assert_eq!(user.sign_in_count, 2);
+    }
+}
@@ -0,0 +1,18 @@
+// Exercise for the `takes_ownership` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Take ownership of `some_string` and return how many bytes it has
+fn consume(some_string: String) -> usize {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consumes() {
+        assert_eq!(consume(String::from("Hello")), 5);
+    }
+}
@@ -0,0 +1,20 @@
+// Exercise for the `tuple_struct` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+struct Color(i32, i32, i32);
+
+// Return the sum of the three color channels
+fn brightness(color: &Color) -> i32 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tuple_fields() {
+        assert_eq!(brightness(&Color(10, 20, 30)), 60);
+    }
+}
@@ -0,0 +1,28 @@
+// Exercise for the `value_in_cents` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter,
+}
+
+// Return the value of the coin in cents
+fn value_in_cents(coin: Coin) -> u8 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coins() {
+        assert_eq!(value_in_cents(Coin::Penny), 1);
+        assert_eq!(value_in_cents(Coin::Nickel), 5);
+        assert_eq!(value_in_cents(Coin::Dime), 10);
+        assert_eq!(value_in_cents(Coin::Quarter), 25);
+    }
+}
@@ -0,0 +1,31 @@
+// Exercise for the `vars` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Shadowing lets us reuse a name with a different type
+// Return the length of `spaces` by shadowing it with its length
+fn count_spaces() -> usize {
+    let spaces: &str = "   ";
+    todo!("shadow `spaces` with spaces.len() and return it")
+}
+
+// Variables are immutable by default
+// Start x at 5, change it to 6 and return it
+fn change_x() -> i32 {
+    todo!("declare a mutable x")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shadowing() {
+        assert_eq!(count_spaces(), 3);
+    }
+
+    #[test]
+    fn mutation() {
+        assert_eq!(change_x(), 6);
+    }
+}
@@ -0,0 +1,31 @@
+// Exercise for the `vector_example` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+// Add 10 to every element in place
+fn add_ten(v: &mut Vec<i32>) {
+    todo!()
+}
+
+// Return the element at `index`, or 0 when it is out of bounds, without panicking
+fn get_or_zero(v: &[i32], index: usize) -> i32 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutate() {
+        let mut v: Vec<i32> = vec![10, 20, 30];
+        add_ten(&mut v);
+        assert_eq!(v, vec![20, 30, 40]);
+    }
+
+    #[test]
+    fn get() {
+        assert_eq!(get_or_zero(&[1, 2], 1), 2);
+        assert_eq!(get_or_zero(&[1, 2], 20), 0);
+    }
+}
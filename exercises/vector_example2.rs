@@ -0,0 +1,30 @@
+// Exercise for the `vector_example2` lesson
+// Replace the todo!()s so the tests pass, without changing the tests
+// `learn_rust watch` checks this file every time it is saved
+
+enum SpreadsheetCell {
+    Int(i32),
+    Float(f64),
+    Text(String),
+}
+
+// Describe a cell as "Integer: 1", "Float: 3.5" or "Text: Hello"
+fn describe(cell: &SpreadsheetCell) -> String {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells() {
+        let row: Vec<SpreadsheetCell> = vec![
+            SpreadsheetCell::Int(1),
+            SpreadsheetCell::Float(3.5),
+            SpreadsheetCell::Text(String::from("Hello")),
+        ];
+        let described: Vec<String> = row.iter().map(describe).collect();
+        assert_eq!(described, ["Integer: 1", "Float: 3.5", "Text: Hello"]);
+    }
+}
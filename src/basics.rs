@@ -0,0 +1,157 @@
+//! Variables, data types, functions, control flow and loops.
+
+use std::io::{self, Write};
+
+use crate::tr;
+
+/// Mutable variables, shadowing and constants.
+pub fn vars(out: &mut dyn Write) -> io::Result<()> {
+    // Variables are immutable by default in Rust
+    // Declare a mutable(mut) variable to allow changes
+    let mut x: i32 = 5;
+    writeln!(out, "{}", tr!("vars.has_value", "x", x))?;
+    x = 6;
+    writeln!(out, "{}", tr!("vars.has_value", "x", x))?;
+
+    // Shadowing allows you to reuse a variable name
+    let y: i32 = 10;
+    writeln!(out, "{}", tr!("vars.has_value", "y", y))?;
+    let y: &str = "ten";
+    writeln!(out, "{}", tr!("vars.has_value", "y", y))?;
+
+    // Constants are immutable and must have a type annotation
+    const EXAMPLE: i32 = 100_000_000; // Using underscores for readability
+    writeln!(out, "{}", tr!("vars.has_value", "EXAMPLE", EXAMPLE))?;
+    Ok(())
+}
+
+/// Scalar types and the different integer literal forms.
+#[allow(clippy::approx_constant)] // 3.14 is just an example value
+pub fn data_types(out: &mut dyn Write) -> io::Result<()> {
+    // Integers
+    // Rust defaults to i32
+    // Signed integers can be positive or negative, Unsigned integers can only be positive
+    let a: i32 = 42; // i8, i16, i32, i64, i128, isize(architecture dependent 32/64 bit)
+    let a1: i32 = 98_222; // Decimal
+    let a2: i32 = 0xff; // Hexadecimal
+    let a3: i32 = 0o77; // Octal
+    let a4: i32 = 0b1111_0000; // Binary
+    let a5: u8 = b'A'; // Byte (ASCII value of 'A')
+    let b: f64 = 3.14; // Floating point
+
+    let c: bool = true; // Boolean
+    let d: char = 'z'; // Character
+    let e: &str = "Hello"; // String slice
+
+    writeln!(
+        out,
+        "a: {}, a1: {}, a2: {}, a3: {}, a4: {}, a5: {}, b: {}, c: {}, d: {}, e: {}",
+        a, a1, a2, a3, a4, a5, b, c, d, e
+    )?;
+    Ok(())
+}
+
+/// Tuples and arrays.
+#[allow(clippy::approx_constant)]
+pub fn compound_data_types(out: &mut dyn Write) -> io::Result<()> {
+    // Tuples
+    let tuple: (i32, f64, &str) = (42, 3.14, "Hello");
+    writeln!(
+        out,
+        "{}",
+        tr!("compound_data_types.tuple", format!("{:?}", tuple))
+    )?;
+    // We can get values from a tuple by two ways
+    // 1. Destructuring
+    let (x, y, z) = tuple;
+    writeln!(out, "{}", tr!("compound_data_types.destructured", x, y, z))?;
+
+    // 2. Indexing
+    writeln!(
+        out,
+        "{}",
+        tr!("compound_data_types.indexed", tuple.0, tuple.1, tuple.2)
+    )?;
+
+    // Arrays
+    // Arrays are fixed-size and must have the same type for all elements
+    // For Dynamic arrays, use Vec<T>
+    let array: [i32; 3] = [1, 2, 3];
+    writeln!(
+        out,
+        "{}",
+        tr!("compound_data_types.array", format!("{:?}", array))
+    )?;
+    Ok(())
+}
+
+// Functions
+// In Rust, functions are declared using the `fn` keyword
+// Are in snake_case
+/// Adds two numbers, printing the operands first.
+pub fn add(out: &mut dyn Write, x: i32, y: i32) -> io::Result<i32> {
+    writeln!(out, "{}", tr!("add.adding", x, y))?; // This is a statement, because this dont return anything
+    // return x + y; // We can return the value of a function using the `return` keyword
+    Ok(x + y) // Or just return the value
+}
+
+// Control flow
+/// `if` / `else if` / `else` and `match`.
+pub fn control_flow(out: &mut dyn Write) -> io::Result<()> {
+    let number: i32 = 5;
+
+    if number < 10 {
+        writeln!(out, "{}", tr!("control_flow.less_than_10"))?;
+    } else if number < 20 {
+        writeln!(out, "{}", tr!("control_flow.between_10_and_20"))?;
+    } else {
+        writeln!(out, "{}", tr!("control_flow.20_or_more"))?;
+    }
+
+    let condition: bool = true;
+    let numb: i32 = if condition { 5 } else { 10 }; // if expressions can return values
+    writeln!(out, "{}", tr!("control_flow.numb_is", numb))?;
+
+    // Match statement
+    match number {
+        1 => writeln!(out, "{}", tr!("control_flow.one"))?,
+        2 => writeln!(out, "{}", tr!("control_flow.two"))?,
+        _ => writeln!(out, "{}", tr!("control_flow.something_else"))?, // _ is a catch-all pattern
+    }
+    Ok(())
+}
+
+// Loops
+// Rust has three types of loops: loop, while, and for
+/// `for` loops over a range and over an array.
+pub fn loops(out: &mut dyn Write) -> io::Result<()> {
+    // loop {
+    //     writeln!(out, "Looping forever")?;
+    // }
+
+    // let mut counter = 0;
+    // let result = loop {
+    //     counter += 1;
+    //     if counter > 5 {
+    //         break counter;
+    //     }
+    //     writeln!(out, "Counter: {}", counter)?;
+    // };
+
+    // writeln!(out, "Result: {}", result)?;
+
+    // while counter != 5 {
+    //     counter += 1;
+    //     writeln!(out, "Counter: {}", counter)?;
+    // }
+
+    for i in 1..=5 {
+        writeln!(out, "{}", tr!("loops.counter", i))?;
+    }
+
+    let a: [i32; 5] = [10, 20, 30, 40, 50];
+    for element in a.iter() {
+        writeln!(out, "{}", tr!("loops.element", element))?;
+    }
+    Ok(())
+}
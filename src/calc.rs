@@ -0,0 +1,1112 @@
+//! A small calculator built on [`Operation`].
+//!
+//! [`parse`] turns text like `20 / 0` into an `Operation`, and [`repl`]
+//! reads one line at a time, executes it in the [`Mode`] and with the
+//! [`NumberType`] of its [`Calculator`] and prints the result. A line can
+//! also assign a variable, like `x = 20`, which later lines can use.
+
+pub mod algebra;
+pub mod bigint;
+pub mod complex;
+pub mod diagnostic;
+pub mod env;
+pub mod lexer;
+pub mod matrix;
+pub mod number;
+pub mod operation;
+pub mod parser;
+pub mod rational;
+pub mod rpn;
+pub mod units;
+pub mod vm;
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+use std::path::Path;
+
+use bigint::BigInt;
+use complex::Complex;
+use diagnostic::{Diagnostic, Span, Style};
+use env::Env;
+use matrix::Matrix;
+use number::{Base, Number};
+pub use operation::{MathError, Mode, Operation};
+pub use parser::{Statement, parse, parse_as, parse_spanned, parse_statement_as};
+use rational::Rational;
+use units::Quantity;
+
+/// Why some input could not be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A character that is not part of any token.
+    UnexpectedChar(char),
+    /// A number was expected, but something else or the end of the input came.
+    ExpectedNumber,
+    /// An operator was expected, but something else or the end of the input came.
+    ExpectedOperator,
+    /// A number outside the range of the named number type.
+    NumberTooLarge(&'static str),
+    /// A number with a fraction, for a number type of whole numbers.
+    NotAWholeNumber(&'static str),
+    /// A `(` without its `)`.
+    UnclosedParen,
+    /// A `)` without its `(`.
+    UnmatchedParen,
+    /// `let` without a `name = value` after it.
+    ExpectedAssignment,
+    /// A hex, octal or binary literal for the named type, which only reads decimals.
+    NotDecimal(&'static str),
+    /// A digit that does not exist in the named base, like the 2 in `0b12`.
+    InvalidDigit(&'static str),
+    /// A matrix literal for the named type, which has no matrices.
+    NoMatrices(&'static str),
+    /// A `[` without its `]`.
+    UnclosedBracket,
+    /// A matrix literal that is not a grid of numbers, and why.
+    InvalidMatrix(&'static str),
+    /// An expression nested deeper than the given number of levels.
+    TooDeep(usize),
+    /// A literal with an exponent like `1e5` for the named integer type.
+    Exponent(&'static str),
+}
+
+/// A parse error and where it happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseError {
+    /// What went wrong.
+    pub kind: ParseErrorKind,
+    /// Byte offset of the problem in the input, its length at the end of the input.
+    pub offset: usize,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character `{}`", c),
+            ParseErrorKind::ExpectedNumber => write!(f, "expected a number, a name or `(`"),
+            ParseErrorKind::ExpectedOperator => write!(
+                f,
+                "expected one of `+`, `-`, `*`, `/`, `%`, `^`, `&`, `|`, `xor`, `<<`, `>>` or `)`"
+            ),
+            ParseErrorKind::NumberTooLarge(name) => write!(f, "number is too large for {}", name),
+            ParseErrorKind::NotAWholeNumber(name) => write!(f, "{} numbers have no fraction", name),
+            ParseErrorKind::UnclosedParen => write!(f, "this `(` is never closed"),
+            ParseErrorKind::UnmatchedParen => write!(f, "this `)` has no matching `(`"),
+            ParseErrorKind::ExpectedAssignment => write!(f, "expected `name = value` after `let`"),
+            ParseErrorKind::NotDecimal(name) => {
+                write!(f, "{} numbers are written in decimal", name)
+            }
+            ParseErrorKind::InvalidDigit(base) => write!(f, "invalid digit for a {} number", base),
+            ParseErrorKind::NoMatrices(name) => write!(f, "{} has no matrices", name),
+            ParseErrorKind::UnclosedBracket => write!(f, "this `[` is never closed"),
+            ParseErrorKind::InvalidMatrix(why) => write!(f, "invalid matrix: {}", why),
+            ParseErrorKind::TooDeep(levels) => {
+                write!(f, "nested deeper than {} levels", levels)
+            }
+            ParseErrorKind::Exponent(name) => {
+                write!(f, "{} numbers are written without an exponent", name)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at byte {}", self.kind, self.offset)
+    }
+}
+
+impl Error for ParseError {}
+
+/// The number type a [`Calculator`] computes with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NumberType {
+    /// `i8`
+    I8,
+    /// `i16`
+    I16,
+    /// `i32`, the type of the enums lesson.
+    #[default]
+    I32,
+    /// `i64`
+    I64,
+    /// `i128`
+    I128,
+    /// `f64`
+    F64,
+    /// [`Rational`]
+    Rational,
+    /// [`BigInt`], whole numbers that never overflow
+    BigInt,
+    /// [`Quantity`], numbers with units like `3 km`
+    Quantity,
+    /// [`Matrix`], matrices and vectors of fractions
+    Matrix,
+    /// [`Complex`], numbers with an imaginary part like `3 + 4i`
+    Complex,
+}
+
+impl NumberType {
+    /// Every number type.
+    pub const ALL: [NumberType; 11] = [
+        NumberType::I8,
+        NumberType::I16,
+        NumberType::I32,
+        NumberType::I64,
+        NumberType::I128,
+        NumberType::F64,
+        NumberType::Rational,
+        NumberType::BigInt,
+        NumberType::Quantity,
+        NumberType::Matrix,
+        NumberType::Complex,
+    ];
+
+    /// The name of the type, e.g. `f64`.
+    pub fn name(self) -> &'static str {
+        match self {
+            NumberType::I8 => i8::NAME,
+            NumberType::I16 => i16::NAME,
+            NumberType::I32 => i32::NAME,
+            NumberType::I64 => i64::NAME,
+            NumberType::I128 => i128::NAME,
+            NumberType::F64 => f64::NAME,
+            NumberType::Rational => Rational::NAME,
+            NumberType::BigInt => BigInt::NAME,
+            NumberType::Quantity => Quantity::NAME,
+            NumberType::Matrix => Matrix::NAME,
+            NumberType::Complex => Complex::NAME,
+        }
+    }
+
+    /// Parses a [`name`](NumberType::name).
+    pub fn from_name(name: &str) -> Option<NumberType> {
+        NumberType::ALL.into_iter().find(|t| t.name() == name)
+    }
+}
+
+/// How a [`Calculator`] reads operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Notation {
+    /// The operator between its operands: `(3 + 4) * 2`.
+    #[default]
+    Infix,
+    /// Reverse Polish notation on a stack: `3 4 + 2 *`, see [`rpn`].
+    Rpn,
+}
+
+impl Notation {
+    /// Every notation.
+    pub const ALL: [Notation; 2] = [Notation::Infix, Notation::Rpn];
+
+    /// The name of the notation, e.g. `rpn`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Notation::Infix => "infix",
+            Notation::Rpn => "rpn",
+        }
+    }
+
+    /// Parses a [`name`](Notation::name).
+    pub fn from_name(name: &str) -> Option<Notation> {
+        Notation::ALL.into_iter().find(|n| n.name() == name)
+    }
+}
+
+/// How a [`Calculator`] on [`Rational`] prints its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FractionStyle {
+    /// In lowest terms, like `2/3`.
+    #[default]
+    Fraction,
+    /// Rounded to this many digits after the point, like `0.667`.
+    Decimal(usize),
+    /// Exact, with the repeating digits in parentheses, like `0.(6)`.
+    Repeating,
+}
+
+impl FractionStyle {
+    /// The most digits `decimal <digits>` rounds to. Writing more would take
+    /// as long as the number of digits asked for.
+    pub const MAX_DIGITS: usize = 1000;
+
+    /// Writes `value` in this style.
+    pub fn format(self, value: &Rational) -> String {
+        match self {
+            FractionStyle::Fraction => value.to_string(),
+            FractionStyle::Decimal(precision) => value.to_decimal(precision),
+            FractionStyle::Repeating => value.to_repeating(),
+        }
+    }
+
+    /// Parses `fraction`, `repeating` or `decimal <digits>`, with at most
+    /// [`MAX_DIGITS`](FractionStyle::MAX_DIGITS) digits.
+    pub fn from_name(name: &str) -> Option<FractionStyle> {
+        match name.split_whitespace().collect::<Vec<&str>>().as_slice() {
+            ["fraction"] => Some(FractionStyle::Fraction),
+            ["repeating"] => Some(FractionStyle::Repeating),
+            ["decimal", digits] => digits
+                .parse()
+                .ok()
+                .filter(|&digits| digits <= FractionStyle::MAX_DIGITS)
+                .map(FractionStyle::Decimal),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FractionStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FractionStyle::Fraction => write!(f, "fraction"),
+            FractionStyle::Decimal(precision) => write!(f, "decimal {}", precision),
+            FractionStyle::Repeating => write!(f, "repeating"),
+        }
+    }
+}
+
+// The variables and the RPN stack of a session, in one number type, and
+// for every assignment the variable and the value it had before
+#[derive(Debug, Clone)]
+struct Session<T> {
+    env: Env<T>,
+    stack: Vec<T>,
+    undo: Vec<(String, Option<T>)>,
+}
+
+impl<T: Number> Session<T> {
+    fn new() -> Session<T> {
+        Session {
+            env: Env::new(),
+            stack: Vec::new(),
+            undo: Vec::new(),
+        }
+    }
+}
+
+// A session in the current number type
+#[derive(Debug, Clone)]
+enum Bindings {
+    I8(Session<i8>),
+    I16(Session<i16>),
+    I32(Session<i32>),
+    I64(Session<i64>),
+    I128(Session<i128>),
+    F64(Session<f64>),
+    Rational(Session<Rational>),
+    BigInt(Session<BigInt>),
+    Quantity(Session<Quantity>),
+    Matrix(Session<Matrix>),
+    Complex(Session<Complex>),
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings::I32(Session::new())
+    }
+}
+
+// Runs `$body` with `$session` bound to the session of whichever number type
+// is current. The arms are the same code, compiled once for every type.
+// `type $number => $body` names the number type instead, for code that needs
+// the type but none of the session
+macro_rules! with_session {
+    ($bindings:expr, $session:ident => $body:expr) => {
+        match $bindings {
+            Bindings::I8($session) => $body,
+            Bindings::I16($session) => $body,
+            Bindings::I32($session) => $body,
+            Bindings::I64($session) => $body,
+            Bindings::I128($session) => $body,
+            Bindings::F64($session) => $body,
+            Bindings::Rational($session) => $body,
+            Bindings::BigInt($session) => $body,
+            Bindings::Quantity($session) => $body,
+            Bindings::Matrix($session) => $body,
+            Bindings::Complex($session) => $body,
+        }
+    };
+    ($bindings:expr, type $number:ident => $body:expr) => {
+        match $bindings {
+            Bindings::I8(_) => {
+                type $number = i8;
+                $body
+            }
+            Bindings::I16(_) => {
+                type $number = i16;
+                $body
+            }
+            Bindings::I32(_) => {
+                type $number = i32;
+                $body
+            }
+            Bindings::I64(_) => {
+                type $number = i64;
+                $body
+            }
+            Bindings::I128(_) => {
+                type $number = i128;
+                $body
+            }
+            Bindings::F64(_) => {
+                type $number = f64;
+                $body
+            }
+            Bindings::Rational(_) => {
+                type $number = Rational;
+                $body
+            }
+            Bindings::BigInt(_) => {
+                type $number = BigInt;
+                $body
+            }
+            Bindings::Quantity(_) => {
+                type $number = Quantity;
+                $body
+            }
+            Bindings::Matrix(_) => {
+                type $number = Matrix;
+                $body
+            }
+            Bindings::Complex(_) => {
+                type $number = Complex;
+                $body
+            }
+        }
+    };
+}
+
+/// The state of a calculator session.
+#[derive(Debug, Clone, Default)]
+pub struct Calculator {
+    /// How results outside the range of the number type are handled.
+    pub mode: Mode,
+    /// How results are printed when the number type is [`NumberType::Rational`].
+    pub fractions: FractionStyle,
+    /// The base integer results are printed in. Other types print decimals.
+    pub base: Base,
+    /// Whether lines are infix operations or RPN.
+    pub notation: Notation,
+    /// How errors are reported.
+    pub errors: Style,
+    session: Bindings,
+    // The input and printed result of `$1`, `$2` and so on
+    history: Vec<(String, String)>,
+    // Every line that did not fail, with what it printed
+    transcript: Vec<(String, String)>,
+}
+
+impl Calculator {
+    /// A calculator on `i32` in [`Mode::Checked`], without variables.
+    pub fn new() -> Calculator {
+        Calculator::default()
+    }
+
+    /// The number type operations are computed with.
+    pub fn number(&self) -> NumberType {
+        match self.session {
+            Bindings::I8(_) => NumberType::I8,
+            Bindings::I16(_) => NumberType::I16,
+            Bindings::I32(_) => NumberType::I32,
+            Bindings::I64(_) => NumberType::I64,
+            Bindings::I128(_) => NumberType::I128,
+            Bindings::F64(_) => NumberType::F64,
+            Bindings::Rational(_) => NumberType::Rational,
+            Bindings::BigInt(_) => NumberType::BigInt,
+            Bindings::Quantity(_) => NumberType::Quantity,
+            Bindings::Matrix(_) => NumberType::Matrix,
+            Bindings::Complex(_) => NumberType::Complex,
+        }
+    }
+
+    /// Switches to another number type, converting the variables and the
+    /// RPN stack.
+    ///
+    /// Each value is read back as input in the new type, so `20/3` becomes 6
+    /// as an `i32`. Returns the names of the variables that could not be
+    /// converted and were dropped, like `0.5` for an `i32`. Values on the
+    /// stack and numbered results like `$1` that can not be converted are
+    /// dropped too, and so are the steps of `undo` that would restore such a
+    /// value. `history` marks the results that were dropped.
+    pub fn set_number(&mut self, number: NumberType) -> Vec<String> {
+        let (lines, stack, undo) = with_session!(&self.session, s => (
+            to_lines(&s.env),
+            to_texts(&s.stack),
+            to_undo(&s.undo),
+        ));
+        let mut dropped: Vec<String> = Vec::new();
+        let mode: Mode = self.mode;
+        self.session = match number {
+            NumberType::I8 => Bindings::I8(from_text(&lines, &stack, &undo, mode, &mut dropped)),
+            NumberType::I16 => Bindings::I16(from_text(&lines, &stack, &undo, mode, &mut dropped)),
+            NumberType::I32 => Bindings::I32(from_text(&lines, &stack, &undo, mode, &mut dropped)),
+            NumberType::I64 => Bindings::I64(from_text(&lines, &stack, &undo, mode, &mut dropped)),
+            NumberType::I128 => {
+                Bindings::I128(from_text(&lines, &stack, &undo, mode, &mut dropped))
+            }
+            NumberType::F64 => Bindings::F64(from_text(&lines, &stack, &undo, mode, &mut dropped)),
+            NumberType::Rational => {
+                Bindings::Rational(from_text(&lines, &stack, &undo, mode, &mut dropped))
+            }
+            NumberType::BigInt => {
+                Bindings::BigInt(from_text(&lines, &stack, &undo, mode, &mut dropped))
+            }
+            NumberType::Quantity => {
+                Bindings::Quantity(from_text(&lines, &stack, &undo, mode, &mut dropped))
+            }
+            NumberType::Matrix => {
+                Bindings::Matrix(from_text(&lines, &stack, &undo, mode, &mut dropped))
+            }
+            NumberType::Complex => {
+                Bindings::Complex(from_text(&lines, &stack, &undo, mode, &mut dropped))
+            }
+        };
+        dropped.retain(|name| !env::is_result(name));
+        dropped
+    }
+
+    /// Evaluates one line of input and returns what to print.
+    ///
+    /// A line is an operation like `y * 3`, an assignment like `x = 20` or
+    /// `let y = x / 4`, or one of the commands:
+    ///
+    /// - `mode [name]` shows or changes the [`Mode`]
+    /// - `type [name]` shows or changes the [`NumberType`]
+    /// - `show [style]` shows or changes the [`FractionStyle`]
+    /// - `base [name]` shows or changes the [`Base`] of integer results
+    /// - `notation [name]` shows or changes the [`Notation`]
+    /// - `errors [style]` shows or changes the [`Style`] of error reports
+    /// - `vars` lists the variables
+    /// - `save <file>` and `load <file>` write and read the variables
+    /// - `dis <operation>` shows the bytecode the operation compiles to
+    /// - `bench <operation>` times the operation on the tree and on the VM
+    /// - `simplify <operation>` rewrites the operation without computing it
+    /// - `derive <name> <operation>` differentiates with respect to `name`
+    /// - `history` lists the numbered results, which `$1`, `$2` and so on
+    ///   refer to. In RPN the result of a line is the top of the stack
+    /// - `undo` gives the variable of the last assignment its value back
+    /// - `transcript save <file>` writes every line of the session with
+    ///   what it printed, `transcript load <file>` runs the lines of such a
+    ///   file again to resume the session. It only loads into a session
+    ///   without variables and results, so that `$1` means the same, and
+    ///   skips the `save` lines instead of writing their files again
+    ///
+    /// With [`NumberType::Quantity`], `<operation> in <unit>` converts the
+    /// result to a unit like `km` or `km/h`. With [`NumberType::Complex`],
+    /// `<operation> in polar` prints the result as `polar(abs, arg)`. Both
+    /// are numbered results, whose `$N` is the value of the operation.
+    ///
+    /// In [`Notation::Rpn`] a line that is no command is evaluated on the
+    /// stack, which is printed afterwards.
+    pub fn eval_line(&mut self, line: &str) -> String {
+        let line: &str = line.trim();
+        self.run_line(line)
+            .unwrap_or_else(|e| e.render(line, self.errors))
+    }
+
+    // Evaluates a line and adds it to the transcript when it succeeds
+    fn run_line(&mut self, line: &str) -> Result<String, Diagnostic> {
+        let (command, argument) = split_command(line);
+        if command == "transcript" {
+            return self.transcript_command(argument).map_err(Diagnostic::from);
+        }
+        let reply: String = self.run_command(line, command, argument)?;
+        self.transcript.push((line.to_string(), reply.clone()));
+        Ok(reply)
+    }
+
+    fn run_command(
+        &mut self,
+        line: &str,
+        command: &str,
+        argument: &str,
+    ) -> Result<String, Diagnostic> {
+        let mode: Mode = self.mode;
+        match command {
+            "mode" => self.mode_command(argument).map_err(Diagnostic::from),
+            "type" => self.type_command(argument).map_err(Diagnostic::from),
+            "show" => self.show_command(argument).map_err(Diagnostic::from),
+            "base" => self.base_command(argument).map_err(Diagnostic::from),
+            "notation" => self.notation_command(argument).map_err(Diagnostic::from),
+            "errors" => self.errors_command(argument).map_err(Diagnostic::from),
+            "vars" if argument.is_empty() => Ok(self.vars_command()),
+            "history" if argument.is_empty() => Ok(self.history_command()),
+            "undo" if argument.is_empty() => {
+                with_session!(&mut self.session, s => undo(s)).map_err(Diagnostic::from)
+            }
+            "save" => self.save_command(argument).map_err(Diagnostic::from),
+            "load" => self.load_command(argument).map_err(Diagnostic::from),
+            "simplify" | "derive" => {
+                with_session!(&self.session, type T => symbolic::<T>(command, line, argument))
+            }
+            "dis" | "bench" => {
+                with_session!(&self.session, s => compiled(command, line, argument, &s.env, mode))
+            }
+            _ => {
+                let (notation, base, fractions) = (self.notation, self.base, self.fractions);
+                let history: &mut Vec<(String, String)> = &mut self.history;
+                match &mut self.session {
+                    Bindings::I8(s) => eval(line, s, history, notation, mode, |v| v.to_base(base)),
+                    Bindings::I16(s) => eval(line, s, history, notation, mode, |v| v.to_base(base)),
+                    Bindings::I32(s) => eval(line, s, history, notation, mode, |v| v.to_base(base)),
+                    Bindings::I64(s) => eval(line, s, history, notation, mode, |v| v.to_base(base)),
+                    Bindings::I128(s) => {
+                        eval(line, s, history, notation, mode, |v| v.to_base(base))
+                    }
+                    Bindings::F64(s) => eval(line, s, history, notation, mode, |v| v.to_string()),
+                    Bindings::Rational(s) => {
+                        eval(line, s, history, notation, mode, |r| fractions.format(r))
+                    }
+                    Bindings::BigInt(s) => {
+                        eval(line, s, history, notation, mode, |v| v.to_string())
+                    }
+                    Bindings::Quantity(s) => match line.rsplit_once(" in ") {
+                        Some((operation, unit)) if notation == Notation::Infix => {
+                            let (value, shown) = convert(line, operation, unit, &s.env, mode)?;
+                            Ok(record(line, value, shown, &mut s.env, history))
+                        }
+                        _ => eval(line, s, history, notation, mode, |v| v.to_string()),
+                    },
+                    Bindings::Matrix(s) => {
+                        eval(line, s, history, notation, mode, |v| v.to_string())
+                    }
+                    Bindings::Complex(s) => match line.rsplit_once(" in ") {
+                        Some((operation, "polar")) if notation == Notation::Infix => {
+                            let (value, shown) = to_polar(line, operation, &s.env, mode)?;
+                            Ok(record(line, value, shown, &mut s.env, history))
+                        }
+                        _ => eval(line, s, history, notation, mode, |v| v.to_string()),
+                    },
+                }
+            }
+        }
+    }
+
+    fn mode_command(&mut self, name: &str) -> Result<String, String> {
+        if !name.is_empty() {
+            self.mode = Mode::from_name(name).ok_or_else(|| {
+                format!(
+                    "unknown mode `{}`, expected one of: {}",
+                    name,
+                    Mode::ALL.map(Mode::name).join(", ")
+                )
+            })?;
+        }
+        Ok(format!("mode: {}", self.mode.name()))
+    }
+
+    fn show_command(&mut self, style: &str) -> Result<String, String> {
+        if !style.is_empty() {
+            self.fractions = FractionStyle::from_name(style).ok_or_else(|| {
+                format!(
+                    "unknown style `{}`, expected fraction, decimal <digits> or repeating, \
+                     with at most {} digits",
+                    style,
+                    FractionStyle::MAX_DIGITS
+                )
+            })?;
+        }
+        Ok(format!("show: {}", self.fractions))
+    }
+
+    fn base_command(&mut self, name: &str) -> Result<String, String> {
+        if !name.is_empty() {
+            self.base = Base::from_name(name).ok_or_else(|| {
+                format!(
+                    "unknown base `{}`, expected one of: {}",
+                    name,
+                    Base::ALL.map(Base::name).join(", ")
+                )
+            })?;
+        }
+        Ok(format!("base: {}", self.base.name()))
+    }
+
+    fn notation_command(&mut self, name: &str) -> Result<String, String> {
+        if !name.is_empty() {
+            self.notation = Notation::from_name(name).ok_or_else(|| {
+                format!(
+                    "unknown notation `{}`, expected one of: {}",
+                    name,
+                    Notation::ALL.map(Notation::name).join(", ")
+                )
+            })?;
+        }
+        Ok(format!("notation: {}", self.notation.name()))
+    }
+
+    fn type_command(&mut self, name: &str) -> Result<String, String> {
+        if name.is_empty() {
+            return Ok(format!("type: {}", self.number().name()));
+        }
+        let number: NumberType = NumberType::from_name(name).ok_or_else(|| {
+            format!(
+                "unknown type `{}`, expected one of: {}",
+                name,
+                NumberType::ALL.map(NumberType::name).join(", ")
+            )
+        })?;
+        let steps: usize = with_session!(&self.session, s => s.undo.len());
+        let dropped: Vec<String> = self.set_number(number);
+        let lost: usize = steps - with_session!(&self.session, s => s.undo.len());
+        let mut reply: String = format!("type: {}", number.name());
+        if !dropped.is_empty() {
+            reply.push_str(&format!(
+                " (dropped {}, not a valid {})",
+                dropped.join(", "),
+                number.name()
+            ));
+        }
+        match lost {
+            0 => {}
+            1 => reply.push_str(" (undo lost a step)"),
+            n => reply.push_str(&format!(" (undo lost {} steps)", n)),
+        }
+        Ok(reply)
+    }
+
+    fn errors_command(&mut self, name: &str) -> Result<String, String> {
+        if !name.is_empty() {
+            self.errors = Style::from_name(name).ok_or_else(|| {
+                format!(
+                    "unknown style `{}`, expected one of: {}",
+                    name,
+                    Style::ALL.map(Style::name).join(", ")
+                )
+            })?;
+        }
+        Ok(format!("errors: {}", self.errors.name()))
+    }
+
+    fn vars_command(&self) -> String {
+        let mut lines: Vec<(String, String)> = with_session!(&self.session, s => to_lines(&s.env));
+        lines.retain(|(name, _)| !env::is_result(name));
+        if lines.is_empty() {
+            return String::from("no variables");
+        }
+        lines
+            .iter()
+            .map(|(name, value)| format!("{} = {}", name, value))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn save_command(&self, path: &str) -> Result<String, String> {
+        if path.is_empty() {
+            return Err(String::from("`save` expects a file name"));
+        }
+        let path: &Path = Path::new(path);
+        let (saved, count) =
+            with_session!(&self.session, s => (s.env.save(path), s.env.variables().len()));
+        saved.map_err(|e| format!("can not write {}: {}", path.display(), e))?;
+        Ok(format!("saved {} variables to {}", count, path.display()))
+    }
+
+    fn load_command(&mut self, path: &str) -> Result<String, String> {
+        if path.is_empty() {
+            return Err(String::from("`load` expects a file name"));
+        }
+        let path: &Path = Path::new(path);
+        let mode: Mode = self.mode;
+        let count: usize = with_session!(&mut self.session, s => load_into(&mut s.env, path, mode))
+            .map_err(|e| format!("can not load {}: {}", path.display(), e))?;
+        Ok(format!(
+            "loaded {} variables from {}",
+            count,
+            path.display()
+        ))
+    }
+
+    fn history_command(&self) -> String {
+        if self.history.is_empty() {
+            return String::from("no history");
+        }
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(i, (input, result))| {
+                let name: String = format!("${}", i + 1);
+                if with_session!(&self.session, s => s.env.get(&name).is_some()) {
+                    format!("{} = {}   ({})", name, result, input)
+                } else {
+                    format!("{} = {}   ({}, dropped by `type`)", name, result, input)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn transcript_command(&mut self, argument: &str) -> Result<String, String> {
+        let (action, path) = argument.split_once(' ').unwrap_or((argument, ""));
+        let path: &Path = Path::new(path.trim());
+        let usage = || String::from("`transcript` expects `save <file>` or `load <file>`");
+        if path.as_os_str().is_empty() {
+            return Err(usage());
+        }
+        match action {
+            "save" => {
+                save_transcript(&self.transcript, path)
+                    .map_err(|e| format!("can not write {}: {}", path.display(), e))?;
+                Ok(format!(
+                    "saved {} lines to {}",
+                    self.transcript.len(),
+                    path.display()
+                ))
+            }
+            "load" => {
+                // `$1` in the transcript must be the first result again
+                let empty: bool = self.history.is_empty()
+                    && with_session!(&self.session, s => s.env.variables().is_empty());
+                if !empty {
+                    return Err(String::from(
+                        "`transcript load` needs a session without variables and results",
+                    ));
+                }
+                let text: String = fs::read_to_string(path)
+                    .map_err(|e| format!("can not load {}: {}", path.display(), e))?;
+                let (mut replayed, mut failed) = (0, 0);
+                // A transcript never loads another one, which could be
+                // itself, and does not overwrite the files it saved
+                let lines = text
+                    .lines()
+                    .filter_map(|line| line.strip_prefix("> "))
+                    .map(str::trim)
+                    .filter(|line| {
+                        let (command, _) = split_command(line);
+                        command != "transcript" && command != "save"
+                    });
+                for line in lines {
+                    replayed += 1;
+                    if self.run_line(line).is_err() {
+                        failed += 1;
+                    }
+                }
+                let mut reply: String =
+                    format!("replayed {} lines from {}", replayed, path.display());
+                if failed > 0 {
+                    reply.push_str(&format!(", {} failed", failed));
+                }
+                Ok(reply)
+            }
+            _ => Err(usage()),
+        }
+    }
+}
+
+// Writes every line after a `> ` prompt, followed by what it printed, the
+// way the REPL shows it. Loading runs the lines after a prompt again
+fn save_transcript(transcript: &[(String, String)], path: &Path) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = fs::File::create(path)?;
+    for (line, reply) in transcript {
+        writeln!(file, "> {}", line)?;
+        writeln!(file, "{}", reply)?;
+    }
+    Ok(())
+}
+
+// Runs one line: a statement, whose assignments change the variables, or
+// RPN words, which change the stack. Results are written by `show` and
+// numbered in `history`, errors of a statement point at the part of the
+// line that failed
+fn eval<T: Number>(
+    line: &str,
+    session: &mut Session<T>,
+    history: &mut Vec<(String, String)>,
+    notation: Notation,
+    mode: Mode,
+    show: impl Fn(&T) -> String,
+) -> Result<String, Diagnostic> {
+    if notation == Notation::Rpn {
+        rpn::eval_located(line, &mut session.stack, &session.env, mode)
+            .map_err(|(span, e)| Diagnostic::rpn(line, &e, span, T::NAME))?;
+        let Some(top) = session.stack.last() else {
+            return Ok(String::from("stack: empty"));
+        };
+        // The top of the stack is the result of the line
+        record(line, top.clone(), show(top), &mut session.env, history);
+        let values: Vec<String> = session.stack.iter().map(show).collect();
+        return Ok(format!("stack: {}", values.join(" ")));
+    }
+    let env: &mut Env<T> = &mut session.env;
+    let (statement, spans) = parse_spanned::<T>(line).map_err(|e| Diagnostic::parse(line, &e))?;
+    let (name, operation) = match statement {
+        Statement::Let { name, value } => (Some(name), value),
+        Statement::Operation(operation) => (None, operation),
+    };
+    let value: T = operation.execute_in(env, mode).map_err(|e| {
+        let span: Option<Span> = diagnostic::locate(&operation, &spans, env, mode);
+        Diagnostic::math(&e, span, T::NAME)
+    })?;
+    let shown: String = record(line, value.clone(), show(&value), env, history);
+    match name {
+        Some(name) => {
+            let reply: String = format!("{} = {}", name, shown);
+            let before: Option<T> = env.set(&name, value);
+            session.undo.push((name, before));
+            Ok(reply)
+        }
+        None => Ok(shown),
+    }
+}
+
+// Numbers the result of a line as the next `$N` and returns how it is shown
+fn record<T: Number>(
+    line: &str,
+    value: T,
+    shown: String,
+    env: &mut Env<T>,
+    history: &mut Vec<(String, String)>,
+) -> String {
+    history.push((line.to_string(), shown.clone()));
+    env.set(&format!("${}", history.len()), value);
+    shown
+}
+
+// Gives the variable of the last assignment its value back, or removes it
+// when it was new
+fn undo<T: Number>(session: &mut Session<T>) -> Result<String, String> {
+    let (name, before) = session
+        .undo
+        .pop()
+        .ok_or_else(|| String::from("nothing to undo"))?;
+    match before {
+        Some(value) => {
+            let reply: String = format!("undo: {} = {}", name, value);
+            session.env.set(&name, value);
+            Ok(reply)
+        }
+        None => {
+            session.env.remove(&name);
+            Ok(format!("undo: removed {}", name))
+        }
+    }
+}
+
+// Parses and computes `part`, which starts at byte `offset` of `line`, so
+// that errors point into the whole line like those of `eval`
+fn compute_part<T: Number>(
+    line: &str,
+    part: &str,
+    offset: usize,
+    env: &Env<T>,
+    mode: Mode,
+) -> Result<T, Diagnostic> {
+    let (statement, spans) = parse_spanned::<T>(part).map_err(|e| part_error(line, e, offset))?;
+    let Statement::Operation(operation) = statement else {
+        return Err(Diagnostic::from(String::from(
+            "`in` converts an operation, not an assignment",
+        )));
+    };
+    operation.execute_in(env, mode).map_err(|e| {
+        let span: Option<Span> =
+            diagnostic::locate(&operation, &spans, env, mode).map(|span| Span {
+                start: span.start + offset,
+                end: span.end + offset,
+            });
+        Diagnostic::math(&e, span, T::NAME)
+    })
+}
+
+// The report of a parse error in a part of `line` that starts at byte `offset`
+fn part_error(line: &str, error: ParseError, offset: usize) -> Diagnostic {
+    let error: ParseError = ParseError {
+        offset: error.offset + offset,
+        ..error
+    };
+    Diagnostic::parse(line, &error)
+}
+
+// Runs `<operation> in <unit>`, where the unit is an operation on units only.
+// Returns the value, which `$N` refers to, and the value in the unit
+fn convert(
+    line: &str,
+    operation: &str,
+    unit: &str,
+    env: &Env<Quantity>,
+    mode: Mode,
+) -> Result<(Quantity, String), Diagnostic> {
+    // The unit ends the line
+    let unit: &str = unit.trim();
+    let unit_start: usize = line.len() - unit.len();
+    let value: Quantity = compute_part(line, operation, 0, env, mode)?;
+    let size: Quantity = compute_part(line, unit, unit_start, &Env::new(), mode)?;
+    let count: f64 = value.in_unit(&size).map_err(|e| {
+        let span: Span = Span {
+            start: 0,
+            end: line.len(),
+        };
+        Diagnostic::math(&e, Some(span), Quantity::NAME)
+    })?;
+    Ok((value, format!("{} {}", count, unit)))
+}
+
+// Runs `<operation> in polar`, returning the value and its polar form
+fn to_polar(
+    line: &str,
+    operation: &str,
+    env: &Env<Complex>,
+    mode: Mode,
+) -> Result<(Complex, String), Diagnostic> {
+    let value: Complex = compute_part(line, operation, 0, env, mode)?;
+    let polar: String = format!("polar({}, {})", value.modulus(), value.argument());
+    Ok((value, polar))
+}
+
+// Iterations of the `bench` command
+const BENCH_ITERATIONS: u32 = 100_000;
+
+// Runs `dis` or `bench` on an operation, the argument that ends `line`
+fn compiled<T: Number>(
+    command: &str,
+    line: &str,
+    argument: &str,
+    env: &Env<T>,
+    mode: Mode,
+) -> Result<String, Diagnostic> {
+    if argument.is_empty() {
+        return Err(Diagnostic::from(format!(
+            "`{}` expects an operation",
+            command
+        )));
+    }
+    let offset: usize = line.len() - argument.len();
+    let operation: Operation<T> =
+        parse_as::<T>(argument).map_err(|e| part_error(line, e, offset))?;
+    if command == "dis" {
+        let listing: String = vm::compile(&operation).to_string();
+        Ok(listing.trim_end().to_string())
+    } else {
+        Ok(vm::benchmark(&operation, env, mode, BENCH_ITERATIONS).to_string())
+    }
+}
+
+// Splits a line into the command and its argument. `save = 3` assigns a
+// variable named like a command, so it has no command
+fn split_command(line: &str) -> (&str, &str) {
+    match line.split_once(' ') {
+        Some((_, argument)) if argument.trim_start().starts_with('=') => ("", line),
+        Some((command, argument)) => (command, argument.trim()),
+        None => (line, ""),
+    }
+}
+
+// Runs `simplify` or `derive`, which only need the number type of the
+// session, on the argument that ends `line`
+fn symbolic<T: Number>(command: &str, line: &str, argument: &str) -> Result<String, Diagnostic> {
+    let (var, operation) = if command == "derive" {
+        argument.split_once(' ').unwrap_or((argument, ""))
+    } else {
+        ("", argument)
+    };
+    if operation.trim().is_empty() {
+        return Err(Diagnostic::from(match command {
+            "derive" => String::from("`derive` expects a name and an operation"),
+            _ => String::from("`simplify` expects an operation"),
+        }));
+    }
+    let offset: usize = line.len() - operation.len();
+    let operation: Operation<T> =
+        parse_as::<T>(operation).map_err(|e| part_error(line, e, offset))?;
+    if command == "derive" {
+        algebra::derive(&operation, var)
+            .map(|d| d.to_string())
+            .map_err(|e| Diagnostic::from(e.to_string()))
+    } else {
+        Ok(algebra::simplify(&operation).to_string())
+    }
+}
+
+// The variables as (name, value) text, sorted by name
+fn to_lines<T: Number>(env: &Env<T>) -> Vec<(String, String)> {
+    env.sorted()
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+fn to_texts<T: Number>(values: &[T]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn to_undo<T: Number>(undo: &[(String, Option<T>)]) -> Vec<(String, Option<String>)> {
+    undo.iter()
+        .map(|(name, before)| (name.clone(), before.as_ref().map(T::to_string)))
+        .collect()
+}
+
+// Reads a session back in another number type, collecting the names of the
+// variables that fail
+fn from_text<T: Number>(
+    lines: &[(String, String)],
+    stack: &[String],
+    undo: &[(String, Option<String>)],
+    mode: Mode,
+    dropped: &mut Vec<String>,
+) -> Session<T> {
+    let convert = |text: &str| {
+        parse_as::<T>(text)
+            .ok()
+            .and_then(|value| value.execute_with(mode).ok())
+    };
+    let mut session: Session<T> = Session::new();
+    for (name, text) in lines {
+        match convert(text) {
+            Some(value) => {
+                session.env.set(name, value);
+            }
+            None => dropped.push(name.clone()),
+        }
+    }
+    session.stack = stack.iter().filter_map(|text| convert(text)).collect();
+    // A step that removes a variable always converts, one that restores a
+    // value only when the value does
+    session.undo = undo
+        .iter()
+        .filter_map(|(name, before)| match before {
+            None => Some((name.clone(), None)),
+            Some(text) => convert(text).map(|value| (name.clone(), Some(value))),
+        })
+        .collect();
+    session
+}
+
+// Adds the variables of a saved file to `env`, replacing those with the same names
+fn load_into<T: Number>(env: &mut Env<T>, path: &Path, mode: Mode) -> io::Result<usize> {
+    let loaded: Env<T> = Env::load(path, mode)?;
+    for (name, value) in loaded.sorted() {
+        env.set(name, value.clone());
+    }
+    Ok(loaded.len())
+}
+
+/// Reads lines from `input` until it ends or a line says `quit`.
+///
+/// Every line is evaluated by `calculator` and its result or error is written to `out`.
+pub fn repl(
+    calculator: &mut Calculator,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    loop {
+        write!(out, "> ")?;
+        out.flush()?;
+        let mut line: String = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(out)?;
+            return Ok(());
+        }
+
+        let line: &str = line.trim_end_matches(['\r', '\n']);
+        match line.trim() {
+            "" => continue,
+            "quit" | "exit" => return Ok(()),
+            _ => writeln!(out, "{}", calculator.eval_line(line))?,
+        }
+    }
+}
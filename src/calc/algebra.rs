@@ -0,0 +1,398 @@
+//! Rewrites an [`Operation`] with variables without computing it.
+//!
+//! [`simplify`] folds the parts without variables into numbers, drops
+//! identities like `x * 1` and `x + 0`, and sorts the operands of `+` and
+//! `*`, so `1 * y + 2 + x * 3` and `x * 3 + y + 2` both become
+//! `3 * x + y + 2`. The operands of `*` keep their order for number types
+//! like matrices, where `a * b` is not `b * a`. [`derive`] differentiates
+//! with respect to a variable.
+
+use std::error::Error;
+use std::fmt;
+
+use super::number::Number;
+use super::parser::Build;
+use super::{Mode, Operation};
+
+/// Why an operation could not be differentiated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeriveError {
+    /// `%` has no derivative where it jumps back to zero.
+    Remainder,
+    /// A power whose exponent depends on the variable, like `2^x`, needs logarithms.
+    VariableExponent(String),
+    /// Bitwise operations only exist for integers, which have no derivative.
+    Bitwise,
+    /// A function call whose operands depend on the variable.
+    Function(String),
+    /// A quotient in a number type whose `/` truncates, like `i64`.
+    TruncatingDivision(&'static str),
+}
+
+impl fmt::Display for DeriveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeriveError::Remainder => write!(f, "`%` can not be differentiated"),
+            DeriveError::VariableExponent(var) => {
+                write!(f, "the exponent of a power can not depend on `{}`", var)
+            }
+            DeriveError::Bitwise => write!(f, "bitwise operations can not be differentiated"),
+            DeriveError::Function(name) => write!(f, "`{}` can not be differentiated", name),
+            DeriveError::TruncatingDivision(name) => write!(
+                f,
+                "`/` truncates {} numbers, use `type rational` or `type f64` to differentiate it",
+                name
+            ),
+        }
+    }
+}
+
+impl Error for DeriveError {}
+
+// A small whole number of any number type
+fn constant<T: Number>(text: &str) -> T {
+    T::parse_literal(text).expect("every number type parses 0 and 1")
+}
+
+fn is<T: Number>(operation: &Operation<T>, text: &str) -> bool {
+    matches!(operation, Operation::Value(v) if *v == constant(text))
+}
+
+// Computes an operation without variables. One that fails, like `1 / 0`,
+// stays as it is so that running it still reports the error
+fn fold<T: Number>(operation: Operation<T>) -> Operation<T> {
+    match operation.execute_with(Mode::Checked) {
+        Ok(v) => Operation::Value(v),
+        Err(_) => operation,
+    }
+}
+
+/// Simplifies `operation` to an equal operation in canonical form.
+///
+/// Numbers are computed in [`Mode::Checked`].
+pub fn simplify<T: Number>(operation: &Operation<T>) -> Operation<T> {
+    // This recurses once per operator of a long row like `x - 1 - 2 - 3`,
+    // so every rule lives in a function of its own and keeps its
+    // temporaries out of this frame
+    let (a, b, rule): (&Operation<T>, &Operation<T>, Rule<T>) = match operation {
+        Operation::Value(_) | Operation::Var(_) => return operation.clone(),
+        Operation::Neg(a) => return negated(simplify(a)),
+        // Matrix products keep their order, only neighbouring numbers fold
+        Operation::Mul(a, b) if !T::COMMUTATIVE_MUL => (a, b, |a, b| binary(a, b, Operation::Mul)),
+        Operation::Add(..) | Operation::Mul(..) => return commutative(operation),
+        Operation::Call { function, args } => {
+            return call(function, args.iter().map(simplify).collect());
+        }
+        Operation::Sub { first, second } => (first, second, difference),
+        Operation::Div { divident, divisor } => (divident, divisor, ratio),
+        Operation::Rem { divident, divisor } => (divident, divisor, |divident, divisor| {
+            binary(divident, divisor, |divident, divisor| Operation::Rem {
+                divident,
+                divisor,
+            })
+        }),
+        Operation::BitAnd(a, b) => (a, b, |a, b| binary(a, b, Operation::BitAnd)),
+        Operation::BitOr(a, b) => (a, b, |a, b| binary(a, b, Operation::BitOr)),
+        Operation::BitXor(a, b) => (a, b, |a, b| binary(a, b, Operation::BitXor)),
+        Operation::Shl { value, amount } => (value, amount, |value, amount| {
+            binary(value, amount, |value, amount| Operation::Shl {
+                value,
+                amount,
+            })
+        }),
+        Operation::Shr { value, amount } => (value, amount, |value, amount| {
+            binary(value, amount, |value, amount| Operation::Shr {
+                value,
+                amount,
+            })
+        }),
+        Operation::Pow { base, exponent } => (base, exponent, power),
+    };
+    let a: Operation<T> = simplify(a);
+    rule(a, simplify(b))
+}
+
+// How a binary operation is simplified, from its simplified operands
+type Rule<T> = fn(Operation<T>, Operation<T>) -> Operation<T>;
+
+// The simplified `-a`, from the simplified `a`
+fn negated<T: Number>(a: Operation<T>) -> Operation<T> {
+    match a {
+        Operation::Neg(a) => *a,
+        // -(2 * x) is -2 * x
+        Operation::Mul(a, b) if matches!(*a, Operation::Value(_)) => {
+            simplify(&Operation::Mul(Box::new(fold(Operation::Neg(a))), b))
+        }
+        a @ Operation::Value(_) => fold(Operation::Neg(Box::new(a))),
+        a => Operation::Neg(Box::new(a)),
+    }
+}
+
+// The simplified `first - second`, from the simplified operands
+fn difference<T: Number>(first: Operation<T>, second: Operation<T>) -> Operation<T> {
+    if T::SHAPED {
+        binary(first, second, |first, second| Operation::Sub {
+            first,
+            second,
+        })
+    } else if is(&second, "0") {
+        first
+    } else if is(&first, "0") {
+        negated(second)
+    } else if first == second {
+        Operation::Value(constant("0"))
+    } else {
+        binary(first, second, |first, second| Operation::Sub {
+            first,
+            second,
+        })
+    }
+}
+
+// The simplified `divident / divisor`, from the simplified operands
+fn ratio<T: Number>(divident: Operation<T>, divisor: Operation<T>) -> Operation<T> {
+    if is(&divisor, "1") {
+        divident
+    } else {
+        binary(divident, divisor, |divident, divisor| Operation::Div {
+            divident,
+            divisor,
+        })
+    }
+}
+
+// The simplified `base^exponent`, from the simplified operands
+fn power<T: Number>(base: Operation<T>, exponent: Operation<T>) -> Operation<T> {
+    if is(&exponent, "1") {
+        base
+    } else if !T::SHAPED && (is(&exponent, "0") || is(&base, "1")) {
+        Operation::Value(constant("1"))
+    } else {
+        binary(base, exponent, |base, exponent| Operation::Pow {
+            base,
+            exponent,
+        })
+    }
+}
+
+// The call of `function` on simplified `args`, computed when they are all numbers
+fn call<T: Number>(function: &str, args: Vec<Operation<T>>) -> Operation<T> {
+    let all_values: bool = args.iter().all(|arg| matches!(arg, Operation::Value(_)));
+    let call: Operation<T> = Operation::Call {
+        function: function.to_string(),
+        args,
+    };
+    if all_values { fold(call) } else { call }
+}
+
+// Builds a binary operation, folded when both operands are numbers
+fn binary<T: Number>(a: Operation<T>, b: Operation<T>, build: Build<T>) -> Operation<T> {
+    let both_values: bool = matches!((&a, &b), (Operation::Value(_), Operation::Value(_)));
+    let operation: Operation<T> = build(Box::new(a), Box::new(b));
+    if both_values {
+        fold(operation)
+    } else {
+        operation
+    }
+}
+
+// Collects the operands of a chain like `a + b + c`, whatever its parentheses
+fn flatten<T: Number>(operation: &Operation<T>, add: bool, operands: &mut Vec<Operation<T>>) {
+    match (operation, add) {
+        (Operation::Add(a, b), true) | (Operation::Mul(a, b), false) => {
+            flatten(a, add, operands);
+            flatten(b, add, operands);
+        }
+        _ => operands.push(simplify(operation)),
+    }
+}
+
+// Simplifies a chain of `+` or of `*`: the numbers are folded into one,
+// which goes last in a sum (`x + 1`) and first in a product (`2 * x`)
+fn commutative<T: Number>(operation: &Operation<T>) -> Operation<T> {
+    let add: bool = matches!(operation, Operation::Add(..));
+    let mut operands: Vec<Operation<T>> = Vec::new();
+    flatten(operation, add, &mut operands);
+    combine(operands, add)
+}
+
+// Folds the simplified operands of a sum or a product into one operation.
+// Apart from `commutative`, so its locals are not on the stack while
+// `flatten` recurses
+fn combine<T: Number>(operands: Vec<Operation<T>>, add: bool) -> Operation<T> {
+    let identity: &str = if add { "0" } else { "1" };
+    let mut number: T = constant(identity);
+    let mut folded: bool = false;
+    let mut rest: Vec<Operation<T>> = Vec::new();
+    for operand in operands {
+        let Operation::Value(v) = &operand else {
+            rest.push(operand);
+            continue;
+        };
+        let combined = if add {
+            number.add(v, Mode::Checked)
+        } else {
+            number.mul(v, Mode::Checked)
+        };
+        match combined {
+            Ok(combined) => {
+                number = combined;
+                folded = true;
+            }
+            // A number that overflows the others is kept on its own
+            Err(_) => rest.push(operand),
+        }
+    }
+    if !add && number == constant("0") && !T::SHAPED {
+        return Operation::Value(number);
+    }
+    // Sorting by the printed operands puts numbers first, then names, and
+    // `x` before `x * y` and `x^2`
+    rest.sort_by_cached_key(|operand| operand.to_string());
+
+    // `x + 0` checks the dimension of `x`, so it stays for shaped types
+    let keep: bool = number != constant(identity) || (add && folded && T::SHAPED);
+    let number: Option<Operation<T>> = keep.then_some(Operation::Value(number));
+    let operands: Vec<Operation<T>> = if add {
+        rest.into_iter().chain(number).collect()
+    } else {
+        number.into_iter().chain(rest).collect()
+    };
+    operands
+        .into_iter()
+        .reduce(|a, b| {
+            if add {
+                Operation::Add(Box::new(a), Box::new(b))
+            } else {
+                Operation::Mul(Box::new(a), Box::new(b))
+            }
+        })
+        .unwrap_or(Operation::Value(constant(identity)))
+}
+
+/// Whether `var` appears in `operation`.
+pub fn contains<T>(operation: &Operation<T>, var: &str) -> bool {
+    match operation {
+        Operation::Value(_) => false,
+        Operation::Var(name) => name == var,
+        Operation::Neg(a) => contains(a, var),
+        Operation::Add(a, b)
+        | Operation::Mul(a, b)
+        | Operation::BitAnd(a, b)
+        | Operation::BitOr(a, b)
+        | Operation::BitXor(a, b) => contains(a, var) || contains(b, var),
+        Operation::Sub { first, second } => contains(first, var) || contains(second, var),
+        Operation::Div { divident, divisor } | Operation::Rem { divident, divisor } => {
+            contains(divident, var) || contains(divisor, var)
+        }
+        Operation::Pow { base, exponent } => contains(base, var) || contains(exponent, var),
+        Operation::Shl { value, amount } | Operation::Shr { value, amount } => {
+            contains(value, var) || contains(amount, var)
+        }
+        Operation::Call { args, .. } => args.iter().any(|arg| contains(arg, var)),
+    }
+}
+
+/// The derivative of `operation` with respect to `var`, simplified.
+///
+/// Every other variable is a constant, so the derivative of `x * y` with
+/// respect to `x` is `y`.
+pub fn derive<T: Number>(operation: &Operation<T>, var: &str) -> Result<Operation<T>, DeriveError> {
+    Ok(simplify(&derivative(operation, var)?))
+}
+
+fn derivative<T: Number>(operation: &Operation<T>, var: &str) -> Result<Operation<T>, DeriveError> {
+    // A long row like `x + x + ... + x` recurses once per operator, so the
+    // rules that build bigger trees live in their own functions and keep
+    // their temporaries out of this frame
+    let d = |operation: &Operation<T>| derivative(operation, var).map(Box::new);
+    // Whatever does not depend on `var` is a constant, even `y % 2`
+    if !contains(operation, var) {
+        return Ok(Operation::Value(constant("0")));
+    }
+    Ok(match operation {
+        Operation::Value(_) => Operation::Value(constant("0")),
+        // The only variable left is `var` itself
+        Operation::Var(_) => Operation::Value(constant("1")),
+        Operation::Neg(a) => Operation::Neg(d(a)?),
+        Operation::Add(a, b) => Operation::Add(d(a)?, d(b)?),
+        Operation::Sub { first, second } => Operation::Sub {
+            first: d(first)?,
+            second: d(second)?,
+        },
+        Operation::Mul(a, b) => product_rule(a, d(a)?, b, d(b)?),
+        // Truncating would fold the quotient rule wrongly
+        Operation::Div { .. } if !T::EXACT_DIV => {
+            return Err(DeriveError::TruncatingDivision(T::NAME));
+        }
+        Operation::Div { divident, divisor } => {
+            quotient_rule(divident, d(divident)?, divisor, d(divisor)?)
+        }
+        Operation::Rem { .. } => return Err(DeriveError::Remainder),
+        Operation::BitAnd(..)
+        | Operation::BitOr(..)
+        | Operation::BitXor(..)
+        | Operation::Shl { .. }
+        | Operation::Shr { .. } => return Err(DeriveError::Bitwise),
+        Operation::Call { function, .. } => return Err(DeriveError::Function(function.clone())),
+        Operation::Pow { base, exponent } => {
+            if contains(exponent, var) {
+                return Err(DeriveError::VariableExponent(var.to_string()));
+            }
+            power_rule(base, exponent, d(base)?)
+        }
+    })
+}
+
+// (ab)' = a'b + ab'
+fn product_rule<T: Number>(
+    a: &Operation<T>,
+    da: Box<Operation<T>>,
+    b: &Operation<T>,
+    db: Box<Operation<T>>,
+) -> Operation<T> {
+    Operation::Add(
+        Box::new(Operation::Mul(da, Box::new(b.clone()))),
+        Box::new(Operation::Mul(Box::new(a.clone()), db)),
+    )
+}
+
+// (a/b)' = (a'b - ab') / b^2
+fn quotient_rule<T: Number>(
+    a: &Operation<T>,
+    da: Box<Operation<T>>,
+    b: &Operation<T>,
+    db: Box<Operation<T>>,
+) -> Operation<T> {
+    Operation::Div {
+        divident: Box::new(Operation::Sub {
+            first: Box::new(Operation::Mul(da, Box::new(b.clone()))),
+            second: Box::new(Operation::Mul(Box::new(a.clone()), db)),
+        }),
+        divisor: Box::new(Operation::Pow {
+            base: Box::new(b.clone()),
+            exponent: Box::new(Operation::Value(constant("2"))),
+        }),
+    }
+}
+
+// (a^n)' = n * a^(n - 1) * a'
+fn power_rule<T: Number>(
+    a: &Operation<T>,
+    n: &Operation<T>,
+    da: Box<Operation<T>>,
+) -> Operation<T> {
+    Operation::Mul(
+        Box::new(Operation::Mul(
+            Box::new(n.clone()),
+            Box::new(Operation::Pow {
+                base: Box::new(a.clone()),
+                exponent: Box::new(Operation::Sub {
+                    first: Box::new(n.clone()),
+                    second: Box::new(Operation::Value(constant("1"))),
+                }),
+            }),
+        )),
+        da,
+    )
+}
@@ -0,0 +1,415 @@
+//! Whole numbers of any size.
+//!
+//! A [`BigInt`] keeps its digits in a `Vec`, so it grows instead of
+//! overflowing: `2^100` is 1267650600228229401496703205376. Each element of
+//! the `Vec` holds nine decimal digits, which keeps printing and parsing as
+//! simple as splitting the text into groups of nine.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use super::number::{self, Number};
+use super::{MathError, Mode, ParseErrorKind};
+
+// One limb holds a number below BASE, nine decimal digits
+const BASE: u64 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+// Results of `pow` with more limbs than this are refused as Overflow, since
+// computing them would take minutes and gigabytes
+const MAX_POW_LIMBS: u64 = 100_000;
+
+/// A whole number without a size limit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool,
+    // Least significant limb first, without trailing zero limbs. Zero has
+    // no limbs and is never negative
+    limbs: Vec<u32>,
+}
+
+/// Why some text is not a [`BigInt`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseBigIntError;
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected decimal digits with an optional sign")
+    }
+}
+
+impl std::error::Error for ParseBigIntError {}
+
+impl BigInt {
+    /// Zero.
+    pub fn zero() -> BigInt {
+        BigInt::default()
+    }
+
+    /// Whether the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Number of decimal digits, without the sign. Zero has one digit.
+    pub fn digits(&self) -> usize {
+        match self.limbs.last() {
+            None => 1,
+            Some(top) => top.to_string().len() + (self.limbs.len() - 1) * BASE_DIGITS,
+        }
+    }
+
+    /// The number without its sign.
+    pub fn abs(&self) -> BigInt {
+        BigInt {
+            negative: false,
+            limbs: self.limbs.clone(),
+        }
+    }
+
+    // Builds a number, fixing up trailing zero limbs and a negative zero
+    fn new(negative: bool, mut limbs: Vec<u32>) -> BigInt {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigInt {
+            negative: negative && !limbs.is_empty(),
+            limbs,
+        }
+    }
+
+    // self + other, or self - other when `subtract`
+    fn add_signed(&self, other: &BigInt, subtract: bool) -> BigInt {
+        let other_negative: bool = other.negative != subtract;
+        if self.negative == other_negative {
+            return BigInt::new(self.negative, add_limbs(&self.limbs, &other.limbs));
+        }
+        // Different signs: the larger magnitude decides the sign
+        match compare_limbs(&self.limbs, &other.limbs) {
+            Ordering::Less => BigInt::new(other_negative, sub_limbs(&other.limbs, &self.limbs)),
+            _ => BigInt::new(self.negative, sub_limbs(&self.limbs, &other.limbs)),
+        }
+    }
+
+    /// The quotient truncated towards zero and the remainder with the sign of `self`.
+    pub fn div_rem(&self, other: &BigInt) -> Result<(BigInt, BigInt), MathError> {
+        if other.is_zero() {
+            return Err(MathError::DivideByZero);
+        }
+        let (quotient, remainder) = div_rem_limbs(&self.limbs, &other.limbs);
+        Ok((
+            BigInt::new(self.negative != other.negative, quotient),
+            BigInt::new(self.negative, remainder),
+        ))
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(n: i64) -> BigInt {
+        let mut magnitude: u64 = n.unsigned_abs();
+        let mut limbs: Vec<u32> = Vec::new();
+        while magnitude > 0 {
+            limbs.push((magnitude % BASE) as u32);
+            magnitude /= BASE;
+        }
+        BigInt::new(n < 0, limbs)
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    /// Parses decimal digits with an optional `+` or `-`, like `-1234`.
+    fn from_str(text: &str) -> Result<BigInt, ParseBigIntError> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ParseBigIntError);
+        }
+        // Groups of nine digits from the right, the least significant first
+        let limbs: Vec<u32> = digits
+            .as_bytes()
+            .rchunks(BASE_DIGITS)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0, |limb, digit| limb * 10 + (digit - b'0') as u32)
+            })
+            .collect();
+        Ok(BigInt::new(negative, limbs))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Some((top, rest)) = self.limbs.split_last() else {
+            return write!(f, "0");
+        };
+        if self.negative {
+            write!(f, "-")?;
+        }
+        // Every limb but the first printed one keeps its leading zeros
+        write!(f, "{}", top)?;
+        for limb in rest.iter().rev() {
+            write!(f, "{:09}", limb)?;
+        }
+        Ok(())
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => compare_limbs(&self.limbs, &other.limbs),
+            (true, true) => compare_limbs(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Compares magnitudes: more limbs is larger, else the first limb that differs
+fn compare_limbs(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+// Schoolbook addition with a carry, like on paper
+fn add_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum: Vec<u32> = Vec::with_capacity(a.len().max(b.len()) + 1);
+    let mut carry: u64 = 0;
+    for i in 0..a.len().max(b.len()) {
+        let digit: u64 = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+        sum.push((digit % BASE) as u32);
+        carry = digit / BASE;
+    }
+    if carry > 0 {
+        sum.push(carry as u32);
+    }
+    sum
+}
+
+// a - b with a borrow, for a magnitude `a` at least as large as `b`
+fn sub_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut difference: Vec<u32> = Vec::with_capacity(a.len());
+    let mut borrow: i64 = 0;
+    for (i, &limb) in a.iter().enumerate() {
+        let mut digit: i64 = limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+        borrow = 0;
+        if digit < 0 {
+            digit += BASE as i64;
+            borrow = 1;
+        }
+        difference.push(digit as u32);
+    }
+    difference
+}
+
+// Multiplies every limb of `a` with every limb of `b`. A product of two
+// limbs plus the carries still fits a u64
+fn mul_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut product: Vec<u64> = vec![0; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry: u64 = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let digit: u64 = product[i + j] + x as u64 * y as u64 + carry;
+            product[i + j] = digit % BASE;
+            carry = digit / BASE;
+        }
+        product[i + b.len()] += carry;
+    }
+    product.into_iter().map(|limb| limb as u32).collect()
+}
+
+// Long division by Knuth's algorithm D. Both numbers are scaled so the top
+// limb of `b` is at least BASE / 2. Then the top two limbs of the remainder
+// and of `b` estimate each quotient limb, at most two too large
+fn div_rem_limbs(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if compare_limbs(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if let [divisor] = b {
+        let (quotient, remainder) = div_small(a, *divisor as u64);
+        return (quotient, vec![remainder as u32]);
+    }
+    let scale: u64 = BASE / (*b.last().expect("b is not zero") as u64 + 1);
+    let b: Vec<u32> = mul_small(b, scale);
+    let mut remainder: Vec<u32> = mul_small(a, scale);
+    remainder.resize(a.len() + 1, 0);
+
+    let n: usize = b.len();
+    let (top, second): (u64, u64) = (b[n - 1] as u64, b[n - 2] as u64);
+    let mut quotient: Vec<u32> = vec![0; a.len() - n + 1];
+    for j in (0..quotient.len()).rev() {
+        let leading: u64 = remainder[j + n] as u64 * BASE + remainder[j + n - 1] as u64;
+        let (mut estimate, mut rest) = (leading / top, leading % top);
+        while estimate >= BASE || estimate * second > rest * BASE + remainder[j + n - 2] as u64 {
+            estimate -= 1;
+            rest += top;
+            if rest >= BASE {
+                break;
+            }
+        }
+
+        // Subtracts estimate * b from the remainder in place
+        let (mut carry, mut borrow): (u64, i64) = (0, 0);
+        for (i, &limb) in b.iter().enumerate() {
+            let product: u64 = estimate * limb as u64 + carry;
+            carry = product / BASE;
+            let digit: i64 = remainder[i + j] as i64 - (product % BASE) as i64 - borrow;
+            borrow = i64::from(digit < 0);
+            remainder[i + j] = (digit + borrow * BASE as i64) as u32;
+        }
+        let digit: i64 = remainder[j + n] as i64 - carry as i64 - borrow;
+        remainder[j + n] = (digit + i64::from(digit < 0) * BASE as i64) as u32;
+
+        // Rarely the estimate is still one too large, then b is added back
+        // and the carry out of the top limb cancels the borrow
+        if digit < 0 {
+            estimate -= 1;
+            let mut carry: u64 = 0;
+            for (i, &limb) in b.iter().enumerate() {
+                let sum: u64 = remainder[i + j] as u64 + limb as u64 + carry;
+                remainder[i + j] = (sum % BASE) as u32;
+                carry = sum / BASE;
+            }
+            remainder[j + n] = ((remainder[j + n] as u64 + carry) % BASE) as u32;
+        }
+        quotient[j] = estimate as u32;
+    }
+    remainder.truncate(n);
+    (quotient, div_small(&remainder, scale).0)
+}
+
+// Multiplies by a number below BASE
+fn mul_small(a: &[u32], factor: u64) -> Vec<u32> {
+    let mut product: Vec<u32> = Vec::with_capacity(a.len() + 1);
+    let mut carry: u64 = 0;
+    for &limb in a {
+        let digit: u64 = limb as u64 * factor + carry;
+        product.push((digit % BASE) as u32);
+        carry = digit / BASE;
+    }
+    if carry > 0 {
+        product.push(carry as u32);
+    }
+    product
+}
+
+// Divides by a number below BASE from the top limb down, like on paper
+fn div_small(a: &[u32], divisor: u64) -> (Vec<u32>, u64) {
+    let mut quotient: Vec<u32> = vec![0; a.len()];
+    let mut remainder: u64 = 0;
+    for (i, &limb) in a.iter().enumerate().rev() {
+        let digit: u64 = remainder * BASE + limb as u64;
+        quotient[i] = (digit / divisor) as u32;
+        remainder = digit % divisor;
+    }
+    (quotient, remainder)
+}
+
+// The mode does not matter: a BigInt never leaves its range
+impl Number for BigInt {
+    const NAME: &'static str = "bigint";
+    const EXACT_DIV: bool = false;
+
+    fn parse_literal(text: &str) -> Result<Self, ParseErrorKind> {
+        if text.contains('.') {
+            return Err(ParseErrorKind::NotAWholeNumber(Self::NAME));
+        }
+        number::decimal_only(text, Self::NAME)?;
+        if text.contains(['e', 'E']) {
+            return Err(ParseErrorKind::Exponent(Self::NAME));
+        }
+        text.parse()
+            .map_err(|_| ParseErrorKind::NumberTooLarge(Self::NAME))
+    }
+
+    fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    fn neg(&self, _mode: Mode) -> Result<Self, MathError> {
+        Ok(BigInt::new(!self.negative, self.limbs.clone()))
+    }
+
+    fn add(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Ok(self.add_signed(other, false))
+    }
+
+    fn sub(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Ok(self.add_signed(other, true))
+    }
+
+    fn mul(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Ok(BigInt::new(
+            self.negative != other.negative,
+            mul_limbs(&self.limbs, &other.limbs),
+        ))
+    }
+
+    fn div(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Ok(self.div_rem(other)?.0)
+    }
+
+    fn rem(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Ok(self.div_rem(other)?.1)
+    }
+
+    fn pow(&self, exponent: &Self, mode: Mode) -> Result<Self, MathError> {
+        if exponent.negative {
+            return Err(MathError::NegativeExponent);
+        }
+        let odd: bool = exponent.limbs.first().is_some_and(|limb| limb % 2 == 1);
+        // 0, 1 and -1 stay that small whatever the exponent
+        if self.limbs.len() <= 1 && self.limbs.first().is_none_or(|&limb| limb <= 1) {
+            return Ok(if exponent.is_zero() {
+                BigInt::from(1)
+            } else {
+                BigInt::new(self.negative && odd, self.limbs.clone())
+            });
+        }
+        let too_large: MathError = if self.negative && odd {
+            MathError::Underflow
+        } else {
+            MathError::Overflow
+        };
+        let exponent: u64 = match exponent.limbs.as_slice() {
+            [] => 0,
+            [low] => *low as u64,
+            [low, high] => *high as u64 * BASE + *low as u64,
+            _ => return Err(too_large),
+        };
+        // The result has exponent * log10(|self|) digits. The top limb plus
+        // one bounds the logarithm from above, so 2^n counts as 3^n at most
+        let top: f64 = *self.limbs.last().expect("0 returned early") as f64 + 1.0;
+        let log10: f64 = top.log10() + ((self.limbs.len() - 1) * BASE_DIGITS) as f64;
+        if exponent as f64 * log10 / BASE_DIGITS as f64 > MAX_POW_LIMBS as f64 {
+            return Err(too_large);
+        }
+        // Squaring keeps the number of multiplications logarithmic
+        let mut result: BigInt = BigInt::from(1);
+        let mut base: BigInt = self.clone();
+        let mut remaining: u64 = exponent;
+        while remaining > 0 {
+            if remaining % 2 == 1 {
+                result = result.mul(&base, mode)?;
+            }
+            remaining /= 2;
+            if remaining > 0 {
+                base = base.mul(&base, mode)?;
+            }
+        }
+        Ok(result)
+    }
+}
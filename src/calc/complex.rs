@@ -0,0 +1,243 @@
+//! Complex numbers: a real and an imaginary part, like `3 + 4i`.
+//!
+//! `i` is the imaginary unit, the number whose square is -1. In the
+//! calculator it is a unit like `km` is for quantities: `4i` is `4 * i`, and
+//! a variable named `i` hides it. `(3 + 4i) * (1 - 2i)` is `11 - 2i`.
+//!
+//! A complex number is also a point in the plane, so it has polar
+//! coordinates: its distance from 0, `abs`, and the angle to the positive
+//! real axis, `arg`. `polar(r, angle)` goes back, and `z in polar` prints a
+//! result that way.
+
+use std::fmt;
+
+use super::number::{self, Number};
+use super::{MathError, Mode, ParseErrorKind};
+
+/// A number `re + im * i`, both parts are `f64`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    /// The imaginary unit `i`.
+    pub const I: Complex = Complex { re: 0.0, im: 1.0 };
+
+    /// `re + im * i`
+    pub fn new(re: f64, im: f64) -> Complex {
+        Complex { re, im }
+    }
+
+    /// The point at distance `modulus` from 0, at the angle `argument` in
+    /// radians.
+    pub fn from_polar(modulus: f64, argument: f64) -> Complex {
+        Complex::new(modulus * argument.cos(), modulus * argument.sin())
+    }
+
+    /// The real part.
+    pub fn re(&self) -> f64 {
+        self.re
+    }
+
+    /// The imaginary part.
+    pub fn im(&self) -> f64 {
+        self.im
+    }
+
+    /// The mirror image across the real axis, `re - im * i`.
+    pub fn conj(&self) -> Complex {
+        Complex::new(self.re, -self.im)
+    }
+
+    /// The distance from 0, also called the absolute value.
+    pub fn modulus(&self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// The angle to the positive real axis in radians, from -π to π.
+    pub fn argument(&self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    /// The parts as they are printed. A part that is smaller than the other
+    /// by a factor of 10^15 is rounding noise and becomes 0, so that
+    /// `polar(1, 3.141592653589793)` prints as -1 rather than
+    /// `-1 + 0.00000000000000012246467991473532i`.
+    fn shown(&self) -> (f64, f64) {
+        let noise: f64 = self.re.abs().max(self.im.abs()) * 1e-15;
+        // Adding 0.0 turns -0 into 0
+        let clean = |part: f64| if part.abs() < noise { 0.0 } else { part + 0.0 };
+        (clean(self.re), clean(self.im))
+    }
+
+    fn real(&self) -> Result<f64, MathError> {
+        match self.shown() {
+            (re, 0.0) => Ok(re),
+            _ => Err(MathError::NotReal(self.to_string())),
+        }
+    }
+}
+
+// A result whose parts are checked like f64 results
+fn checked(re: f64, im: f64, mode: Mode) -> Result<Complex, MathError> {
+    Ok(Complex::new(
+        number::finite(re, mode)?,
+        number::finite(im, mode)?,
+    ))
+}
+
+/// Prints `3 + 4i`, `-2i`, `3 - i` or just `3`, which read back as
+/// calculator input.
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let imaginary = |im: f64| match im {
+            1.0 => String::from("i"),
+            -1.0 => String::from("-i"),
+            _ => format!("{}i", im),
+        };
+        match self.shown() {
+            (re, 0.0) => write!(f, "{}", re),
+            (0.0, im) => write!(f, "{}", imaginary(im)),
+            (re, im) if im < 0.0 => write!(f, "{} - {}", re, imaginary(-im)),
+            (re, im) => write!(f, "{} + {}", re, imaginary(im)),
+        }
+    }
+}
+
+// Checks the number of operands of `function`
+fn arity(function: &str, args: &[Complex], n: usize) -> Result<(), MathError> {
+    if args.len() == n {
+        Ok(())
+    } else {
+        Err(MathError::ArgumentCount(function.to_string(), n))
+    }
+}
+
+impl Number for Complex {
+    const NAME: &'static str = "complex";
+
+    // A literal is real, `4i` is a literal times the unit `i`
+    fn parse_literal(text: &str) -> Result<Self, ParseErrorKind> {
+        let re: f64 = number::parse_f64(text, Self::NAME)?;
+        Ok(Complex::new(re, 0.0))
+    }
+
+    fn is_negative(&self) -> bool {
+        match self.shown() {
+            (re, 0.0) => re < 0.0,
+            (0.0, im) => im < 0.0,
+            _ => false,
+        }
+    }
+
+    // `4i` reads like the product it came from: (4i)^2 is not 4i^2
+    fn is_fraction(&self) -> bool {
+        matches!(self.shown(), (0.0, im) if im.abs() != 1.0)
+    }
+
+    fn is_sum(&self) -> bool {
+        !matches!(self.shown(), (0.0, _) | (_, 0.0))
+    }
+
+    fn unit(name: &str) -> Option<Self> {
+        (name == "i").then_some(Complex::I)
+    }
+
+    fn neg(&self, _mode: Mode) -> Result<Self, MathError> {
+        Ok(Complex::new(-self.re, -self.im))
+    }
+
+    fn add(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        checked(self.re + other.re, self.im + other.im, mode)
+    }
+
+    fn sub(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        checked(self.re - other.re, self.im - other.im, mode)
+    }
+
+    // (a + bi)(c + di) = ac - bd + (ad + bc)i, since i * i = -1
+    fn mul(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        checked(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+            mode,
+        )
+    }
+
+    // Multiplying above and below by the conjugate of the divisor leaves a
+    // real number below: c^2 + d^2
+    fn div(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        let below: f64 = other.re * other.re + other.im * other.im;
+        if below == 0.0 {
+            return Err(MathError::DivideByZero);
+        }
+        let above: Complex = self.mul(&other.conj(), mode)?;
+        checked(above.re / below, above.im / below, mode)
+    }
+
+    // Only real numbers have a remainder
+    fn rem(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        let value: f64 = self.real()?.rem(&other.real()?, mode)?;
+        Ok(Complex::new(value, 0.0))
+    }
+
+    fn pow(&self, exponent: &Self, mode: Mode) -> Result<Self, MathError> {
+        let (n, im) = exponent.shown();
+        // Whole powers by multiplying, so (1 + i)^2 is exactly 2i
+        if im == 0.0 && n.fract() == 0.0 && n.abs() <= i32::MAX as f64 {
+            let mut base: Complex = *self;
+            let mut result: Complex = Complex::new(1.0, 0.0);
+            let mut count: u32 = n.abs() as u32;
+            while count > 0 {
+                if count % 2 == 1 {
+                    result = result.mul(&base, mode)?;
+                }
+                count /= 2;
+                if count > 0 {
+                    base = base.mul(&base, mode)?;
+                }
+            }
+            return if n < 0.0 {
+                Complex::new(1.0, 0.0).div(&result, mode)
+            } else {
+                Ok(result)
+            };
+        }
+        if self.re == 0.0 && self.im == 0.0 {
+            return if exponent.re > 0.0 {
+                Ok(*self)
+            } else {
+                Err(MathError::DivideByZero)
+            };
+        }
+        // z^w = e^(w * ln z), where ln z = ln |z| + arg(z) * i
+        let ln: Complex = Complex::new(self.modulus().ln(), self.argument());
+        let w: Complex = exponent.mul(&ln, mode)?;
+        let power: Complex = Complex::from_polar(w.re.exp(), w.im);
+        checked(power.re, power.im, mode)
+    }
+
+    fn call(function: &str, args: &[Self], _mode: Mode) -> Result<Self, MathError> {
+        let real = |value: f64| Ok(Complex::new(value, 0.0));
+        match function {
+            "re" | "im" | "abs" | "arg" | "conj" => {
+                arity(function, args, 1)?;
+                let z: Complex = args[0];
+                match function {
+                    "re" => real(z.re),
+                    "im" => real(z.im),
+                    "abs" => real(z.modulus()),
+                    "arg" => real(z.argument()),
+                    _ => Ok(z.conj()),
+                }
+            }
+            "polar" => {
+                arity(function, args, 2)?;
+                Ok(Complex::from_polar(args[0].real()?, args[1].real()?))
+            }
+            _ => Err(MathError::UnknownFunction(function.to_string())),
+        }
+    }
+}
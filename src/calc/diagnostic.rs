@@ -0,0 +1,401 @@
+//! Error reports that point into the input, in the style of the compiler:
+//!
+//! ```text
+//! error[E101]: Can not divide by zero
+//!   |
+//!   | 1 + 20 / (5 - 5)
+//!   |     ^^^^^^^^^^^^
+//!   = help: no number type can divide by zero
+//! ```
+//!
+//! A parse error knows its offset. For an error while computing, the
+//! operation is run again on the [`vm`](super::vm): instruction `i` of the
+//! bytecode is part `i` of the tree in post-order, the order in which
+//! [`parse_spanned`](super::parser::parse_spanned) records the spans, so the
+//! failing instruction tells which part of the input to underline.
+
+use super::env::Env;
+use super::lexer::{self, Token, TokenKind};
+use super::number::Number;
+use super::rpn::RpnError;
+use super::vm;
+use super::{MathError, Mode, Operation, ParseError, ParseErrorKind};
+
+/// A range of byte offsets in the input, `end` excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Offset of the first byte.
+    pub start: usize,
+    /// Offset just after the last byte.
+    pub end: usize,
+}
+
+/// How a [`Diagnostic`] is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Style {
+    /// One line: `Error: Can not divide by zero`, or with the offset of a
+    /// parse error: `Error: number is too large for i32 at byte 4`.
+    #[default]
+    Short,
+    /// The code, the input with the problem underlined and a help note.
+    Plain,
+    /// Like [`Style::Plain`], coloured with ANSI escape codes for a terminal.
+    Color,
+}
+
+impl Style {
+    /// Every style.
+    pub const ALL: [Style; 3] = [Style::Short, Style::Plain, Style::Color];
+
+    /// The name of the style, e.g. `plain`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Style::Short => "short",
+            Style::Plain => "plain",
+            Style::Color => "color",
+        }
+    }
+
+    /// Parses a [`name`](Style::name).
+    pub fn from_name(name: &str) -> Option<Style> {
+        Style::ALL.into_iter().find(|style| style.name() == name)
+    }
+}
+
+// The help for results outside the range of the number type named `number`.
+// Only the fixed-size integers have a larger type, and the modes change
+// nothing for the exact types
+fn out_of_range(number: &str) -> &'static str {
+    match number {
+        "i128" => "`mode wrapping` or `mode saturating` keeps a result, `type bigint` has no limit",
+        "f64" | "quantity" | "complex" => {
+            "`mode wrapping` keeps infinity, `mode saturating` the largest f64"
+        }
+        "rational" | "matrix" => {
+            "fractions have an i64 numerator and denominator, `type f64` rounds instead"
+        }
+        "bigint" => "a power of a bigint may have at most 900000 digits",
+        _ => "`mode wrapping` or `mode saturating` keeps a result, `type` picks a larger type",
+    }
+}
+
+/// An error with what is needed to explain it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// A code like `E101`, the same for every error of a kind.
+    pub code: Option<&'static str>,
+    /// What went wrong.
+    pub message: String,
+    /// The part of the input that caused it.
+    pub span: Option<Span>,
+    /// How to fix it.
+    pub help: Option<String>,
+}
+
+impl From<String> for Diagnostic {
+    fn from(message: String) -> Diagnostic {
+        Diagnostic {
+            code: None,
+            message,
+            span: None,
+            help: None,
+        }
+    }
+}
+
+impl Diagnostic {
+    /// The report of a parse error in `input`, underlining the token where
+    /// it happened.
+    pub fn parse(input: &str, error: &ParseError) -> Diagnostic {
+        let (code, help): (&'static str, String) = match error.kind {
+            ParseErrorKind::UnexpectedChar(_) => (
+                "E001",
+                String::from(
+                    "the operators are + - * / % ^ & | xor << >>, names are letters, digits and _, \
+                     `$1` is the first result",
+                ),
+            ),
+            ParseErrorKind::ExpectedNumber => (
+                "E002",
+                String::from("every operator needs a value on both sides"),
+            ),
+            ParseErrorKind::ExpectedOperator => (
+                "E003",
+                String::from("put an operator between two values, like `x * y`"),
+            ),
+            ParseErrorKind::NumberTooLarge(name) => (
+                "E004",
+                format!(
+                    "{} is too small for it, `type i128` or `type bigint` holds larger numbers",
+                    name
+                ),
+            ),
+            ParseErrorKind::NotAWholeNumber(_) => (
+                "E005",
+                String::from("`type f64` or `type rational` computes with fractions"),
+            ),
+            ParseErrorKind::UnclosedParen => {
+                ("E006", String::from("add a `)` where the group ends"))
+            }
+            ParseErrorKind::UnmatchedParen => (
+                "E007",
+                String::from("remove it, or add a `(` where the group starts"),
+            ),
+            ParseErrorKind::ExpectedAssignment => {
+                ("E008", String::from("write `let name = value`"))
+            }
+            ParseErrorKind::NotDecimal(_) => (
+                "E009",
+                String::from("only the integer types, like `type i64`, read hex, octal and binary"),
+            ),
+            ParseErrorKind::InvalidDigit(base) => (
+                "E010",
+                format!(
+                    "the digits of a {} number are {}",
+                    base,
+                    match base {
+                        "bin" => "0 and 1",
+                        "oct" => "0 to 7",
+                        _ => "0 to 9 and a to f",
+                    }
+                ),
+            ),
+            ParseErrorKind::NoMatrices(_) => {
+                ("E011", String::from("`type matrix` computes with matrices"))
+            }
+            ParseErrorKind::UnclosedBracket => {
+                ("E012", String::from("add a `]` where the matrix ends"))
+            }
+            ParseErrorKind::InvalidMatrix(_) => (
+                "E013",
+                String::from("write a vector like `[1, 2]` or rows like `[[1, 2], [3, 4]]`"),
+            ),
+            ParseErrorKind::TooDeep(_) => (
+                "E014",
+                String::from("split the expression, e.g. into variables with `name = value`"),
+            ),
+            ParseErrorKind::Exponent(_) => (
+                "E015",
+                String::from("write the digits out, or `type f64` or `type rational` reads `1e5`"),
+            ),
+        };
+        Diagnostic {
+            code: Some(code),
+            message: error.kind.to_string(),
+            span: Some(token_span(input, error.offset)),
+            help: Some(help),
+        }
+    }
+
+    /// The report of an error while computing the part of the input at
+    /// `span` with the number type named `number`, like `i64`.
+    pub fn math(error: &MathError, span: Option<Span>, number: &'static str) -> Diagnostic {
+        let (code, help): (&'static str, String) = match error {
+            MathError::DivideByZero => ("E101", String::from("no number type can divide by zero")),
+            MathError::Overflow => ("E102", String::from(out_of_range(number))),
+            MathError::Underflow => ("E103", String::from(out_of_range(number))),
+            MathError::NegativeExponent => (
+                "E104",
+                String::from("integers have no fractions, `type f64` computes negative powers"),
+            ),
+            MathError::FractionalExponent => (
+                "E105",
+                String::from("`type f64` computes fractional powers"),
+            ),
+            MathError::UndefinedVariable(name) if name.starts_with('$') => {
+                ("E106", String::from("`history` lists the numbered results"))
+            }
+            MathError::UndefinedVariable(name) if name == "i" => (
+                "E106",
+                String::from("`type complex` computes with the imaginary unit `i`"),
+            ),
+            MathError::UndefinedVariable(name) => (
+                "E106",
+                format!(
+                    "assign it first, like `{} = 1`, or list the variables with `vars`",
+                    name
+                ),
+            ),
+            MathError::NotAnInteger if number == "bigint" => (
+                "E107",
+                String::from("bigint has no bitwise operators, `type i128` has them"),
+            ),
+            MathError::NotAnInteger => (
+                "E107",
+                String::from("switch to an integer type, like `type i64`"),
+            ),
+            MathError::InvalidShift => (
+                "E108",
+                String::from("shift by 0 up to the bits of the type minus one, like 31 for i32"),
+            ),
+            MathError::DimensionMismatch(..) => (
+                "E109",
+                String::from(
+                    "only quantities of the same dimension can be added, subtracted or converted",
+                ),
+            ),
+            MathError::FractionalUnit(_) => (
+                "E110",
+                String::from("raise a quantity with units to a whole power, like 2 or -1"),
+            ),
+            MathError::UnknownFunction(_) => (
+                "E111",
+                String::from(
+                    "`type matrix` has det, inv, transpose, solve and emul, \
+                     `type complex` has re, im, abs, arg, conj and polar",
+                ),
+            ),
+            MathError::ArgumentCount(..) => {
+                ("E112", String::from("separate the operands with `,`"))
+            }
+            MathError::ShapeMismatch(..) => (
+                "E113",
+                String::from(
+                    "`+` and `-` need the same size, `a * b` needs as many columns in a as rows in b",
+                ),
+            ),
+            MathError::NotSquare(_) => (
+                "E114",
+                String::from("determinants and inverses need as many rows as columns"),
+            ),
+            MathError::SingularMatrix => (
+                "E115",
+                String::from("its determinant is zero, like dividing by zero"),
+            ),
+            MathError::NotReal(_) => (
+                "E116",
+                String::from("`%` and the operands of `polar` need numbers without an `i`"),
+            ),
+            MathError::DivideByMatrix => (
+                "E117",
+                String::from("multiply by the inverse instead, like `a * inv(b)`"),
+            ),
+            MathError::NotANumber if number == "complex" => (
+                "E118",
+                String::from("a part of it grew too large on the way, like in an overflow"),
+            ),
+            MathError::NotANumber => (
+                "E118",
+                String::from(
+                    "a negative number has no real fractional power, `type complex` has it",
+                ),
+            ),
+        };
+        Diagnostic {
+            code: Some(code),
+            message: error.to_string(),
+            span,
+            help: Some(help),
+        }
+    }
+
+    /// The report of an error of the RPN word at `span` in `input`, with
+    /// the number type named `number`.
+    pub fn rpn(input: &str, error: &RpnError, span: Span, number: &'static str) -> Diagnostic {
+        match error {
+            RpnError::UnknownWord(_) => Diagnostic {
+                code: Some("E016"),
+                message: error.to_string(),
+                span: Some(span),
+                help: Some(String::from(
+                    "the words are numbers, names, operators, neg, dup, swap, drop and clear",
+                )),
+            },
+            // The word failed like an infix literal would, underlined whole
+            RpnError::Number(kind) => {
+                let error: ParseError = ParseError {
+                    kind: *kind,
+                    offset: span.start,
+                };
+                Diagnostic {
+                    span: Some(span),
+                    ..Diagnostic::parse(input, &error)
+                }
+            }
+            RpnError::Math(e) => Diagnostic::math(e, None, number),
+            RpnError::StackUnderflow { .. } => Diagnostic::from(error.to_string()),
+        }
+    }
+
+    /// Writes the report of an error in `input`.
+    pub fn render(&self, input: &str, style: Style) -> String {
+        if style == Style::Short {
+            // Parse errors, E0xx, say where they are like a `ParseError` does
+            return match (self.code, self.span) {
+                (Some(code), Some(span)) if code.starts_with("E0") => {
+                    format!("Error: {} at byte {}", self.message, span.start)
+                }
+                _ => format!("Error: {}", self.message),
+            };
+        }
+        let paint = |color: &str, text: &str| match style {
+            Style::Color => format!("{}{}\x1b[0m", color, text),
+            _ => text.to_string(),
+        };
+        let (red, blue, bold) = ("\x1b[1;31m", "\x1b[1;34m", "\x1b[1m");
+        let gutter: String = paint(blue, "  |");
+
+        let mut report: String = match self.code {
+            Some(code) => paint(red, &format!("error[{}]", code)),
+            None => paint(red, "error"),
+        };
+        report.push_str(&paint(bold, &format!(": {}", self.message)));
+        if let Some(span) = self.span {
+            // Columns count characters, so `é` takes one like on the screen
+            let column: usize = input[..span.start].chars().count();
+            let width: usize = input[span.start..span.end].chars().count().max(1);
+            let underline: String = format!("{}{}", " ".repeat(column), "^".repeat(width));
+            report.push_str(&format!("\n{}\n{} {}", gutter, gutter, input));
+            report.push_str(&format!("\n{} {}", gutter, paint(red, &underline)));
+        }
+        if let Some(help) = &self.help {
+            let equals: String = paint(blue, "  =");
+            report.push_str(&format!("\n{} {} {}", equals, paint(bold, "help:"), help));
+        }
+        report
+    }
+}
+
+// The token starting at `offset`, with the number after a `-` that starts
+// a negative literal, or an empty span at the end of the input
+fn token_span(input: &str, offset: usize) -> Span {
+    let empty: Span = Span {
+        start: offset,
+        end: offset,
+    };
+    let Ok(tokens) = lexer::tokenize(input) else {
+        // The lexer failed on an unexpected character, which is at `offset`
+        let width: usize = input[offset..].chars().next().map_or(0, char::len_utf8);
+        return Span {
+            start: offset,
+            end: offset + width,
+        };
+    };
+    let Some(i) = tokens.iter().position(|token| token.offset == offset) else {
+        return empty;
+    };
+    let token: &Token = match (tokens[i].kind, tokens.get(i + 1)) {
+        (TokenKind::Minus, Some(next)) if matches!(next.kind, TokenKind::Number(_)) => next,
+        _ => &tokens[i],
+    };
+    Span {
+        start: offset,
+        end: token.end(),
+    }
+}
+
+/// Where `operation` fails when computed in `mode` with the variables of
+/// `env`, given the spans [`parse_spanned`](super::parser::parse_spanned)
+/// returned for it.
+pub fn locate<T: Number>(
+    operation: &Operation<T>,
+    spans: &[Span],
+    env: &Env<T>,
+    mode: Mode,
+) -> Option<Span> {
+    let program: vm::Program<T> = vm::compile(operation);
+    match program.run_located(env, mode) {
+        Ok(_) => None,
+        Err((index, _)) => spans.get(index).copied(),
+    }
+}
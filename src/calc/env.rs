@@ -0,0 +1,137 @@
+//! Variables of a calculator session.
+//!
+//! Like the word counts of the `HashMap` lessons, the bindings are a map
+//! from a name to a value. They are saved as one `name = value` line per
+//! variable, which is calculator input itself, so loading a file runs it.
+//!
+//! The calculator also keeps its numbered results here, as `$1`, `$2` and
+//! so on. They are read like variables but are not saved.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use super::Mode;
+use super::number::Number;
+use super::parser::{self, Statement};
+
+/// Variables and their values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Env<T> {
+    vars: HashMap<String, T>,
+}
+
+impl<T> Default for Env<T> {
+    fn default() -> Self {
+        Env {
+            vars: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Number> Env<T> {
+    /// An environment without variables.
+    pub fn new() -> Env<T> {
+        Env::default()
+    }
+
+    /// The value of `name`, if it was assigned.
+    pub fn get(&self, name: &str) -> Option<&T> {
+        self.vars.get(name)
+    }
+
+    /// The value `name` stands for: its variable, or else the unit of that
+    /// name, like `km` for a [`Quantity`](super::units::Quantity).
+    pub fn value(&self, name: &str) -> Option<T> {
+        self.get(name).cloned().or_else(|| T::unit(name))
+    }
+
+    /// Assigns `value` to `name` and returns the value it had before.
+    pub fn set(&mut self, name: &str, value: T) -> Option<T> {
+        self.vars.insert(name.to_string(), value)
+    }
+
+    /// Forgets `name` and returns its value.
+    pub fn remove(&mut self, name: &str) -> Option<T> {
+        self.vars.remove(name)
+    }
+
+    /// Number of variables, counting the results like `$1`.
+    pub fn len(&self) -> usize {
+        self.vars.len()
+    }
+
+    /// Whether no variable or result is assigned.
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// The variables sorted by name, since a `HashMap` has no order of its own.
+    pub fn sorted(&self) -> Vec<(&str, &T)> {
+        let mut vars: Vec<(&str, &T)> = self
+            .vars
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+            .collect();
+        vars.sort_by(|a, b| a.0.cmp(b.0));
+        vars
+    }
+
+    /// The variables sorted by name, without the results like `$1`.
+    pub fn variables(&self) -> Vec<(&str, &T)> {
+        let mut vars: Vec<(&str, &T)> = self.sorted();
+        vars.retain(|(name, _)| !is_result(name));
+        vars
+    }
+
+    /// Writes one `name = value` line per variable to `path`, results like
+    /// `$1` are left out.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
+        for (name, value) in self.variables() {
+            writeln!(file, "{} = {}", name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a file written by [`save`](Env::save).
+    ///
+    /// Every line is an assignment computed in `mode`, so a value may also be
+    /// an operation on the variables above it. Empty lines and lines starting
+    /// with `#` are skipped.
+    pub fn load(path: &Path, mode: Mode) -> io::Result<Env<T>> {
+        let text: String = fs::read_to_string(path)?;
+        let mut env: Env<T> = Env::new();
+        for (n, line) in text.lines().enumerate() {
+            let line: &str = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |message: String| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: {}", path.display(), n + 1, message),
+                )
+            };
+            let Statement::Let { name, value } =
+                parser::parse_statement_as::<T>(line).map_err(|e| invalid(e.to_string()))?
+            else {
+                return Err(invalid(String::from("expected `name = value`")));
+            };
+            let value: T = value
+                .execute_in(&env, mode)
+                .map_err(|e| invalid(e.to_string()))?;
+            env.set(&name, value);
+        }
+        Ok(env)
+    }
+}
+
+/// Whether `name` is a numbered result like `$2` rather than a variable.
+pub fn is_result(name: &str) -> bool {
+    name.starts_with('$')
+}
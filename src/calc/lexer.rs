@@ -0,0 +1,175 @@
+//! Splits calculator input into tokens.
+
+use super::{ParseError, ParseErrorKind};
+
+/// What a token is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind<'a> {
+    /// Decimal digits with an optional fraction and exponent like `1.5` or
+    /// `2e-3`, or hex, octal or binary digits after `0x`, `0o` or `0b`,
+    /// without a sign. Like in Rust, `_` may separate the digits: `0b1111_0000`.
+    Number(&'a str),
+    /// A name like `x` or `total_2`.
+    Ident(&'a str),
+    /// An earlier result like `$2`.
+    History(&'a str),
+    /// `=`
+    Equals,
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+    /// `*`
+    Star,
+    /// `/`
+    Slash,
+    /// `%`
+    Percent,
+    /// `^`
+    Caret,
+    /// `(`
+    LParen,
+    /// `)`
+    RParen,
+    /// `&`
+    Amp,
+    /// `|`
+    Pipe,
+    /// `<<`
+    Shl,
+    /// `>>`
+    Shr,
+    /// `[`
+    LBracket,
+    /// `]`
+    RBracket,
+    /// `,`
+    Comma,
+}
+
+/// A token and the byte offset where it starts in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    /// What the token is.
+    pub kind: TokenKind<'a>,
+    /// Byte offset of the token's first character.
+    pub offset: usize,
+}
+
+impl Token<'_> {
+    /// Byte offset just after the token's last character.
+    pub fn end(&self) -> usize {
+        let len: usize = match self.kind {
+            TokenKind::Number(text) | TokenKind::Ident(text) | TokenKind::History(text) => {
+                text.len()
+            }
+            TokenKind::Shl | TokenKind::Shr => 2,
+            _ => 1,
+        };
+        self.offset + len
+    }
+}
+
+/// Splits `input` into tokens, skipping whitespace.
+pub fn tokenize(input: &str) -> Result<Vec<Token<'_>>, ParseError> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((offset, c)) = chars.next() {
+        let kind: TokenKind = match c {
+            c if c.is_whitespace() => continue,
+            '+' => TokenKind::Plus,
+            '-' => TokenKind::Minus,
+            '*' => TokenKind::Star,
+            '/' => TokenKind::Slash,
+            '%' => TokenKind::Percent,
+            '^' => TokenKind::Caret,
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            '=' => TokenKind::Equals,
+            '&' => TokenKind::Amp,
+            '|' => TokenKind::Pipe,
+            '[' => TokenKind::LBracket,
+            ']' => TokenKind::RBracket,
+            ',' => TokenKind::Comma,
+            '<' | '>' if chars.peek().map(|&(_, next)| next) == Some(c) => {
+                chars.next();
+                if c == '<' {
+                    TokenKind::Shl
+                } else {
+                    TokenKind::Shr
+                }
+            }
+            '$' if matches!(chars.peek(), Some((_, '0'..='9'))) => {
+                let mut end: usize = offset + 1;
+                while let Some(&(i, '0'..='9')) = chars.peek() {
+                    end = i + 1;
+                    chars.next();
+                }
+                TokenKind::History(&input[offset..end])
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut end: usize = offset + 1;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + 1;
+                    chars.next();
+                }
+                TokenKind::Ident(&input[offset..end])
+            }
+            '0'..='9' => {
+                let mut end: usize = offset + 1;
+                // After `0x`, `0o` or `0b` letters are digits too, a wrong
+                // one like the 2 in `0b12` is reported when parsing the number
+                let prefixed: bool = c == '0'
+                    && matches!(chars.peek(), Some((_, 'x' | 'o' | 'b')))
+                    && input[offset + 2..].starts_with(|c: char| c.is_ascii_alphanumeric());
+                while let Some(&(i, c)) = chars.peek() {
+                    let digit: bool = if prefixed {
+                        c.is_ascii_alphanumeric() || c == '_'
+                    } else {
+                        c.is_ascii_digit() || c == '_'
+                    };
+                    if !digit {
+                        break;
+                    }
+                    end = i + 1;
+                    chars.next();
+                }
+                // A `.` is only part of the number when digits follow it
+                let fraction: &str = &input[end..];
+                if !prefixed
+                    && fraction.starts_with('.')
+                    && fraction[1..].starts_with(|c: char| c.is_ascii_digit())
+                {
+                    chars.next();
+                    while let Some(&(i, '0'..='9')) = chars.peek() {
+                        end = i + 1;
+                        chars.next();
+                    }
+                }
+                // So is an exponent like `e5` or `e-5` right after the
+                // digits, while `3 e5` is still 3 times `e5`
+                let exponent: &str = input[end..]
+                    .strip_prefix(['e', 'E'])
+                    .map_or("", |rest| rest.strip_prefix(['+', '-']).unwrap_or(rest));
+                if !prefixed && exponent.starts_with(|c: char| c.is_ascii_digit()) {
+                    let digits: usize = exponent.bytes().take_while(u8::is_ascii_digit).count();
+                    end = input.len() - exponent.len() + digits;
+                    while chars.next_if(|&(i, _)| i < end).is_some() {}
+                }
+                TokenKind::Number(&input[offset..end])
+            }
+            c => {
+                return Err(ParseError {
+                    kind: ParseErrorKind::UnexpectedChar(c),
+                    offset,
+                });
+            }
+        };
+        tokens.push(Token { kind, offset });
+    }
+    Ok(tokens)
+}
@@ -0,0 +1,501 @@
+//! Matrices and vectors of exact fractions.
+//!
+//! `[1, 2]` is a column vector and `[[1, 2], [3, 4]]` a matrix of two rows.
+//! `+` and `-` work entry by entry, `*` is the matrix product and a plain
+//! number scales a matrix. The functions are `det`, `inv`, `transpose`,
+//! `solve(a, b)`, which finds the `x` with `a * x = b`, and `emul(a, b)`,
+//! the product entry by entry.
+//!
+//! The entries are [`Rational`]s, so the inverse of `[[1, 2], [3, 4]]` is
+//! exactly `[[-2, 1], [3/2, -1/2]]`. A matrix whose determinant is zero has
+//! no inverse, which like a division by zero is an error:
+//! [`MathError::SingularMatrix`].
+
+use std::fmt;
+
+use super::number::{self, Number};
+use super::rational::Rational;
+use super::{MathError, Mode, ParseErrorKind};
+use crate::tr;
+
+/// A grid of at least one row and one column, every row as long.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    entries: Vec<Vec<Rational>>,
+}
+
+fn zero() -> Rational {
+    Rational::from_integer(0)
+}
+
+fn one() -> Rational {
+    Rational::from_integer(1)
+}
+
+impl Grid {
+    /// A grid of these rows, `None` when there are none or they differ in length.
+    pub fn new(rows: Vec<Vec<Rational>>) -> Option<Grid> {
+        let cols: usize = rows.first()?.len();
+        if cols == 0 || rows.iter().any(|row| row.len() != cols) {
+            return None;
+        }
+        Some(Grid { entries: rows })
+    }
+
+    /// A column vector.
+    pub fn column(entries: Vec<Rational>) -> Option<Grid> {
+        Grid::new(entries.into_iter().map(|entry| vec![entry]).collect())
+    }
+
+    /// The `n` by `n` identity matrix, ones on the diagonal. `None` for 0,
+    /// since a grid has at least one row.
+    pub fn identity(n: usize) -> Option<Grid> {
+        let rows: Vec<Vec<Rational>> = (0..n)
+            .map(|row| {
+                (0..n)
+                    .map(|col| if row == col { one() } else { zero() })
+                    .collect()
+            })
+            .collect();
+        Grid::new(rows)
+    }
+
+    // The identity of the size of this square grid
+    fn unit(&self) -> Grid {
+        Grid::identity(self.rows()).expect("a grid has at least one row")
+    }
+
+    /// Number of rows.
+    pub fn rows(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Number of columns.
+    pub fn cols(&self) -> usize {
+        self.entries[0].len()
+    }
+
+    /// The entry in `row` and `col`, counted from 0.
+    pub fn get(&self, row: usize, col: usize) -> Rational {
+        self.entries[row][col]
+    }
+
+    /// The size, like `2x3 matrix`.
+    pub fn shape(&self) -> String {
+        tr!("math.matrix_shape", self.rows(), self.cols())
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Grid {
+        let rows: Vec<Vec<Rational>> = (0..self.cols())
+            .map(|col| self.entries.iter().map(|row| row[col]).collect())
+            .collect();
+        Grid { entries: rows }
+    }
+
+    /// The matrix product: entry `(i, j)` is row `i` of `self` times column
+    /// `j` of `other`, so `self` needs as many columns as `other` has rows.
+    pub fn product(&self, other: &Grid, mode: Mode) -> Result<Grid, MathError> {
+        if self.cols() != other.rows() {
+            return Err(MathError::ShapeMismatch(self.shape(), other.shape()));
+        }
+        let mut rows: Vec<Vec<Rational>> = Vec::new();
+        for row in &self.entries {
+            let mut out: Vec<Rational> = Vec::new();
+            for col in 0..other.cols() {
+                let mut sum: Rational = zero();
+                for (k, entry) in row.iter().enumerate() {
+                    sum = sum.add(&entry.mul(&other.entries[k][col], mode)?, mode)?;
+                }
+                out.push(sum);
+            }
+            rows.push(out);
+        }
+        Ok(Grid { entries: rows })
+    }
+
+    /// Combines the entries at the same place, both grids need the same size.
+    pub fn zip(
+        &self,
+        other: &Grid,
+        mode: Mode,
+        f: fn(&Rational, &Rational, Mode) -> Result<Rational, MathError>,
+    ) -> Result<Grid, MathError> {
+        if self.rows() != other.rows() || self.cols() != other.cols() {
+            return Err(MathError::ShapeMismatch(self.shape(), other.shape()));
+        }
+        let rows: Vec<Vec<Rational>> = self
+            .entries
+            .iter()
+            .zip(&other.entries)
+            .map(|(a, b)| a.iter().zip(b).map(|(a, b)| f(a, b, mode)).collect())
+            .collect::<Result<Vec<Vec<Rational>>, MathError>>()?;
+        Ok(Grid { entries: rows })
+    }
+
+    /// Applies `f` to every entry.
+    pub fn map(
+        &self,
+        f: impl Fn(&Rational) -> Result<Rational, MathError>,
+    ) -> Result<Grid, MathError> {
+        let rows: Vec<Vec<Rational>> = self
+            .entries
+            .iter()
+            .map(|row| row.iter().map(&f).collect())
+            .collect::<Result<Vec<Vec<Rational>>, MathError>>()?;
+        Ok(Grid { entries: rows })
+    }
+
+    fn square(&self) -> Result<usize, MathError> {
+        if self.rows() == self.cols() {
+            Ok(self.rows())
+        } else {
+            Err(MathError::NotSquare(self.shape()))
+        }
+    }
+
+    /// The determinant, by elimination: below the diagonal every row gets
+    /// a multiple of the pivot row subtracted, which leaves the determinant
+    /// as it is. Then it is the product of the diagonal, negated for every
+    /// swap of two rows.
+    pub fn determinant(&self, mode: Mode) -> Result<Rational, MathError> {
+        let n: usize = self.square()?;
+        let mut a: Vec<Vec<Rational>> = self.entries.clone();
+        let mut det: Rational = one();
+        for col in 0..n {
+            let Some(pivot) = (col..n).find(|&row| a[row][col] != zero()) else {
+                return Ok(zero());
+            };
+            if pivot != col {
+                a.swap(pivot, col);
+                det = det.neg(mode)?;
+            }
+            det = det.mul(&a[col][col], mode)?;
+            let pivot_row: Vec<Rational> = a[col].clone();
+            for row in &mut a[col + 1..] {
+                let factor: Rational = row[col].div(&pivot_row[col], mode)?;
+                subtract(row, &pivot_row, factor, mode)?;
+            }
+        }
+        Ok(det)
+    }
+
+    /// The matrix that multiplied with this one gives the identity.
+    pub fn inverse(&self, mode: Mode) -> Result<Grid, MathError> {
+        self.square()?;
+        self.solve(&self.unit(), mode)
+    }
+
+    /// The `x` with `self * x = b`, where `b` has a column per system.
+    ///
+    /// Gauss-Jordan elimination: the same row operations that turn `self`
+    /// into the identity turn `b` into `x`.
+    pub fn solve(&self, b: &Grid, mode: Mode) -> Result<Grid, MathError> {
+        let n: usize = self.square()?;
+        if b.rows() != n {
+            return Err(MathError::ShapeMismatch(self.shape(), b.shape()));
+        }
+        let mut a: Vec<Vec<Rational>> = self.entries.clone();
+        let mut x: Vec<Vec<Rational>> = b.entries.clone();
+        for col in 0..n {
+            let pivot: usize = (col..n)
+                .find(|&row| a[row][col] != zero())
+                .ok_or(MathError::SingularMatrix)?;
+            a.swap(pivot, col);
+            x.swap(pivot, col);
+            let scale: Rational = a[col][col];
+            for entry in a[col].iter_mut().chain(x[col].iter_mut()) {
+                *entry = entry.div(&scale, mode)?;
+            }
+            let (pivot_a, pivot_x) = (a[col].clone(), x[col].clone());
+            for row in (0..n).filter(|&row| row != col) {
+                let factor: Rational = a[row][col];
+                subtract(&mut a[row], &pivot_a, factor, mode)?;
+                subtract(&mut x[row], &pivot_x, factor, mode)?;
+            }
+        }
+        Ok(Grid { entries: x })
+    }
+}
+
+// row = row - factor * pivot
+fn subtract(
+    row: &mut [Rational],
+    pivot: &[Rational],
+    factor: Rational,
+    mode: Mode,
+) -> Result<(), MathError> {
+    for (entry, p) in row.iter_mut().zip(pivot) {
+        *entry = entry.sub(&factor.mul(p, mode)?, mode)?;
+    }
+    Ok(())
+}
+
+/// A column vector prints like `[1, 2]`, other matrices row by row like
+/// `[[1, 2], [3, 4]]`, which both read back as calculator input.
+impl fmt::Display for Grid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let row = |row: &Vec<Rational>| {
+            let entries: Vec<String> = row.iter().map(|entry| entry.to_string()).collect();
+            format!("[{}]", entries.join(", "))
+        };
+        if self.cols() == 1 {
+            return write!(f, "{}", row(&self.transpose().entries[0]));
+        }
+        let rows: Vec<String> = self.entries.iter().map(row).collect();
+        write!(f, "[{}]", rows.join(", "))
+    }
+}
+
+/// A number or a matrix, what the calculator computes with in `type matrix`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Matrix {
+    /// A plain number, which scales matrices.
+    Scalar(Rational),
+    /// A matrix or vector.
+    Grid(Grid),
+}
+
+impl Matrix {
+    /// The size, like `2x3 matrix`, or `number` for a scalar.
+    pub fn shape(&self) -> String {
+        match self {
+            Matrix::Scalar(_) => tr!("math.scalar_shape").to_string(),
+            Matrix::Grid(grid) => grid.shape(),
+        }
+    }
+
+    fn mismatch(&self, other: &Matrix) -> MathError {
+        MathError::ShapeMismatch(self.shape(), other.shape())
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Matrix::Scalar(r) => write!(f, "{}", r),
+            Matrix::Grid(grid) => write!(f, "{}", grid),
+        }
+    }
+}
+
+// An entry of a literal: a number, maybe negative, maybe a fraction like 3/2
+fn parse_entry(text: &str) -> Result<Rational, ParseErrorKind> {
+    let not_a_number: ParseErrorKind = ParseErrorKind::InvalidMatrix("entries must be numbers");
+    let (numer, denom) = text.split_once('/').unwrap_or((text, "1"));
+    let numer: Rational = Rational::parse_literal(numer).map_err(|_| not_a_number)?;
+    let denom: Rational = Rational::parse_literal(denom).map_err(|_| not_a_number)?;
+    numer
+        .div(&denom, Mode::Checked)
+        .map_err(|_| ParseErrorKind::InvalidMatrix("an entry divides by zero"))
+}
+
+// The parts of `[a, b, c]` between the commas that are not inside brackets
+fn split_list(text: &str) -> Result<Vec<&str>, ParseErrorKind> {
+    let inner: &str = text
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or(ParseErrorKind::InvalidMatrix("rows must be in brackets"))?;
+    if inner.is_empty() {
+        return Err(ParseErrorKind::InvalidMatrix("it needs at least one entry"));
+    }
+    let mut parts: Vec<&str> = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in inner.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&inner[start..]);
+    Ok(parts)
+}
+
+// Checks the number of operands of `function`
+fn arity(function: &str, args: &[Matrix], n: usize) -> Result<(), MathError> {
+    if args.len() == n {
+        Ok(())
+    } else {
+        Err(MathError::ArgumentCount(function.to_string(), n))
+    }
+}
+
+// A scalar as the 1x1 matrix it acts like in `det`, `inv` and `solve`
+fn as_grid(value: &Matrix) -> Grid {
+    match value {
+        Matrix::Scalar(r) => Grid {
+            entries: vec![vec![*r]],
+        },
+        Matrix::Grid(grid) => grid.clone(),
+    }
+}
+
+// The result of a function on scalars is a scalar again
+fn like(value: &Matrix, result: Grid) -> Matrix {
+    match value {
+        Matrix::Scalar(_) => Matrix::Scalar(result.get(0, 0)),
+        Matrix::Grid(_) => Matrix::Grid(result),
+    }
+}
+
+impl Number for Matrix {
+    const NAME: &'static str = "matrix";
+    const SHAPED: bool = true;
+    const COMMUTATIVE_MUL: bool = false;
+
+    fn parse_literal(text: &str) -> Result<Self, ParseErrorKind> {
+        number::decimal_only(text, Self::NAME)?;
+        Rational::parse_literal(text)
+            .map(Matrix::Scalar)
+            .map_err(|_| ParseErrorKind::NumberTooLarge(Self::NAME))
+    }
+
+    // `[1, 2]` is a column, `[[1, 2]]` a row
+    fn parse_matrix(text: &str) -> Result<Self, ParseErrorKind> {
+        let text: String = text.split_whitespace().collect();
+        let parts: Vec<&str> = split_list(&text)?;
+        let rows: Vec<Vec<Rational>> = if parts[0].starts_with('[') {
+            parts
+                .iter()
+                .map(|row| split_list(row)?.into_iter().map(parse_entry).collect())
+                .collect::<Result<Vec<Vec<Rational>>, ParseErrorKind>>()?
+        } else {
+            let column: Vec<Rational> = parts
+                .into_iter()
+                .map(parse_entry)
+                .collect::<Result<Vec<Rational>, ParseErrorKind>>()?;
+            column.into_iter().map(|entry| vec![entry]).collect()
+        };
+        Grid::new(rows)
+            .map(Matrix::Grid)
+            .ok_or(ParseErrorKind::InvalidMatrix("the rows must be as long"))
+    }
+
+    fn is_negative(&self) -> bool {
+        matches!(self, Matrix::Scalar(r) if r.is_negative())
+    }
+
+    fn is_fraction(&self) -> bool {
+        matches!(self, Matrix::Scalar(r) if r.is_fraction())
+    }
+
+    fn neg(&self, mode: Mode) -> Result<Self, MathError> {
+        match self {
+            Matrix::Scalar(r) => Ok(Matrix::Scalar(r.neg(mode)?)),
+            Matrix::Grid(grid) => Ok(Matrix::Grid(grid.map(|entry| entry.neg(mode))?)),
+        }
+    }
+
+    fn add(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        match (self, other) {
+            (Matrix::Scalar(a), Matrix::Scalar(b)) => Ok(Matrix::Scalar(a.add(b, mode)?)),
+            (Matrix::Grid(a), Matrix::Grid(b)) => {
+                Ok(Matrix::Grid(a.zip(b, mode, Rational::add)?))
+            }
+            _ => Err(self.mismatch(other)),
+        }
+    }
+
+    fn sub(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        match (self, other) {
+            (Matrix::Scalar(a), Matrix::Scalar(b)) => Ok(Matrix::Scalar(a.sub(b, mode)?)),
+            (Matrix::Grid(a), Matrix::Grid(b)) => {
+                Ok(Matrix::Grid(a.zip(b, mode, Rational::sub)?))
+            }
+            _ => Err(self.mismatch(other)),
+        }
+    }
+
+    fn mul(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        match (self, other) {
+            (Matrix::Scalar(a), Matrix::Scalar(b)) => Ok(Matrix::Scalar(a.mul(b, mode)?)),
+            (Matrix::Scalar(k), Matrix::Grid(grid)) | (Matrix::Grid(grid), Matrix::Scalar(k)) => {
+                Ok(Matrix::Grid(grid.map(|entry| entry.mul(k, mode))?))
+            }
+            (Matrix::Grid(a), Matrix::Grid(b)) => Ok(Matrix::Grid(a.product(b, mode)?)),
+        }
+    }
+
+    // Only by a number, `a * inv(b)` divides by a matrix
+    fn div(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        match (self, other) {
+            (Matrix::Scalar(a), Matrix::Scalar(b)) => Ok(Matrix::Scalar(a.div(b, mode)?)),
+            (Matrix::Grid(grid), Matrix::Scalar(k)) => {
+                Ok(Matrix::Grid(grid.map(|entry| entry.div(k, mode))?))
+            }
+            (_, Matrix::Grid(_)) => Err(MathError::DivideByMatrix),
+        }
+    }
+
+    fn rem(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        match (self, other) {
+            (Matrix::Scalar(a), Matrix::Scalar(b)) => Ok(Matrix::Scalar(a.rem(b, mode)?)),
+            _ => Err(self.mismatch(other)),
+        }
+    }
+
+    // A square matrix to a whole power, by squaring: a^-1 is the inverse
+    fn pow(&self, exponent: &Self, mode: Mode) -> Result<Self, MathError> {
+        let (base, n) = match (self, exponent) {
+            (Matrix::Scalar(a), Matrix::Scalar(b)) => return Ok(Matrix::Scalar(a.pow(b, mode)?)),
+            (Matrix::Grid(grid), Matrix::Scalar(n)) if n.is_integer() => (grid, n.numer()),
+            (Matrix::Grid(_), Matrix::Scalar(_)) => return Err(MathError::FractionalExponent),
+            _ => return Err(self.mismatch(exponent)),
+        };
+        base.square()?;
+        let mut base: Grid = if n < 0 {
+            base.inverse(mode)?
+        } else {
+            base.clone()
+        };
+        let mut result: Grid = base.unit();
+        let mut n: u64 = n.unsigned_abs();
+        while n > 0 {
+            if n % 2 == 1 {
+                result = result.product(&base, mode)?;
+            }
+            n /= 2;
+            if n > 0 {
+                base = base.product(&base, mode)?;
+            }
+        }
+        Ok(Matrix::Grid(result))
+    }
+
+    fn call(function: &str, args: &[Self], mode: Mode) -> Result<Self, MathError> {
+        match function {
+            "det" => {
+                arity(function, args, 1)?;
+                Ok(Matrix::Scalar(as_grid(&args[0]).determinant(mode)?))
+            }
+            "inv" => {
+                arity(function, args, 1)?;
+                Ok(like(&args[0], as_grid(&args[0]).inverse(mode)?))
+            }
+            "transpose" => {
+                arity(function, args, 1)?;
+                Ok(like(&args[0], as_grid(&args[0]).transpose()))
+            }
+            "solve" => {
+                arity(function, args, 2)?;
+                let x: Grid = as_grid(&args[0]).solve(&as_grid(&args[1]), mode)?;
+                Ok(like(&args[1], x))
+            }
+            "emul" => {
+                arity(function, args, 2)?;
+                match (&args[0], &args[1]) {
+                    (Matrix::Grid(a), Matrix::Grid(b)) => {
+                        Ok(Matrix::Grid(a.zip(b, mode, Rational::mul)?))
+                    }
+                    (a, b) => a.mul(b, mode),
+                }
+            }
+            _ => Err(MathError::UnknownFunction(function.to_string())),
+        }
+    }
+}
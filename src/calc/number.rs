@@ -0,0 +1,453 @@
+//! The number types an [`Operation`](super::Operation) can compute with.
+//!
+//! Every type brings its own arithmetic: `i32` and `i64` truncate divisions
+//! and handle overflow as the [`Mode`] says, `f64` divides exactly up to
+//! rounding, and [`Rational`](super::rational::Rational) divides exactly.
+//! Only the integers have the bitwise operators and print in other bases.
+
+use std::fmt::{self, Debug, Display};
+use std::num::IntErrorKind;
+
+use super::{MathError, Mode, ParseErrorKind};
+
+/// The base integers are written in, like the literals of the
+/// `data_types` lesson: `255`, `0xff`, `0o377` or `0b11111111`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Base {
+    /// Base 10, without a prefix.
+    #[default]
+    Decimal,
+    /// Base 16, `0x`
+    Hex,
+    /// Base 8, `0o`
+    Octal,
+    /// Base 2, `0b`
+    Binary,
+}
+
+impl Base {
+    /// Every base.
+    pub const ALL: [Base; 4] = [Base::Decimal, Base::Hex, Base::Octal, Base::Binary];
+
+    /// The name of the base, e.g. `hex`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Base::Decimal => "dec",
+            Base::Hex => "hex",
+            Base::Octal => "oct",
+            Base::Binary => "bin",
+        }
+    }
+
+    /// Parses a [`name`](Base::name).
+    pub fn from_name(name: &str) -> Option<Base> {
+        Base::ALL.into_iter().find(|base| base.name() == name)
+    }
+
+    /// The base of a literal like `0xff` and its digits, `None` for decimals.
+    pub fn split_prefix(literal: &str) -> Option<(Base, &str)> {
+        let base: Base = match literal.get(..2)? {
+            "0x" => Base::Hex,
+            "0o" => Base::Octal,
+            "0b" => Base::Binary,
+            _ => return None,
+        };
+        Some((base, &literal[2..]))
+    }
+
+    /// 16 for [`Base::Hex`] and so on.
+    pub fn radix(self) -> u32 {
+        match self {
+            Base::Decimal => 10,
+            Base::Hex => 16,
+            Base::Octal => 8,
+            Base::Binary => 2,
+        }
+    }
+}
+
+impl fmt::Display for Base {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Parse error for a literal like `0xff` of a type that only reads decimals.
+pub(super) fn decimal_only(text: &str, name: &'static str) -> Result<(), ParseErrorKind> {
+    match Base::split_prefix(text.trim_start_matches('-')) {
+        Some(_) => Err(ParseErrorKind::NotDecimal(name)),
+        None => Ok(()),
+    }
+}
+
+/// Reads a decimal literal of a type computing with `f64`, like `1.5e3`.
+/// A literal beyond the range of `f64`, like `1e999`, is too large rather
+/// than infinite.
+pub(super) fn parse_f64(text: &str, name: &'static str) -> Result<f64, ParseErrorKind> {
+    decimal_only(text, name)?;
+    match text.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(ParseErrorKind::NumberTooLarge(name)),
+    }
+}
+
+/// Arithmetic on one number type.
+///
+/// Dividing by zero is [`MathError::DivideByZero`] for every type and mode.
+pub trait Number: Clone + Debug + Display + PartialEq {
+    /// The name of the type at the calculator prompt, e.g. `i64`.
+    const NAME: &'static str;
+
+    /// Whether values have a dimension or a shape that the numbers `0` and
+    /// `1` lack, like `3 m` or a matrix. [`simplify`](super::algebra::simplify)
+    /// then keeps `x + 0`, `x - x`, `0 * x`, `x^0` and `1^x`, which would
+    /// hide an error or drop the dimension of `x`.
+    const SHAPED: bool = false;
+
+    /// Whether `a * b` always equals `b * a`, which is not so for matrices.
+    /// [`simplify`](super::algebra::simplify) only reorders the operands of
+    /// `*` when it does.
+    const COMMUTATIVE_MUL: bool = true;
+
+    /// Whether `/` divides without truncating, which is not so for the
+    /// integers. [`derive`](super::algebra::derive) only differentiates
+    /// quotients when it does, since `(x / 2)'` would fold to `2 / 4 = 0`.
+    const EXACT_DIV: bool = true;
+
+    /// Parses a literal like `42`, `-7` or `1.5` from the calculator input.
+    fn parse_literal(text: &str) -> Result<Self, ParseErrorKind>;
+
+    /// Parses a literal in brackets like `[[1, 2], [3, 4]]`. Only
+    /// [`Matrix`](super::matrix::Matrix) has them.
+    fn parse_matrix(_text: &str) -> Result<Self, ParseErrorKind> {
+        Err(ParseErrorKind::NoMatrices(Self::NAME))
+    }
+
+    /// Whether the value is below zero, it then prints with a leading `-`.
+    fn is_negative(&self) -> bool;
+
+    /// Whether the value prints like a fraction or a product, e.g. `1/2`
+    /// or `3 m`.
+    fn is_fraction(&self) -> bool {
+        false
+    }
+
+    /// Whether the value prints like a sum, e.g. `3 + 4i`.
+    fn is_sum(&self) -> bool {
+        false
+    }
+
+    /// The value of a unit like `km` or of the imaginary unit `i`, which a
+    /// variable of the same name hides. Only
+    /// [`Quantity`](super::units::Quantity) and
+    /// [`Complex`](super::complex::Complex) have units.
+    fn unit(_name: &str) -> Option<Self> {
+        None
+    }
+
+    /// `-self`
+    fn neg(&self, mode: Mode) -> Result<Self, MathError>;
+    /// `self + other`
+    fn add(&self, other: &Self, mode: Mode) -> Result<Self, MathError>;
+    /// `self - other`
+    fn sub(&self, other: &Self, mode: Mode) -> Result<Self, MathError>;
+    /// `self * other`
+    fn mul(&self, other: &Self, mode: Mode) -> Result<Self, MathError>;
+    /// `self / other`
+    fn div(&self, other: &Self, mode: Mode) -> Result<Self, MathError>;
+    /// `self % other`, with the sign of `self`.
+    fn rem(&self, other: &Self, mode: Mode) -> Result<Self, MathError>;
+    /// `self ^ exponent`
+    fn pow(&self, exponent: &Self, mode: Mode) -> Result<Self, MathError>;
+
+    /// `self & other`, integers only.
+    fn bit_and(&self, _other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Err(MathError::NotAnInteger)
+    }
+    /// `self | other`, integers only.
+    fn bit_or(&self, _other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Err(MathError::NotAnInteger)
+    }
+    /// `self xor other`, integers only.
+    fn bit_xor(&self, _other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Err(MathError::NotAnInteger)
+    }
+    /// `self << amount`, integers only.
+    fn shl(&self, _amount: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Err(MathError::NotAnInteger)
+    }
+    /// `self >> amount`, integers only.
+    fn shr(&self, _amount: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Err(MathError::NotAnInteger)
+    }
+
+    /// `function(args)`, for the functions of the type like `det`.
+    fn call(function: &str, _args: &[Self], _mode: Mode) -> Result<Self, MathError> {
+        Err(MathError::UnknownFunction(function.to_string()))
+    }
+
+    /// The value written in `base`. Types that are not integers always
+    /// write decimals.
+    fn to_base(&self, _base: Base) -> String {
+        self.to_string()
+    }
+}
+
+// Integers use the checked, wrapping or saturating methods of the standard
+// library. A checked operation that fails tells which way it left the range
+// by the signs of its operands.
+//
+// `$u` is the unsigned type of the same width. Hex, octal and binary go
+// through it, so they are the bits of two's complement: 0xff is -1 as an i8.
+macro_rules! impl_integer {
+    ($t:ty, $u:ty) => {
+        impl Number for $t {
+            const NAME: &'static str = stringify!($t);
+            const EXACT_DIV: bool = false;
+
+            fn parse_literal(text: &str) -> Result<Self, ParseErrorKind> {
+                if text.contains('.') {
+                    return Err(ParseErrorKind::NotAWholeNumber(Self::NAME));
+                }
+                let (negative, unsigned) = match text.strip_prefix('-') {
+                    Some(rest) => (true, rest),
+                    None => (false, text),
+                };
+                let Some((base, digits)) = Base::split_prefix(unsigned) else {
+                    if text.contains(['e', 'E']) {
+                        return Err(ParseErrorKind::Exponent(Self::NAME));
+                    }
+                    return text
+                        .parse()
+                        .map_err(|_| ParseErrorKind::NumberTooLarge(Self::NAME));
+                };
+                let bits: $u =
+                    <$u>::from_str_radix(digits, base.radix()).map_err(|e| match e.kind() {
+                        IntErrorKind::PosOverflow => ParseErrorKind::NumberTooLarge(Self::NAME),
+                        _ => ParseErrorKind::InvalidDigit(base.name()),
+                    })?;
+                let value: $t = bits as $t;
+                Ok(if negative {
+                    value.wrapping_neg()
+                } else {
+                    value
+                })
+            }
+
+            fn is_negative(&self) -> bool {
+                *self < 0
+            }
+
+            fn neg(&self, mode: Mode) -> Result<Self, MathError> {
+                match mode {
+                    Mode::Checked => self.checked_neg().ok_or(MathError::Overflow),
+                    Mode::Wrapping => Ok(self.wrapping_neg()),
+                    Mode::Saturating => Ok(self.saturating_neg()),
+                }
+            }
+
+            fn add(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+                match mode {
+                    Mode::Checked => self.checked_add(*other).ok_or(if *other > 0 {
+                        MathError::Overflow
+                    } else {
+                        MathError::Underflow
+                    }),
+                    Mode::Wrapping => Ok(self.wrapping_add(*other)),
+                    Mode::Saturating => Ok(self.saturating_add(*other)),
+                }
+            }
+
+            fn sub(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+                match mode {
+                    Mode::Checked => self.checked_sub(*other).ok_or(if *other < 0 {
+                        MathError::Overflow
+                    } else {
+                        MathError::Underflow
+                    }),
+                    Mode::Wrapping => Ok(self.wrapping_sub(*other)),
+                    Mode::Saturating => Ok(self.saturating_sub(*other)),
+                }
+            }
+
+            fn mul(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+                match mode {
+                    Mode::Checked => {
+                        self.checked_mul(*other)
+                            .ok_or(if (*self < 0) != (*other < 0) {
+                                MathError::Underflow
+                            } else {
+                                MathError::Overflow
+                            })
+                    }
+                    Mode::Wrapping => Ok(self.wrapping_mul(*other)),
+                    Mode::Saturating => Ok(self.saturating_mul(*other)),
+                }
+            }
+
+            // Truncates towards zero. `MIN / -1` is the one division that overflows
+            fn div(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+                if *other == 0 {
+                    return Err(MathError::DivideByZero);
+                }
+                match mode {
+                    Mode::Checked => self.checked_div(*other).ok_or(MathError::Overflow),
+                    Mode::Wrapping => Ok(self.wrapping_div(*other)),
+                    Mode::Saturating => Ok(self.saturating_div(*other)),
+                }
+            }
+
+            // `MIN % -1` is 0, even though the `%` operator panics on it
+            fn rem(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+                if *other == 0 {
+                    return Err(MathError::DivideByZero);
+                }
+                Ok(self.wrapping_rem(*other))
+            }
+
+            // Exponents above `u32::MAX` only fit 0, 1 and -1, every other
+            // base overflows
+            fn pow(&self, exponent: &Self, mode: Mode) -> Result<Self, MathError> {
+                if *exponent < 0 {
+                    return Err(MathError::NegativeExponent);
+                }
+                let negative: bool = *self < 0 && *exponent % 2 == 1;
+                let too_large: MathError = if negative {
+                    MathError::Underflow
+                } else {
+                    MathError::Overflow
+                };
+                match (u32::try_from(*exponent), *self) {
+                    (Ok(exponent), _) => match mode {
+                        Mode::Checked => self.checked_pow(exponent).ok_or(too_large),
+                        Mode::Wrapping => Ok(self.wrapping_pow(exponent)),
+                        Mode::Saturating => Ok(self.saturating_pow(exponent)),
+                    },
+                    (Err(_), 0 | 1) => Ok(*self),
+                    (Err(_), -1) => Ok(if negative { -1 } else { 1 }),
+                    (Err(_), _) => match mode {
+                        Mode::Checked => Err(too_large),
+                        Mode::Wrapping => {
+                            let (mut base, mut exponent, mut result) = (*self, *exponent, 1);
+                            while exponent > 0 {
+                                if exponent % 2 == 1 {
+                                    result = base.wrapping_mul(result);
+                                }
+                                base = base.wrapping_mul(base);
+                                exponent /= 2;
+                            }
+                            Ok(result)
+                        }
+                        Mode::Saturating => Ok(if negative { <$t>::MIN } else { <$t>::MAX }),
+                    },
+                }
+            }
+
+            fn bit_and(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+                Ok(self & other)
+            }
+
+            fn bit_or(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+                Ok(self | other)
+            }
+
+            fn bit_xor(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+                Ok(self ^ other)
+            }
+
+            // Wrapping takes the amount modulo the number of bits, like
+            // `wrapping_shl`. The other modes refuse shifting every bit out
+            fn shl(&self, amount: &Self, mode: Mode) -> Result<Self, MathError> {
+                let amount: u32 = u32::try_from(*amount).map_err(|_| MathError::InvalidShift)?;
+                match mode {
+                    Mode::Wrapping => Ok(self.wrapping_shl(amount)),
+                    _ => self.checked_shl(amount).ok_or(MathError::InvalidShift),
+                }
+            }
+
+            fn shr(&self, amount: &Self, mode: Mode) -> Result<Self, MathError> {
+                let amount: u32 = u32::try_from(*amount).map_err(|_| MathError::InvalidShift)?;
+                match mode {
+                    Mode::Wrapping => Ok(self.wrapping_shr(amount)),
+                    _ => self.checked_shr(amount).ok_or(MathError::InvalidShift),
+                }
+            }
+
+            fn to_base(&self, base: Base) -> String {
+                let bits: $u = *self as $u;
+                match base {
+                    Base::Decimal => self.to_string(),
+                    Base::Hex => format!("{:#x}", bits),
+                    Base::Octal => format!("{:#o}", bits),
+                    Base::Binary => format!("{:#b}", bits),
+                }
+            }
+        }
+    };
+}
+
+impl_integer!(i8, u8);
+impl_integer!(i16, u16);
+impl_integer!(i32, u32);
+impl_integer!(i64, u64);
+impl_integer!(i128, u128);
+
+// In checked mode a result that became infinite overflowed, and one that
+// is no number, like (-8)^0.5, is an error too. The other modes keep IEEE
+// 754 behaviour and return the infinity or NaN.
+pub(super) fn finite(value: f64, mode: Mode) -> Result<f64, MathError> {
+    match mode {
+        Mode::Checked if value.is_nan() => Err(MathError::NotANumber),
+        Mode::Checked if value == f64::INFINITY => Err(MathError::Overflow),
+        Mode::Checked if value == f64::NEG_INFINITY => Err(MathError::Underflow),
+        Mode::Saturating => Ok(value.clamp(f64::MIN, f64::MAX)),
+        _ => Ok(value),
+    }
+}
+
+impl Number for f64 {
+    const NAME: &'static str = "f64";
+
+    fn parse_literal(text: &str) -> Result<Self, ParseErrorKind> {
+        parse_f64(text, Self::NAME)
+    }
+
+    fn is_negative(&self) -> bool {
+        self.is_sign_negative() && *self != 0.0
+    }
+
+    fn neg(&self, _mode: Mode) -> Result<Self, MathError> {
+        Ok(-self)
+    }
+
+    fn add(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        finite(self + other, mode)
+    }
+
+    fn sub(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        finite(self - other, mode)
+    }
+
+    fn mul(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        finite(self * other, mode)
+    }
+
+    // Does not truncate: 20 / 3 is 6.666666666666667
+    fn div(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        if *other == 0.0 {
+            return Err(MathError::DivideByZero);
+        }
+        finite(self / other, mode)
+    }
+
+    fn rem(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        if *other == 0.0 {
+            return Err(MathError::DivideByZero);
+        }
+        finite(self % other, mode)
+    }
+
+    fn pow(&self, exponent: &Self, mode: Mode) -> Result<Self, MathError> {
+        finite(self.powf(*exponent), mode)
+    }
+}
@@ -0,0 +1,340 @@
+//! The tree of an expression, how it is computed and printed, and why
+//! computing it can fail.
+//!
+//! The enums lesson runs an [`Operation`], and the calculator parses text
+//! into one.
+
+use std::error::Error;
+use std::fmt;
+
+use super::env::Env;
+use super::number::Number;
+use crate::tr;
+
+// A binary method of `Number`, like `Number::add`
+type Apply<T> = fn(&T, &T, Mode) -> Result<T, MathError>;
+
+/// An arithmetic expression on numbers of type `T`, `i32` unless said otherwise.
+///
+/// The operands are operations themselves, so an `Operation` is a tree like
+/// `(1 + 2) * 3`. A recursive enum needs the `Box`es: without them an
+/// `Operation` would contain itself and have no known size.
+/// [`parse`](super::parse) builds one from text like `20 / 0`.
+/// `T` is any [`Number`], which decides what the arithmetic does, e.g.
+/// whether `20 / 3` is `6`, `6.666666666666667` or `20/3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation<T = i32> {
+    /// A number.
+    Value(T),
+    /// A variable, looked up in an [`Env`] when executed.
+    Var(String),
+    /// `-a`
+    Neg(Box<Operation<T>>),
+    /// `a + b`
+    Add(Box<Operation<T>>, Box<Operation<T>>),
+    /// `a * b`
+    Mul(Box<Operation<T>>, Box<Operation<T>>),
+    /// `first - second`
+    Sub {
+        /// Left operand.
+        first: Box<Operation<T>>,
+        /// Right operand.
+        second: Box<Operation<T>>,
+    },
+    /// `divident / divisor`. How the quotient is rounded is up to
+    /// [`Number::div`]: the integers truncate towards zero, `rational` is exact.
+    Div {
+        /// Left operand.
+        divident: Box<Operation<T>>,
+        /// Right operand.
+        divisor: Box<Operation<T>>,
+    },
+    /// `divident % divisor`, with the sign of the divident.
+    Rem {
+        /// Left operand.
+        divident: Box<Operation<T>>,
+        /// Right operand.
+        divisor: Box<Operation<T>>,
+    },
+    /// `base ^ exponent`
+    Pow {
+        /// Left operand.
+        base: Box<Operation<T>>,
+        /// Right operand. Which exponents work is up to [`Number::pow`],
+        /// e.g. the integers refuse negative ones.
+        exponent: Box<Operation<T>>,
+    },
+    /// `a & b`, the bits set in both integers.
+    BitAnd(Box<Operation<T>>, Box<Operation<T>>),
+    /// `a | b`, the bits set in either integer.
+    BitOr(Box<Operation<T>>, Box<Operation<T>>),
+    /// `a xor b`, the bits set in exactly one integer. Rust writes it `a ^ b`,
+    /// but here `^` is a power.
+    BitXor(Box<Operation<T>>, Box<Operation<T>>),
+    /// `value << amount`
+    Shl {
+        /// Left operand.
+        value: Box<Operation<T>>,
+        /// Right operand, below the number of bits of the type.
+        amount: Box<Operation<T>>,
+    },
+    /// `value >> amount`, keeping the sign of `value`.
+    Shr {
+        /// Left operand.
+        value: Box<Operation<T>>,
+        /// Right operand, below the number of bits of the type.
+        amount: Box<Operation<T>>,
+    },
+    /// `function(args)`, like `det(a)`. Which functions exist depends on `T`.
+    Call {
+        /// The name of the function.
+        function: String,
+        /// The operands, in order.
+        args: Vec<Operation<T>>,
+    },
+}
+
+/// Why an [`Operation`] has no result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MathError {
+    /// The divisor is zero.
+    DivideByZero,
+    /// The result is larger than the largest value of its type, e.g. `i32::MAX`.
+    Overflow,
+    /// The result is smaller than the smallest value of its type, e.g. `i32::MIN`.
+    Underflow,
+    /// The exponent of an integer power is negative.
+    NegativeExponent,
+    /// The exponent of a rational power is not a whole number.
+    FractionalExponent,
+    /// A variable that was never assigned.
+    UndefinedVariable(String),
+    /// A bitwise operation on a type that is not an integer, e.g. `f64`.
+    NotAnInteger,
+    /// A shift by a negative amount or by at least the number of bits.
+    InvalidShift,
+    /// Adding, subtracting or converting quantities of different
+    /// dimensions, like metres and seconds.
+    DimensionMismatch(String, String),
+    /// A quantity with units raised to a power that is not a whole number.
+    FractionalUnit(String),
+    /// A function the number type does not have.
+    UnknownFunction(String),
+    /// A function called with the wrong number of operands: the function
+    /// and how many it takes.
+    ArgumentCount(String, usize),
+    /// Matrices whose sizes do not fit the operation, like adding a 2x2
+    /// and a 3x3 matrix.
+    ShapeMismatch(String, String),
+    /// A matrix that would need to be square, like for a determinant.
+    NotSquare(String),
+    /// A matrix with a determinant of zero, which has no inverse.
+    SingularMatrix,
+    /// A division by a matrix, which `a * inv(b)` writes instead.
+    DivideByMatrix,
+    /// A complex number with an imaginary part where only a real one works,
+    /// like in `%`.
+    NotReal(String),
+    /// A floating point result that is NaN, like the power `(-8)^0.5`.
+    NotANumber,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MathError::DivideByZero => write!(f, "{}", tr!("math.divide_by_zero")),
+            MathError::Overflow => write!(f, "{}", tr!("math.overflow")),
+            MathError::Underflow => write!(f, "{}", tr!("math.underflow")),
+            MathError::NegativeExponent => write!(f, "{}", tr!("math.negative_exponent")),
+            MathError::FractionalExponent => write!(f, "{}", tr!("math.fractional_exponent")),
+            MathError::UndefinedVariable(name) => {
+                write!(f, "{}", tr!("math.undefined_variable", name))
+            }
+            MathError::NotAnInteger => write!(f, "{}", tr!("math.not_an_integer")),
+            MathError::InvalidShift => write!(f, "{}", tr!("math.invalid_shift")),
+            MathError::DimensionMismatch(a, b) => {
+                write!(f, "{}", tr!("math.dimension_mismatch", a, b))
+            }
+            MathError::FractionalUnit(unit) => {
+                write!(f, "{}", tr!("math.fractional_unit", unit))
+            }
+            MathError::UnknownFunction(name) => {
+                write!(f, "{}", tr!("math.unknown_function", name))
+            }
+            MathError::ArgumentCount(name, count) => {
+                write!(f, "{}", tr!("math.argument_count", name, count))
+            }
+            MathError::ShapeMismatch(a, b) => write!(f, "{}", tr!("math.shape_mismatch", a, b)),
+            MathError::NotSquare(shape) => write!(f, "{}", tr!("math.not_square", shape)),
+            MathError::SingularMatrix => write!(f, "{}", tr!("math.singular_matrix")),
+            MathError::DivideByMatrix => write!(f, "{}", tr!("math.divide_by_matrix")),
+            MathError::NotReal(value) => write!(f, "{}", tr!("math.not_real", value)),
+            MathError::NotANumber => write!(f, "{}", tr!("math.not_a_number")),
+        }
+    }
+}
+
+impl Error for MathError {}
+
+/// What [`Operation::execute_with`] does with results that do not fit in their type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Returns [`MathError::Overflow`] or [`MathError::Underflow`].
+    #[default]
+    Checked,
+    /// Wraps around, like `i32::wrapping_add`.
+    Wrapping,
+    /// Clamps to the smallest or largest value, like `i32::saturating_add`.
+    Saturating,
+}
+
+impl Mode {
+    /// Every mode.
+    pub const ALL: [Mode; 3] = [Mode::Checked, Mode::Wrapping, Mode::Saturating];
+
+    /// The name of the mode, e.g. `wrapping`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Mode::Checked => "checked",
+            Mode::Wrapping => "wrapping",
+            Mode::Saturating => "saturating",
+        }
+    }
+
+    /// Parses a [`name`](Mode::name).
+    pub fn from_name(name: &str) -> Option<Mode> {
+        Mode::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+}
+
+impl<T: Number> Operation<T> {
+    /// Computes the result, or an error when dividing by zero or leaving the range of `T`.
+    pub fn execute(&self) -> Result<T, MathError> {
+        self.execute_with(Mode::Checked)
+    }
+
+    /// Computes the result, handling results outside the range of `T` as `mode` says.
+    ///
+    /// Every operation in the tree is computed in `mode`. Dividing by zero is
+    /// an error in every mode.
+    pub fn execute_with(&self, mode: Mode) -> Result<T, MathError> {
+        self.execute_in(&Env::new(), mode)
+    }
+
+    /// Computes the result in `mode`, taking the values of variables from `env`.
+    pub fn execute_in(&self, env: &Env<T>, mode: Mode) -> Result<T, MathError> {
+        // Every level of a long row like `1 + 2 + ... + 500` recurses here,
+        // so the binary operations share one call instead of each keeping
+        // its own temporaries in the frame
+        let (left, right, apply): (&Self, &Self, Apply<T>) = match self {
+            Self::Value(v) => return Ok(v.clone()),
+            Self::Var(name) => {
+                return env
+                    .value(name)
+                    .ok_or_else(|| MathError::UndefinedVariable(name.clone()));
+            }
+            Self::Neg(a) => return a.execute_in(env, mode)?.neg(mode),
+            Self::Call { function, args } => {
+                let args: Vec<T> = args
+                    .iter()
+                    .map(|arg| arg.execute_in(env, mode))
+                    .collect::<Result<Vec<T>, MathError>>()?;
+                return T::call(function, &args, mode);
+            }
+            Self::Add(a, b) => (a, b, T::add),
+            Self::Mul(a, b) => (a, b, T::mul),
+            Self::Sub { first, second } => (first, second, T::sub),
+            Self::Div { divident, divisor } => (divident, divisor, T::div),
+            Self::Rem { divident, divisor } => (divident, divisor, T::rem),
+            Self::Pow { base, exponent } => (base, exponent, T::pow),
+            Self::BitAnd(a, b) => (a, b, T::bit_and),
+            Self::BitOr(a, b) => (a, b, T::bit_or),
+            Self::BitXor(a, b) => (a, b, T::bit_xor),
+            Self::Shl { value, amount } => (value, amount, T::shl),
+            Self::Shr { value, amount } => (value, amount, T::shr),
+        };
+        let left: T = left.execute_in(env, mode)?;
+        apply(&left, &right.execute_in(env, mode)?, mode)
+    }
+
+    // How tightly the operation binds when printed, higher binds tighter.
+    // The bitwise operators bind looser than `+`, like in Rust
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::BitOr(..) => 1,
+            Self::BitXor(..) => 2,
+            Self::BitAnd(..) => 3,
+            Self::Shl { .. } | Self::Shr { .. } => 4,
+            Self::Add(..) | Self::Sub { .. } => 5,
+            // A sum reads like an addition: (3 + 4i) * 2 is not 3 + 4i * 2
+            Self::Value(v) if v.is_sum() => 5,
+            Self::Mul(..) | Self::Div { .. } | Self::Rem { .. } => 6,
+            // A fraction reads like a division: (1/2)^2 is not 1/2^2
+            Self::Value(v) if v.is_fraction() => 6,
+            Self::Neg(_) => 7,
+            // A negative number reads like a negation: (-2)^2 is not -2^2
+            Self::Value(v) if v.is_negative() => 7,
+            Self::Pow { .. } => 8,
+            Self::Value(_) | Self::Var(_) | Self::Call { .. } => 9,
+        }
+    }
+}
+
+// Writes `operand`, in parentheses when it binds less tightly than `min`
+fn write_operand<T: Number>(
+    f: &mut fmt::Formatter,
+    operand: &Operation<T>,
+    min: u8,
+) -> fmt::Result {
+    if operand.precedence() < min {
+        write!(f, "({})", operand)
+    } else {
+        write!(f, "{}", operand)
+    }
+}
+
+/// Prints the operation with as few parentheses as it needs to be read back the same.
+///
+/// `+ - * / %` are left associative, so `1 - (2 - 3)` keeps its parentheses
+/// while `(1 - 2) - 3` prints as `1 - 2 - 3`. `^` is right associative and
+/// binds tighter than negation: `-2^2` is `-(2^2)`.
+impl<T: Number> fmt::Display for Operation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (left, symbol, right) = match self {
+            Self::Value(v) => return write!(f, "{}", v),
+            Self::Var(name) => return write!(f, "{}", name),
+            Self::Call { function, args } => {
+                let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                return write!(f, "{}({})", function, args.join(", "));
+            }
+            Self::Neg(a) => {
+                write!(f, "-")?;
+                // "- -5" rather than "--5"
+                match &**a {
+                    Self::Neg(_) => write!(f, " ")?,
+                    Self::Value(v) if v.is_negative() => write!(f, " ")?,
+                    _ => {}
+                }
+                return write_operand(f, a, 7);
+            }
+            Self::Pow { base, exponent } => {
+                write_operand(f, base, 9)?;
+                write!(f, "^")?;
+                return write_operand(f, exponent, 7);
+            }
+            Self::Add(a, b) => (a, "+", b),
+            Self::Mul(a, b) => (a, "*", b),
+            Self::Sub { first, second } => (first, "-", second),
+            Self::Div { divident, divisor } => (divident, "/", divisor),
+            Self::Rem { divident, divisor } => (divident, "%", divisor),
+            Self::BitAnd(a, b) => (a, "&", b),
+            Self::BitOr(a, b) => (a, "|", b),
+            Self::BitXor(a, b) => (a, "xor", b),
+            Self::Shl { value, amount } => (value, "<<", amount),
+            Self::Shr { value, amount } => (value, ">>", amount),
+        };
+        write_operand(f, left, self.precedence())?;
+        write!(f, " {} ", symbol)?;
+        write_operand(f, right, self.precedence() + 1)
+    }
+}
@@ -0,0 +1,483 @@
+//! Turns tokens into an [`Operation`] tree.
+//!
+//! A recursive descent parser, one function per precedence level, from the
+//! loosest to the tightest binding:
+//!
+//! ```text
+//! statement  = ["let"] name "=" expression | expression
+//! expression = xor ("|" xor)*
+//! xor        = and ("xor" and)*
+//! and        = shift ("&" shift)*
+//! shift      = sum (("<<" | ">>") sum)*
+//! sum        = term (("+" | "-") term)*
+//! term       = scaled (("*" | "/" | "%") scaled)*
+//! scaled     = unary [name ["^" unary]]
+//! unary      = ("-" | "+") unary | power
+//! power      = atom ["^" unary]
+//! atom       = digits | matrix | name ["(" [expression ("," expression)*] ")"]
+//!            | "(" expression ")"
+//! matrix     = "[" digits ("," digits)* "]" | "[" matrix ("," matrix)* "]"
+//! ```
+//!
+//! The bitwise operators bind looser than `+` and `-`, in the order of
+//! Rust: `1 << 2 + 1` is `1 << 3`. The loops make every binary operator but
+//! `^` left associative, and `power` calling `unary` for its exponent makes
+//! `^` right associative: `2^3^2` is `2^(3^2)`.
+//!
+//! A number followed by a name is multiplied by it, the way units are
+//! written: `3 km` is `3 * km`. That binds tighter than `*` and `/`, so
+//! `20 m / 4 s` is `(20 * m) / (4 * s)`.
+//!
+//! The parser recurses for every `(`, `-` and `^`, and stops with an error
+//! at [`MAX_DEPTH`] of them, long before `((((...))))` from a file could
+//! overflow the stack. A row of operators is a loop instead, but it still
+//! builds a tree one level taller per operator: `1 + 2 + 3` is
+//! `(1 + 2) + 3`. Computing, printing and simplifying recurse through
+//! those levels, so no tree may grow taller than [`MAX_HEIGHT`].
+
+use super::diagnostic::Span;
+use super::lexer::{self, Token, TokenKind};
+use super::number::Number;
+use super::{Operation, ParseError, ParseErrorKind};
+
+/// How deeply an expression may nest: every group, sign and power counts
+/// as one level.
+pub const MAX_DEPTH: usize = 100;
+
+/// How many levels the tree of an expression may have below its root, so a
+/// sum of at most 501 terms.
+pub const MAX_HEIGHT: usize = 500;
+
+pub(super) type Build<T> = fn(Box<Operation<T>>, Box<Operation<T>>) -> Operation<T>;
+
+/// Parses input like `20 / 0`, `-7 - 2` or `(1 + 2) * 3^2` into `i32` operations.
+pub fn parse(input: &str) -> Result<Operation, ParseError> {
+    parse_as(input)
+}
+
+/// Parses input into operations on `T`, e.g. `parse_as::<f64>("1.5 * 2")`.
+pub fn parse_as<T: Number>(input: &str) -> Result<Operation<T>, ParseError> {
+    let tokens: Vec<Token> = lexer::tokenize(input)?;
+    let mut parser: Parser = Parser {
+        input,
+        tokens: &tokens,
+        position: 0,
+        end: input.len(),
+        spans: Vec::new(),
+        depth: 0,
+        height: 0,
+    };
+    let operation: Operation<T> = parser.expression()?;
+    parser.finish()?;
+    Ok(operation)
+}
+
+/// One line of calculator input: an assignment or an operation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement<T> {
+    /// `x = 20` or `let x = 20`
+    Let {
+        /// The variable that is assigned.
+        name: String,
+        /// The operation computing its value.
+        value: Operation<T>,
+    },
+    /// An operation whose result is shown.
+    Operation(Operation<T>),
+}
+
+/// Parses an assignment like `y = x / 4` or an operation like `y * 3`.
+pub fn parse_statement_as<T: Number>(input: &str) -> Result<Statement<T>, ParseError> {
+    parse_spanned(input).map(|(statement, _)| statement)
+}
+
+/// Parses a statement like [`parse_statement_as`], with the [`Span`] of
+/// every part of its operation.
+///
+/// The spans are in post-order: the operands of a part come before it,
+/// left before right, which is also the order of the instructions
+/// [`vm::compile`](super::vm::compile) writes.
+pub fn parse_spanned<T: Number>(input: &str) -> Result<(Statement<T>, Vec<Span>), ParseError> {
+    let tokens: Vec<Token> = lexer::tokenize(input)?;
+    let mut parser: Parser = Parser {
+        input,
+        tokens: &tokens,
+        position: 0,
+        end: input.len(),
+        spans: Vec::new(),
+        depth: 0,
+        height: 0,
+    };
+
+    let with_let: bool = parser.peek_kind(0) == Some(TokenKind::Ident("let"));
+    if with_let {
+        parser.position += 1;
+    }
+    let statement: Statement<T> = match (parser.peek_kind(0), parser.peek_kind(1)) {
+        (Some(TokenKind::Ident(name)), Some(TokenKind::Equals)) => {
+            parser.position += 2;
+            Statement::Let {
+                name: name.to_string(),
+                value: parser.expression()?,
+            }
+        }
+        _ if with_let => {
+            return Err(ParseError {
+                kind: ParseErrorKind::ExpectedAssignment,
+                offset: parser.peek().map_or(parser.end, |t| t.offset),
+            });
+        }
+        _ => Statement::Operation(parser.expression()?),
+    };
+    parser.finish()?;
+    Ok((statement, parser.spans))
+}
+
+struct Parser<'t, 'a> {
+    input: &'a str,
+    tokens: &'t [Token<'a>],
+    position: usize,
+    // Offset reported for errors at the end of the input
+    end: usize,
+    // Where each operation that was built came from, in the order they were built
+    spans: Vec<Span>,
+    // How many groups, signs and powers enclose the current token
+    depth: usize,
+    // How many levels the operation parsed last has below its root
+    height: usize,
+}
+
+impl<'a> Parser<'_, 'a> {
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn node(&mut self, start: usize, end: usize) {
+        self.spans.push(Span { start, end });
+    }
+
+    // The span of the operation parsed last, which is always the root of
+    // what a parse function returned
+    fn last_span(&self) -> Span {
+        *self
+            .spans
+            .last()
+            .expect("every parsed operation has a span")
+    }
+
+    // Fails when tokens are left after a complete statement
+    fn finish(&self) -> Result<(), ParseError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(token) => Err(ParseError {
+                kind: match token.kind {
+                    TokenKind::RParen => ParseErrorKind::UnmatchedParen,
+                    _ => ParseErrorKind::ExpectedOperator,
+                },
+                offset: token.offset,
+            }),
+        }
+    }
+
+    // The kind of the token `ahead` tokens after the current one
+    fn peek_kind(&self, ahead: usize) -> Option<TokenKind<'a>> {
+        self.tokens.get(self.position + ahead).map(|t| t.kind)
+    }
+
+    // The next token, or a `missing` error at the end of the input
+    fn next(&mut self, missing: ParseErrorKind) -> Result<Token<'a>, ParseError> {
+        let token: Token = self.peek().ok_or(ParseError {
+            kind: missing,
+            offset: self.end,
+        })?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    // One level of left associative operators: `next (operator next)*`, where
+    // `operator` returns how to build the operation of a token of this level
+    fn left_assoc<T: Number>(
+        &mut self,
+        next: fn(&mut Self) -> Result<Operation<T>, ParseError>,
+        operator: fn(TokenKind) -> Option<Build<T>>,
+    ) -> Result<Operation<T>, ParseError> {
+        let mut left: Operation<T> = next(self)?;
+        let start: usize = self.last_span().start;
+        while let Some(build) = self.peek_kind(0).and_then(operator) {
+            let offset: usize = self.tokens[self.position].offset;
+            let left_height: usize = self.height;
+            self.position += 1;
+            let right: Operation<T> = next(self)?;
+            // Each operator puts what came before one level further down
+            self.grow(left_height.max(self.height), offset)?;
+            left = build(Box::new(left), Box::new(right));
+            self.node(start, self.last_span().end);
+        }
+        Ok(left)
+    }
+
+    fn expression<T: Number>(&mut self) -> Result<Operation<T>, ParseError> {
+        self.left_assoc(Self::xor, |kind| match kind {
+            TokenKind::Pipe => Some(Operation::BitOr),
+            _ => None,
+        })
+    }
+
+    // `xor` is a word, since `^` is already the power
+    fn xor<T: Number>(&mut self) -> Result<Operation<T>, ParseError> {
+        self.left_assoc(Self::and, |kind| match kind {
+            TokenKind::Ident("xor") => Some(Operation::BitXor),
+            _ => None,
+        })
+    }
+
+    fn and<T: Number>(&mut self) -> Result<Operation<T>, ParseError> {
+        self.left_assoc(Self::shift, |kind| match kind {
+            TokenKind::Amp => Some(Operation::BitAnd),
+            _ => None,
+        })
+    }
+
+    fn shift<T: Number>(&mut self) -> Result<Operation<T>, ParseError> {
+        self.left_assoc(Self::sum, |kind| match kind {
+            TokenKind::Shl => Some(|value, amount| Operation::Shl { value, amount }),
+            TokenKind::Shr => Some(|value, amount| Operation::Shr { value, amount }),
+            _ => None,
+        })
+    }
+
+    fn sum<T: Number>(&mut self) -> Result<Operation<T>, ParseError> {
+        self.left_assoc(Self::term, |kind| match kind {
+            TokenKind::Plus => Some(Operation::Add),
+            TokenKind::Minus => Some(|first, second| Operation::Sub { first, second }),
+            _ => None,
+        })
+    }
+
+    fn term<T: Number>(&mut self) -> Result<Operation<T>, ParseError> {
+        self.left_assoc(Self::scaled, |kind| match kind {
+            TokenKind::Star => Some(Operation::Mul),
+            TokenKind::Slash => Some(|divident, divisor| Operation::Div { divident, divisor }),
+            TokenKind::Percent => Some(|divident, divisor| Operation::Rem { divident, divisor }),
+            _ => None,
+        })
+    }
+
+    // `3 km`: only a number right before the name scales it, `x y` stays an
+    // error, and so does `3 xor` since `xor` is an operator
+    fn scaled<T: Number>(&mut self) -> Result<Operation<T>, ParseError> {
+        let value: Operation<T> = self.unary()?;
+        let start: usize = self.last_span().start;
+        let after_number: bool = self.position > 0
+            && matches!(self.tokens[self.position - 1].kind, TokenKind::Number(_));
+        match self.peek_kind(0) {
+            Some(TokenKind::Ident(name)) if after_number && name != "xor" => {
+                let value_height: usize = self.height;
+                let unit: Operation<T> = self.power()?;
+                self.grow(value_height.max(self.height), start)?;
+                self.node(start, self.last_span().end);
+                Ok(Operation::Mul(Box::new(value), Box::new(unit)))
+            }
+            _ => Ok(value),
+        }
+    }
+
+    // Records the height of an operation whose tallest operand has
+    // `operands` levels, or fails at `offset` if the tree grows too tall
+    fn grow(&mut self, operands: usize, offset: usize) -> Result<(), ParseError> {
+        if operands == MAX_HEIGHT {
+            return Err(ParseError {
+                kind: ParseErrorKind::TooDeep(MAX_HEIGHT),
+                offset,
+            });
+        }
+        self.height = operands + 1;
+        Ok(())
+    }
+
+    // Every nested group, sign and exponent passes through here, one level
+    // deeper, or an error at the current token if that is too deep
+    fn unary<T: Number>(&mut self) -> Result<Operation<T>, ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(ParseError {
+                kind: ParseErrorKind::TooDeep(MAX_DEPTH),
+                offset: self.peek().map_or(self.end, |t| t.offset),
+            });
+        }
+        self.depth += 1;
+        let operation: Result<Operation<T>, ParseError> = self.signed();
+        self.depth -= 1;
+        operation
+    }
+
+    fn signed<T: Number>(&mut self) -> Result<Operation<T>, ParseError> {
+        match self.peek_kind(0) {
+            // `-7` is the number -7 rather than the negation of 7, so that
+            // `-2147483648` fits. `-2^2` stays a negation: -(2^2)
+            Some(TokenKind::Minus)
+                if matches!(self.peek_kind(1), Some(TokenKind::Number(_)))
+                    && self.peek_kind(2) != Some(TokenKind::Caret) =>
+            {
+                let minus: Token = self.next(ParseErrorKind::ExpectedNumber)?;
+                let number: Token = self.next(ParseErrorKind::ExpectedNumber)?;
+                self.number(number, true, minus.offset)
+            }
+            Some(TokenKind::Minus) => {
+                let minus: Token = self.next(ParseErrorKind::ExpectedNumber)?;
+                let operand: Operation<T> = self.unary()?;
+                self.grow(self.height, minus.offset)?;
+                self.node(minus.offset, self.last_span().end);
+                Ok(Operation::Neg(Box::new(operand)))
+            }
+            Some(TokenKind::Plus) => {
+                self.position += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power<T: Number>(&mut self) -> Result<Operation<T>, ParseError> {
+        let base: Operation<T> = self.atom()?;
+        if self.peek_kind(0) != Some(TokenKind::Caret) {
+            return Ok(base);
+        }
+        let start: usize = self.last_span().start;
+        let base_height: usize = self.height;
+        self.position += 1;
+        let exponent: Operation<T> = self.unary()?;
+        self.grow(base_height.max(self.height), start)?;
+        self.node(start, self.last_span().end);
+        Ok(Operation::Pow {
+            base: Box::new(base),
+            exponent: Box::new(exponent),
+        })
+    }
+
+    fn atom<T: Number>(&mut self) -> Result<Operation<T>, ParseError> {
+        let token: Token = self.next(ParseErrorKind::ExpectedNumber)?;
+        match token.kind {
+            TokenKind::Number(_) => self.number(token, false, token.offset),
+            TokenKind::Ident(function) if self.peek_kind(0) == Some(TokenKind::LParen) => {
+                let open: Token = self.next(ParseErrorKind::ExpectedOperator)?;
+                let mut args: Vec<Operation<T>> = Vec::new();
+                let mut tallest: usize = 0;
+                if self.peek_kind(0) != Some(TokenKind::RParen) {
+                    args.push(self.expression()?);
+                    tallest = self.height;
+                    while self.peek_kind(0) == Some(TokenKind::Comma) {
+                        self.position += 1;
+                        args.push(self.expression()?);
+                        tallest = tallest.max(self.height);
+                    }
+                }
+                let close: Token = self.close_paren(open)?;
+                self.grow(tallest, token.offset)?;
+                self.node(token.offset, close.end());
+                Ok(Operation::Call {
+                    function: function.to_string(),
+                    args,
+                })
+            }
+            TokenKind::Ident(name) | TokenKind::History(name) => {
+                self.height = 0;
+                self.node(token.offset, token.end());
+                Ok(Operation::Var(name.to_string()))
+            }
+            TokenKind::LParen => {
+                let inner: Operation<T> = self.expression()?;
+                let close: Token = self.close_paren(token)?;
+                // The parentheses belong to what they enclose
+                self.spans.pop();
+                self.node(token.offset, close.end());
+                Ok(inner)
+            }
+            TokenKind::LBracket => self.matrix(token),
+            _ => Err(ParseError {
+                kind: ParseErrorKind::ExpectedNumber,
+                offset: token.offset,
+            }),
+        }
+    }
+
+    // The `)` that closes `open`
+    fn close_paren(&mut self, open: Token) -> Result<Token<'a>, ParseError> {
+        match self.peek() {
+            Some(
+                close @ Token {
+                    kind: TokenKind::RParen,
+                    ..
+                },
+            ) => {
+                self.position += 1;
+                Ok(close)
+            }
+            // The error points at the parenthesis that is never closed
+            None => Err(ParseError {
+                kind: ParseErrorKind::UnclosedParen,
+                offset: open.offset,
+            }),
+            Some(other) => Err(ParseError {
+                kind: ParseErrorKind::ExpectedOperator,
+                offset: other.offset,
+            }),
+        }
+    }
+
+    // A literal from `open` to its `]`. The number type reads the text
+    // itself, since its entries are numbers rather than operations
+    fn matrix<T: Number>(&mut self, open: Token) -> Result<Operation<T>, ParseError> {
+        let mut depth: usize = 1;
+        let close: Token = loop {
+            let token: Token =
+                self.next(ParseErrorKind::UnclosedBracket)
+                    .map_err(|e| ParseError {
+                        offset: open.offset,
+                        ..e
+                    })?;
+            match token.kind {
+                TokenKind::LBracket => depth += 1,
+                TokenKind::RBracket if depth == 1 => break token,
+                TokenKind::RBracket => depth -= 1,
+                _ => {}
+            }
+        };
+        let text: String = self.input[open.offset..close.end()].replace('_', "");
+        let value: T = T::parse_matrix(&text).map_err(|kind| ParseError {
+            kind,
+            offset: open.offset,
+        })?;
+        self.height = 0;
+        self.node(open.offset, close.end());
+        Ok(Operation::Value(value))
+    }
+
+    // The value of a number token, `start` is where its sign begins
+    fn number<T: Number>(
+        &mut self,
+        token: Token,
+        negative: bool,
+        start: usize,
+    ) -> Result<Operation<T>, ParseError> {
+        let TokenKind::Number(digits) = token.kind else {
+            return Err(ParseError {
+                kind: ParseErrorKind::ExpectedNumber,
+                offset: token.offset,
+            });
+        };
+        // The sign is parsed with the digits so that -2147483648 fits an i32
+        let digits: String = digits.replace('_', "");
+        let text: String = if negative {
+            format!("-{}", digits)
+        } else {
+            digits
+        };
+        let value: T = T::parse_literal(&text).map_err(|kind| ParseError {
+            kind,
+            offset: start,
+        })?;
+        self.height = 0;
+        self.node(start, token.end());
+        Ok(Operation::Value(value))
+    }
+}
@@ -0,0 +1,279 @@
+//! Exact fractions of two `i64`s.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+use super::number::{self, Number};
+use super::{MathError, Mode, ParseErrorKind};
+
+/// A fraction `numer / denom` kept in lowest terms, with a positive denominator.
+///
+/// Arithmetic is exact, so `20 / 3` stays `20/3`. A numerator or denominator
+/// that does not fit in an `i64` is an error whatever the [`Mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i64,
+    denom: i64,
+}
+
+// How many fraction digits `to_repeating` writes while looking for the
+// repeating part, which can be almost as long as the denominator
+const MAX_REPEATING_DIGITS: usize = 1000;
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs()
+}
+
+impl Rational {
+    /// `numer / denom` in lowest terms.
+    pub fn new(numer: i64, denom: i64) -> Result<Rational, MathError> {
+        Rational::reduce(numer as i128, denom as i128)
+    }
+
+    /// The whole number `n`.
+    pub fn from_integer(n: i64) -> Rational {
+        Rational { numer: n, denom: 1 }
+    }
+
+    /// The numerator, negative for negative fractions.
+    pub fn numer(&self) -> i64 {
+        self.numer
+    }
+
+    /// The denominator, always positive.
+    pub fn denom(&self) -> i64 {
+        self.denom
+    }
+
+    /// Whether the denominator is 1.
+    pub fn is_integer(&self) -> bool {
+        self.denom == 1
+    }
+
+    /// The fraction as a decimal rounded to `precision` digits after the
+    /// point, halves away from zero: 2/3 is `0.667` with a precision of 3.
+    pub fn to_decimal(&self, precision: usize) -> String {
+        let denom: i128 = self.denom as i128;
+        let mut remainder: i128 = (self.numer as i128).abs() % denom;
+        let mut digits: Vec<u8> = ((self.numer as i128).abs() / denom)
+            .to_string()
+            .into_bytes();
+        for _ in 0..precision {
+            remainder *= 10;
+            digits.push(b'0' + (remainder / denom) as u8);
+            remainder %= denom;
+        }
+        // Rounds up when the next digit is 5 or more, carrying past the nines
+        if remainder * 10 / denom >= 5 {
+            let mut i: usize = digits.len();
+            loop {
+                if i == 0 {
+                    digits.insert(0, b'1');
+                    break;
+                }
+                i -= 1;
+                if digits[i] == b'9' {
+                    digits[i] = b'0';
+                } else {
+                    digits[i] += 1;
+                    break;
+                }
+            }
+        }
+        let (whole, fraction) = digits.split_at(digits.len() - precision);
+        let sign: &str = if self.numer < 0 && digits.iter().any(|&d| d != b'0') {
+            "-"
+        } else {
+            ""
+        };
+        let whole: &str = std::str::from_utf8(whole).expect("digits are ASCII");
+        let fraction: &str = std::str::from_utf8(fraction).expect("digits are ASCII");
+        if precision == 0 {
+            format!("{}{}", sign, whole)
+        } else {
+            format!("{}{}.{}", sign, whole, fraction)
+        }
+    }
+
+    /// The exact decimal, with the digits that repeat forever in parentheses:
+    /// 1/6 is `0.1(6)` and 22/7 is `3.(142857)`.
+    ///
+    /// A repeating part can be almost as long as the denominator, so after
+    /// 1000 digits without finding it the decimal ends with `...`.
+    pub fn to_repeating(&self) -> String {
+        let denom: i128 = self.denom as i128;
+        let mut remainder: i128 = (self.numer as i128).abs() % denom;
+        let sign: &str = if self.numer < 0 { "-" } else { "" };
+        let whole: i128 = (self.numer as i128).abs() / denom;
+        if remainder == 0 {
+            return format!("{}{}", sign, whole);
+        }
+        // Long division repeats from the first remainder seen twice
+        let mut seen: HashMap<i128, usize> = HashMap::new();
+        let mut fraction: String = String::new();
+        while remainder != 0 {
+            if let Some(&start) = seen.get(&remainder) {
+                fraction.insert(start, '(');
+                fraction.push(')');
+                break;
+            }
+            if fraction.len() == MAX_REPEATING_DIGITS {
+                fraction.push_str("...");
+                break;
+            }
+            seen.insert(remainder, fraction.len());
+            remainder *= 10;
+            fraction.push(char::from(b'0' + (remainder / denom) as u8));
+            remainder %= denom;
+        }
+        format!("{}{}.{}", sign, whole, fraction)
+    }
+
+    // Computes in i128 so that no intermediate product overflows, then
+    // reduces and checks that the result fits again
+    fn reduce(numer: i128, denom: i128) -> Result<Rational, MathError> {
+        if denom == 0 {
+            return Err(MathError::DivideByZero);
+        }
+        let sign: i128 = if denom < 0 { -1 } else { 1 };
+        let divisor: i128 = gcd(numer, denom).max(1);
+        let (numer, denom) = (sign * numer / divisor, sign * denom / divisor);
+        let out_of_range: MathError = if numer < 0 {
+            MathError::Underflow
+        } else {
+            MathError::Overflow
+        };
+        Ok(Rational {
+            numer: i64::try_from(numer).map_err(|_| out_of_range.clone())?,
+            denom: i64::try_from(denom).map_err(|_| out_of_range)?,
+        })
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.denom == 1 {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+// a/b < c/d exactly when a*d < c*b, since both denominators are positive
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        (self.numer as i128 * other.denom as i128).cmp(&(other.numer as i128 * self.denom as i128))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Number for Rational {
+    const NAME: &'static str = "rational";
+
+    // Decimal literals are exact: `1.25` is 5/4
+    fn parse_literal(text: &str) -> Result<Self, ParseErrorKind> {
+        number::decimal_only(text, Self::NAME)?;
+        let too_large: ParseErrorKind = ParseErrorKind::NumberTooLarge(Self::NAME);
+        // `1.5e-3` is 1.5 times 10^-3, exactly
+        if let Some((mantissa, exponent)) = text.split_once(['e', 'E']) {
+            let exponent: i64 = exponent.parse().map_err(|_| too_large)?;
+            let scale: Rational = Rational::from_integer(10)
+                .pow(&Rational::from_integer(exponent), Mode::Checked)
+                .map_err(|_| too_large)?;
+            return Rational::parse_literal(mantissa)?
+                .mul(&scale, Mode::Checked)
+                .map_err(|_| too_large);
+        }
+        let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let denom: i64 = u32::try_from(fraction.len())
+            .ok()
+            .and_then(|digits| 10i64.checked_pow(digits))
+            .ok_or(too_large)?;
+        let numer: i64 = format!("{}{}", whole, fraction)
+            .parse()
+            .map_err(|_| too_large)?;
+        Rational::new(numer, denom).map_err(|_| too_large)
+    }
+
+    fn is_negative(&self) -> bool {
+        self.numer < 0
+    }
+
+    fn is_fraction(&self) -> bool {
+        self.denom != 1
+    }
+
+    fn neg(&self, _mode: Mode) -> Result<Self, MathError> {
+        Rational::reduce(-(self.numer as i128), self.denom as i128)
+    }
+
+    fn add(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Rational::reduce(
+            self.numer as i128 * other.denom as i128 + other.numer as i128 * self.denom as i128,
+            self.denom as i128 * other.denom as i128,
+        )
+    }
+
+    fn sub(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Rational::reduce(
+            self.numer as i128 * other.denom as i128 - other.numer as i128 * self.denom as i128,
+            self.denom as i128 * other.denom as i128,
+        )
+    }
+
+    fn mul(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Rational::reduce(
+            self.numer as i128 * other.numer as i128,
+            self.denom as i128 * other.denom as i128,
+        )
+    }
+
+    fn div(&self, other: &Self, _mode: Mode) -> Result<Self, MathError> {
+        Rational::reduce(
+            self.numer as i128 * other.denom as i128,
+            self.denom as i128 * other.numer as i128,
+        )
+    }
+
+    // self - other * trunc(self / other)
+    fn rem(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        let quotient: Rational = self.div(other, mode)?;
+        let truncated: Rational = Rational::from_integer(quotient.numer / quotient.denom);
+        self.sub(&truncated.mul(other, mode)?, mode)
+    }
+
+    // Whole exponents only, a negative one inverts: (2/3)^-2 is 9/4
+    fn pow(&self, exponent: &Self, mode: Mode) -> Result<Self, MathError> {
+        if !exponent.is_integer() {
+            return Err(MathError::FractionalExponent);
+        }
+        let mut result: Rational = Rational::from_integer(1);
+        let mut base: Rational = if exponent.numer < 0 {
+            Rational::from_integer(1).div(self, mode)?
+        } else {
+            *self
+        };
+        // Squaring keeps the number of multiplications logarithmic
+        let mut remaining: u64 = exponent.numer.unsigned_abs();
+        while remaining > 0 {
+            if remaining % 2 == 1 {
+                result = result.mul(&base, mode)?;
+            }
+            remaining /= 2;
+            if remaining > 0 {
+                base = base.mul(&base, mode)?;
+            }
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,214 @@
+//! Reverse Polish notation: the operands come first, then the operator.
+//!
+//! `3 4 + 2 *` pushes 3 and 4 on a stack, `+` replaces them with 7, 2 is
+//! pushed and `*` leaves 14. No parentheses are needed, since the order of
+//! the words is the order of the operations. The stack stays between lines,
+//! and words like `dup` and `swap` rearrange it.
+//!
+//! A word that starts with a digit, a `-` and a digit, or a `[` is read like
+//! an infix literal: `-7`, `0x1f` and `1.5`, a number right before a unit
+//! like `3km` or `4i`, or a matrix without spaces like `[[1,2],[3,4]]`.
+//! Anything else, like `3km^2`, is written as words: `3 km 2 ^ *`.
+
+use std::error::Error;
+use std::fmt;
+
+use super::diagnostic::Span;
+use super::env::Env;
+use super::number::Number;
+use super::{MathError, Mode, Operation, ParseErrorKind, parse_as};
+
+/// Why a line of RPN could not be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RpnError {
+    /// A word needs more values than the stack has.
+    StackUnderflow {
+        /// The word.
+        word: String,
+        /// How many values it takes from the stack.
+        needed: usize,
+    },
+    /// A word that is no number, name, operator or stack command.
+    UnknownWord(String),
+    /// A number that does not fit the number type.
+    Number(ParseErrorKind),
+    /// An operation failed.
+    Math(MathError),
+}
+
+impl fmt::Display for RpnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RpnError::StackUnderflow { word, needed: 1 } => {
+                write!(f, "`{}` needs a value on the stack", word)
+            }
+            RpnError::StackUnderflow { word, needed } => {
+                write!(f, "`{}` needs {} values on the stack", word, needed)
+            }
+            RpnError::UnknownWord(word) => write!(f, "unknown word `{}`", word),
+            RpnError::Number(kind) => write!(f, "{}", kind),
+            RpnError::Math(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for RpnError {}
+
+impl From<MathError> for RpnError {
+    fn from(e: MathError) -> RpnError {
+        RpnError::Math(e)
+    }
+}
+
+type Binary<T> = fn(&T, &T, Mode) -> Result<T, MathError>;
+
+// The operator a word stands for. The bitwise ones also have names, since
+// `and` reads better than `&` between numbers
+fn binary<T: Number>(word: &str) -> Option<Binary<T>> {
+    Some(match word {
+        "+" => T::add,
+        "-" => T::sub,
+        "*" => T::mul,
+        "/" => T::div,
+        "%" => T::rem,
+        "^" => T::pow,
+        "&" | "and" => T::bit_and,
+        "|" | "or" => T::bit_or,
+        "xor" => T::bit_xor,
+        "<<" | "shl" => T::shl,
+        ">>" | "shr" => T::shr,
+        _ => return None,
+    })
+}
+
+// Takes `n` values off the stack, the deepest first
+fn pop<T>(stack: &mut Vec<T>, word: &str, n: usize) -> Result<Vec<T>, RpnError> {
+    if stack.len() < n {
+        return Err(RpnError::StackUnderflow {
+            word: word.to_string(),
+            needed: n,
+        });
+    }
+    Ok(stack.split_off(stack.len() - n))
+}
+
+// The value of a word that starts like a number. The infix parser reads
+// it, and only a number, a matrix or a number times a unit is a literal
+fn literal<T: Number>(word: &str, mode: Mode) -> Result<T, RpnError> {
+    let unknown = || RpnError::UnknownWord(word.to_string());
+    let operation: Operation<T> = parse_as(word).map_err(|e| match e.kind {
+        // The word is a number that does not fit the type
+        ParseErrorKind::NumberTooLarge(_)
+        | ParseErrorKind::NotAWholeNumber(_)
+        | ParseErrorKind::NotDecimal(_)
+        | ParseErrorKind::InvalidDigit(_)
+        | ParseErrorKind::NoMatrices(_)
+        | ParseErrorKind::UnclosedBracket
+        | ParseErrorKind::InvalidMatrix(_)
+        | ParseErrorKind::Exponent(_) => RpnError::Number(e.kind),
+        _ => unknown(),
+    })?;
+    match operation {
+        Operation::Value(v) => Ok(v),
+        Operation::Mul(number, unit) => match (*number, *unit) {
+            (Operation::Value(v), Operation::Var(name)) => {
+                let unit: T = T::unit(&name).ok_or_else(unknown)?;
+                Ok(v.mul(&unit, mode)?)
+            }
+            _ => Err(unknown()),
+        },
+        _ => Err(unknown()),
+    }
+}
+
+// The words of `line` with where they are in it
+fn words(line: &str) -> impl Iterator<Item = (Span, &str)> {
+    line.split_whitespace().map(move |word| {
+        // `word` is a slice of `line`, so their pointers tell the offset
+        let start: usize = word.as_ptr() as usize - line.as_ptr() as usize;
+        let span: Span = Span {
+            start,
+            end: start + word.len(),
+        };
+        (span, word)
+    })
+}
+
+/// Evaluates the words of `line` on `stack`, taking variables from `env`.
+///
+/// Besides numbers, names and the operators of the infix calculator, the
+/// words are `neg`, `dup` (copy the top value), `swap` (exchange the top two
+/// values), `drop` (remove the top value) and `clear`. When a word fails the
+/// stack is left as it was before the line.
+pub fn eval<T: Number>(
+    line: &str,
+    stack: &mut Vec<T>,
+    env: &Env<T>,
+    mode: Mode,
+) -> Result<(), RpnError> {
+    eval_located(line, stack, env, mode).map_err(|(_, e)| e)
+}
+
+/// Evaluates `line` like [`eval`], an error comes with the span of the word
+/// that failed.
+pub fn eval_located<T: Number>(
+    line: &str,
+    stack: &mut Vec<T>,
+    env: &Env<T>,
+    mode: Mode,
+) -> Result<(), (Span, RpnError)> {
+    let mut work: Vec<T> = stack.clone();
+    for (span, word) in words(line) {
+        run_word(word, &mut work, env, mode).map_err(|e| (span, e))?;
+    }
+    *stack = work;
+    Ok(())
+}
+
+// Runs one word on `stack`
+fn run_word<T: Number>(
+    word: &str,
+    stack: &mut Vec<T>,
+    env: &Env<T>,
+    mode: Mode,
+) -> Result<(), RpnError> {
+    let is_literal: bool = word
+        .strip_prefix('-')
+        .unwrap_or(word)
+        .starts_with(|c: char| c.is_ascii_digit())
+        || word.starts_with('[');
+    if is_literal {
+        stack.push(literal(word, mode)?);
+    } else if let Some(operation) = binary::<T>(word) {
+        let operands: Vec<T> = pop(stack, word, 2)?;
+        stack.push(operation(&operands[0], &operands[1], mode)?);
+    } else {
+        match word {
+            "neg" => {
+                let a: Vec<T> = pop(stack, word, 1)?;
+                stack.push(a[0].neg(mode)?);
+            }
+            "dup" => {
+                let a: Vec<T> = pop(stack, word, 1)?;
+                stack.extend([a[0].clone(), a[0].clone()]);
+            }
+            "swap" => {
+                let mut ab: Vec<T> = pop(stack, word, 2)?;
+                ab.reverse();
+                stack.extend(ab);
+            }
+            "drop" => {
+                pop(stack, word, 1)?;
+            }
+            "clear" => stack.clear(),
+            name if name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$') => {
+                let value: T = env
+                    .value(name)
+                    .ok_or_else(|| MathError::UndefinedVariable(name.to_string()))?;
+                stack.push(value);
+            }
+            _ => return Err(RpnError::UnknownWord(word.to_string())),
+        }
+    }
+    Ok(())
+}
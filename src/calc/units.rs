@@ -0,0 +1,298 @@
+//! Physical quantities: numbers with units like `3 km` or `20 m/s`.
+//!
+//! A [`Quantity`] keeps its value in the base units kilogram, metre and
+//! second, and the powers of those in a [`Dimension`]. `3 km` is stored as
+//! 3000 metres, so `3 km + 200 m` only adds the values, while `1 m + 1 s` is
+//! a [`MathError::DimensionMismatch`]. Multiplying and dividing combine the
+//! dimensions, which is where derived units like `m/s` come from.
+//!
+//! In the calculator a number followed by a unit name is multiplied by the
+//! unit, and `3 km in m` converts to another unit of the same dimension.
+
+use std::fmt;
+
+use super::number::{self, Number};
+use super::{MathError, Mode, ParseErrorKind};
+
+/// The powers of the base units in a quantity: `m/s^2` is
+/// `Dimension { mass: 0, length: 1, time: -2 }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Dimension {
+    /// Power of kilograms.
+    pub mass: i32,
+    /// Power of metres.
+    pub length: i32,
+    /// Power of seconds.
+    pub time: i32,
+}
+
+impl Dimension {
+    /// A plain number without units.
+    pub const NONE: Dimension = Dimension {
+        mass: 0,
+        length: 0,
+        time: 0,
+    };
+    /// Kilograms.
+    pub const MASS: Dimension = Dimension {
+        mass: 1,
+        ..Dimension::NONE
+    };
+    /// Metres.
+    pub const LENGTH: Dimension = Dimension {
+        length: 1,
+        ..Dimension::NONE
+    };
+    /// Seconds.
+    pub const TIME: Dimension = Dimension {
+        time: 1,
+        ..Dimension::NONE
+    };
+
+    /// The dimension of a product, the powers are added. `None` when a
+    /// power does not fit an `i32`.
+    pub fn times(self, other: Dimension) -> Option<Dimension> {
+        Some(Dimension {
+            mass: self.mass.checked_add(other.mass)?,
+            length: self.length.checked_add(other.length)?,
+            time: self.time.checked_add(other.time)?,
+        })
+    }
+
+    /// The dimension of a quotient, the powers are subtracted.
+    pub fn per(self, other: Dimension) -> Option<Dimension> {
+        self.times(other.pow(-1)?)
+    }
+
+    /// The dimension of a power, the powers are multiplied.
+    pub fn pow(self, exponent: i32) -> Option<Dimension> {
+        Some(Dimension {
+            mass: self.mass.checked_mul(exponent)?,
+            length: self.length.checked_mul(exponent)?,
+            time: self.time.checked_mul(exponent)?,
+        })
+    }
+
+    fn powers(self) -> [(&'static str, i32); 3] {
+        [("kg", self.mass), ("m", self.length), ("s", self.time)]
+    }
+}
+
+/// Writes the dimension as units that read back as calculator input, like
+/// `kg*m/s^2`, `s^-1` or `1` for no units.
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let unit = |(name, power): (&str, i32)| match power {
+            1 => name.to_string(),
+            _ => format!("{}^{}", name, power),
+        };
+        let above: Vec<String> = self
+            .powers()
+            .into_iter()
+            .filter(|&(_, power)| power > 0)
+            .map(unit)
+            .collect();
+        let below: Vec<String> = self
+            .powers()
+            .into_iter()
+            .filter(|&(_, power)| power < 0)
+            .map(|(name, power)| unit((name, -power)))
+            .collect();
+        match (above.is_empty(), below.is_empty()) {
+            (true, true) => write!(f, "1"),
+            // Nothing to divide: s^-1 instead of 1/s, which would be a number
+            (true, false) => {
+                let units: Vec<String> = self
+                    .powers()
+                    .into_iter()
+                    .filter(|&(_, power)| power < 0)
+                    .map(unit)
+                    .collect();
+                write!(f, "{}", units.join("*"))
+            }
+            // One `/` before every unit below, since m/s*kg would be (m/s)*kg
+            _ => {
+                write!(f, "{}", above.join("*"))?;
+                for unit in below {
+                    write!(f, "/{}", unit)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// The units the calculator knows: name, size in base units and dimension
+const UNITS: [(&str, f64, Dimension); 10] = [
+    ("m", 1.0, Dimension::LENGTH),
+    ("km", 1000.0, Dimension::LENGTH),
+    ("cm", 0.01, Dimension::LENGTH),
+    ("mm", 0.001, Dimension::LENGTH),
+    ("s", 1.0, Dimension::TIME),
+    ("ms", 0.001, Dimension::TIME),
+    ("min", 60.0, Dimension::TIME),
+    ("h", 3600.0, Dimension::TIME),
+    ("kg", 1.0, Dimension::MASS),
+    ("g", 0.001, Dimension::MASS),
+];
+
+/// A number of base units, like 3000 metres.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    value: f64,
+    dimension: Dimension,
+}
+
+impl Quantity {
+    /// `value` kilograms, metres and seconds to the powers in `dimension`.
+    pub fn new(value: f64, dimension: Dimension) -> Quantity {
+        Quantity { value, dimension }
+    }
+
+    /// One of the unit named `name`, like 1000 metres for `km`.
+    pub fn unit(name: &str) -> Option<Quantity> {
+        UNITS
+            .iter()
+            .find(|(unit, _, _)| *unit == name)
+            .map(|&(_, size, dimension)| Quantity::new(size, dimension))
+    }
+
+    /// The value in base units.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// The powers of the base units.
+    pub fn dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    /// How many of `unit` the quantity is: `3 km` is 3000 of `1 m`.
+    ///
+    /// Both must have the same dimension.
+    pub fn in_unit(&self, unit: &Quantity) -> Result<f64, MathError> {
+        self.same_dimension(unit)?;
+        if unit.value == 0.0 {
+            return Err(MathError::DivideByZero);
+        }
+        Ok(tidy(self.value / unit.value))
+    }
+
+    fn same_dimension(&self, other: &Quantity) -> Result<(), MathError> {
+        if self.dimension == other.dimension {
+            Ok(())
+        } else {
+            Err(MathError::DimensionMismatch(
+                self.dimension.to_string(),
+                other.dimension.to_string(),
+            ))
+        }
+    }
+}
+
+// Rounds to 15 significant digits. Units like 0.01 have no exact f64, so
+// 3 cm in cm would otherwise be 2.9999999999999996
+fn tidy(value: f64) -> f64 {
+    format!("{:.14e}", value).parse().unwrap_or(value)
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", tidy(self.value))?;
+        if self.dimension != Dimension::NONE {
+            write!(f, " {}", self.dimension)?;
+        }
+        Ok(())
+    }
+}
+
+// The values are computed like f64, the dimensions are checked and combined
+impl Number for Quantity {
+    const NAME: &'static str = "quantity";
+
+    // A literal is a plain number, its unit is a separate name
+    fn parse_literal(text: &str) -> Result<Self, ParseErrorKind> {
+        let value: f64 = number::parse_f64(text, Self::NAME)?;
+        Ok(Quantity::new(value, Dimension::NONE))
+    }
+
+    fn is_negative(&self) -> bool {
+        Number::is_negative(&self.value)
+    }
+
+    // `3 m` reads like the product it came from: (3 m)^2 is not 3 m^2
+    fn is_fraction(&self) -> bool {
+        self.dimension != Dimension::NONE
+    }
+
+    const SHAPED: bool = true;
+
+    fn unit(name: &str) -> Option<Self> {
+        Quantity::unit(name)
+    }
+
+    fn neg(&self, mode: Mode) -> Result<Self, MathError> {
+        Ok(Quantity::new(self.value.neg(mode)?, self.dimension))
+    }
+
+    fn add(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        self.same_dimension(other)?;
+        Ok(Quantity::new(
+            self.value.add(&other.value, mode)?,
+            self.dimension,
+        ))
+    }
+
+    fn sub(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        self.same_dimension(other)?;
+        Ok(Quantity::new(
+            self.value.sub(&other.value, mode)?,
+            self.dimension,
+        ))
+    }
+
+    fn mul(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        Ok(Quantity::new(
+            self.value.mul(&other.value, mode)?,
+            self.dimension
+                .times(other.dimension)
+                .ok_or(MathError::Overflow)?,
+        ))
+    }
+
+    fn div(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        Ok(Quantity::new(
+            self.value.div(&other.value, mode)?,
+            self.dimension
+                .per(other.dimension)
+                .ok_or(MathError::Overflow)?,
+        ))
+    }
+
+    fn rem(&self, other: &Self, mode: Mode) -> Result<Self, MathError> {
+        self.same_dimension(other)?;
+        Ok(Quantity::new(
+            self.value.rem(&other.value, mode)?,
+            self.dimension,
+        ))
+    }
+
+    // The exponent is a plain number, and a whole one when the base has
+    // units: m^2 is an area, but m^0.5 has no unit
+    fn pow(&self, exponent: &Self, mode: Mode) -> Result<Self, MathError> {
+        exponent.same_dimension(&Quantity::new(1.0, Dimension::NONE))?;
+        let value: f64 = self.value.pow(&exponent.value, mode)?;
+        if self.dimension == Dimension::NONE {
+            return Ok(Quantity::new(value, Dimension::NONE));
+        }
+        let whole: f64 = exponent.value.trunc();
+        if whole != exponent.value || whole.abs() > i32::MAX as f64 {
+            return Err(MathError::FractionalUnit(self.dimension.to_string()));
+        }
+        let dimension: Dimension = self
+            .dimension
+            .pow(whole as i32)
+            .ok_or(MathError::Overflow)?;
+        Ok(Quantity::new(value, dimension))
+    }
+}
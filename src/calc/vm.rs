@@ -0,0 +1,334 @@
+//! Compiles an [`Operation`] to bytecode for a small stack machine.
+//!
+//! Walking the tree of an operation follows a `Box` for every operand.
+//! [`compile`] does that walk once and writes the operation down in postfix
+//! order, so `(1 + x) * 3` becomes `push 1, load x, add, push 3, mul`.
+//! A program then only loops over a flat list of instructions.
+//!
+//! To evaluate the same formula many times, [`Program::bind`] looks up its
+//! variables once and [`Program::run_with`] reuses one stack. In a release
+//! build `cargo run --release --example vm_benchmark` measures about half
+//! the time of walking the tree for formulas with variables, where the tree
+//! looks up every name on every run, and about the same for `1 + 2`.
+//! Without optimizations the tree is faster on small formulas.
+//!
+//! The instructions call the same [`Number`] methods as
+//! [`Operation::execute_in`], in the same order, so a program fails with
+//! the same [`MathError`] as the operation it was compiled from.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use super::env::Env;
+use super::number::Number;
+use super::{MathError, Mode, Operation};
+
+/// One instruction of the stack machine.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Instr<T> {
+    /// Pushes a number.
+    Push(T),
+    /// Pushes the value of the variable with this index in [`Program::names`].
+    Load(usize),
+    /// Replaces the top value `a` with `-a`.
+    Neg,
+    /// Pops `b` and `a`, pushes `a + b`.
+    Add,
+    /// Pops `b` and `a`, pushes `a - b`.
+    Sub,
+    /// Pops `b` and `a`, pushes `a * b`.
+    Mul,
+    /// Pops `b` and `a`, pushes `a / b`.
+    Div,
+    /// Pops `b` and `a`, pushes `a % b`.
+    Rem,
+    /// Pops `b` and `a`, pushes `a ^ b`.
+    Pow,
+    /// Pops `b` and `a`, pushes `a & b`.
+    BitAnd,
+    /// Pops `b` and `a`, pushes `a | b`.
+    BitOr,
+    /// Pops `b` and `a`, pushes `a xor b`.
+    BitXor,
+    /// Pops `b` and `a`, pushes `a << b`.
+    Shl,
+    /// Pops `b` and `a`, pushes `a >> b`.
+    Shr,
+    /// Pops `args` values, pushes the function of them.
+    Call {
+        /// The name of the function.
+        function: String,
+        /// How many operands it takes from the stack.
+        args: usize,
+    },
+}
+
+/// A compiled operation. The fields are private so that every program comes
+/// from [`compile`], which `run` relies on: a `load` never reads past
+/// `names`, and every instruction finds its operands on the stack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program<T> {
+    // The instructions, in execution order
+    code: Vec<Instr<T>>,
+    // The variables the program loads, each once
+    names: Vec<String>,
+    // The most values on the stack at any time
+    max_stack: usize,
+}
+
+/// Compiles `operation` into a [`Program`].
+pub fn compile<T: Number>(operation: &Operation<T>) -> Program<T> {
+    let mut program: Program<T> = Program {
+        code: Vec::new(),
+        names: Vec::new(),
+        max_stack: 0,
+    };
+    emit(operation, &mut program, 0);
+    program
+}
+
+// Writes the instructions of `operation`, where `depth` values are already
+// on the stack
+fn emit<T: Number>(operation: &Operation<T>, program: &mut Program<T>, depth: usize) {
+    program.max_stack = program.max_stack.max(depth + 1);
+    let (left, right, instr) = match operation {
+        Operation::Value(v) => return program.code.push(Instr::Push(v.clone())),
+        Operation::Var(name) => {
+            let slot: usize = match program.names.iter().position(|n| n == name) {
+                Some(slot) => slot,
+                None => {
+                    program.names.push(name.clone());
+                    program.names.len() - 1
+                }
+            };
+            return program.code.push(Instr::Load(slot));
+        }
+        Operation::Neg(a) => {
+            emit(a, program, depth);
+            return program.code.push(Instr::Neg);
+        }
+        Operation::Call { function, args } => {
+            for (i, arg) in args.iter().enumerate() {
+                emit(arg, program, depth + i);
+            }
+            return program.code.push(Instr::Call {
+                function: function.clone(),
+                args: args.len(),
+            });
+        }
+        Operation::Add(a, b) => (a, b, Instr::Add),
+        Operation::Mul(a, b) => (a, b, Instr::Mul),
+        Operation::Sub { first, second } => (first, second, Instr::Sub),
+        Operation::Div { divident, divisor } => (divident, divisor, Instr::Div),
+        Operation::Rem { divident, divisor } => (divident, divisor, Instr::Rem),
+        Operation::Pow { base, exponent } => (base, exponent, Instr::Pow),
+        Operation::BitAnd(a, b) => (a, b, Instr::BitAnd),
+        Operation::BitOr(a, b) => (a, b, Instr::BitOr),
+        Operation::BitXor(a, b) => (a, b, Instr::BitXor),
+        Operation::Shl { value, amount } => (value, amount, Instr::Shl),
+        Operation::Shr { value, amount } => (value, amount, Instr::Shr),
+    };
+    emit(left, program, depth);
+    emit(right, program, depth + 1);
+    program.code.push(instr);
+}
+
+impl<T: Number> Program<T> {
+    /// The instructions, in execution order.
+    pub fn code(&self) -> &[Instr<T>] {
+        &self.code
+    }
+
+    /// The variables the program loads, each once. `Load(i)` loads `names()[i]`.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// The most values on the stack at any time.
+    pub fn max_stack(&self) -> usize {
+        self.max_stack
+    }
+
+    /// Runs the program in `mode`, taking the values of variables from `env`.
+    ///
+    /// This looks up the variables and allocates a stack on every call. To
+    /// run a program many times, [`bind`](Program::bind) the variables once
+    /// and call [`run_with`](Program::run_with).
+    pub fn run(&self, env: &Env<T>, mode: Mode) -> Result<T, MathError> {
+        self.run_located(env, mode).map_err(|(_, e)| e)
+    }
+
+    /// Runs the program like [`run`](Program::run), an error comes with the
+    /// index of the instruction that failed.
+    pub fn run_located(&self, env: &Env<T>, mode: Mode) -> Result<T, (usize, MathError)> {
+        let mut stack: Vec<T> = Vec::with_capacity(self.max_stack);
+        self.execute(&self.bind(env), &mut stack, mode)
+    }
+
+    /// The values of [`names`](Program::names) in `env`, in slot order. A
+    /// missing variable is `None` and only an error when its `load` runs,
+    /// like in the tree.
+    pub fn bind(&self, env: &Env<T>) -> Vec<Option<T>> {
+        self.names.iter().map(|name| env.value(name)).collect()
+    }
+
+    /// Runs the program with variables from [`bind`](Program::bind) on a
+    /// stack the caller keeps, so repeated runs neither look up names nor
+    /// allocate. The stack is cleared first.
+    ///
+    /// # Panics
+    ///
+    /// If `vars` has fewer values than the program has names.
+    pub fn run_with(
+        &self,
+        vars: &[Option<T>],
+        stack: &mut Vec<T>,
+        mode: Mode,
+    ) -> Result<T, MathError> {
+        self.execute(vars, stack, mode).map_err(|(_, e)| e)
+    }
+
+    fn execute(
+        &self,
+        vars: &[Option<T>],
+        stack: &mut Vec<T>,
+        mode: Mode,
+    ) -> Result<T, (usize, MathError)> {
+        assert!(vars.len() >= self.names.len(), "a value for every name");
+        stack.clear();
+        for (index, instr) in self.code.iter().enumerate() {
+            let value: Result<T, MathError> = match instr {
+                Instr::Push(v) => Ok(v.clone()),
+                Instr::Load(slot) => vars[*slot]
+                    .clone()
+                    .ok_or_else(|| MathError::UndefinedVariable(self.names[*slot].clone())),
+                Instr::Neg => pop(stack).neg(mode),
+                Instr::Call { function, args } => {
+                    let args: Vec<T> = stack.split_off(stack.len() - args);
+                    T::call(function, &args, mode)
+                }
+                binary => {
+                    let b: T = pop(stack);
+                    let a: T = pop(stack);
+                    match binary {
+                        Instr::Add => a.add(&b, mode),
+                        Instr::Sub => a.sub(&b, mode),
+                        Instr::Mul => a.mul(&b, mode),
+                        Instr::Div => a.div(&b, mode),
+                        Instr::Rem => a.rem(&b, mode),
+                        Instr::Pow => a.pow(&b, mode),
+                        Instr::BitAnd => a.bit_and(&b, mode),
+                        Instr::BitOr => a.bit_or(&b, mode),
+                        Instr::BitXor => a.bit_xor(&b, mode),
+                        Instr::Shl => a.shl(&b, mode),
+                        Instr::Shr => a.shr(&b, mode),
+                        Instr::Push(_) | Instr::Load(_) | Instr::Neg | Instr::Call { .. } => {
+                            unreachable!()
+                        }
+                    }
+                }
+            };
+            stack.push(value.map_err(|e| (index, e))?);
+        }
+        Ok(pop(stack))
+    }
+}
+
+// `compile` only emits instructions whose operands were pushed before them
+fn pop<T>(stack: &mut Vec<T>) -> T {
+    stack
+        .pop()
+        .expect("compiled programs never underflow the stack")
+}
+
+/// The disassembly: one numbered instruction per line.
+impl<T: Number> fmt::Display for Program<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, instr) in self.code.iter().enumerate() {
+            write!(f, "{:04}  ", i)?;
+            match instr {
+                Instr::Push(v) => writeln!(f, "push {}", v)?,
+                Instr::Load(slot) => writeln!(f, "load {}", self.names[*slot])?,
+                Instr::Neg => writeln!(f, "neg")?,
+                Instr::Add => writeln!(f, "add")?,
+                Instr::Sub => writeln!(f, "sub")?,
+                Instr::Mul => writeln!(f, "mul")?,
+                Instr::Div => writeln!(f, "div")?,
+                Instr::Rem => writeln!(f, "rem")?,
+                Instr::Pow => writeln!(f, "pow")?,
+                Instr::BitAnd => writeln!(f, "and")?,
+                Instr::BitOr => writeln!(f, "or")?,
+                Instr::BitXor => writeln!(f, "xor")?,
+                Instr::Shl => writeln!(f, "shl")?,
+                Instr::Shr => writeln!(f, "shr")?,
+                Instr::Call { function, args } => writeln!(f, "call {} {}", function, args)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How long both evaluation paths took for the same operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comparison {
+    /// Times the operation was evaluated on each path.
+    pub iterations: u32,
+    /// Total time of [`Operation::execute_in`].
+    pub tree: Duration,
+    /// Total time of [`Program::run_with`], binding the variables once
+    /// included.
+    pub vm: Duration,
+    /// Time of compiling the operation once.
+    pub compile: Duration,
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let per_run = |total: Duration| total.as_nanos() / self.iterations.max(1) as u128;
+        write!(
+            f,
+            "{} runs: tree {:?} ({} ns/run), vm {:?} ({} ns/run), compile {:?}",
+            self.iterations,
+            self.tree,
+            per_run(self.tree),
+            self.vm,
+            per_run(self.vm),
+            self.compile
+        )
+    }
+}
+
+/// Evaluates `operation` `iterations` times by walking the tree, then as
+/// many times on the VM, and measures both.
+pub fn benchmark<T: Number>(
+    operation: &Operation<T>,
+    env: &Env<T>,
+    mode: Mode,
+    iterations: u32,
+) -> Comparison {
+    let start: Instant = Instant::now();
+    for _ in 0..iterations {
+        // black_box keeps the optimizer from skipping the unused results
+        std::hint::black_box(operation.execute_in(env, mode)).ok();
+    }
+    let tree: Duration = start.elapsed();
+
+    let start: Instant = Instant::now();
+    let program: Program<T> = compile(operation);
+    let compile: Duration = start.elapsed();
+
+    let start: Instant = Instant::now();
+    let vars: Vec<Option<T>> = program.bind(env);
+    let mut stack: Vec<T> = Vec::with_capacity(program.max_stack);
+    for _ in 0..iterations {
+        std::hint::black_box(program.run_with(&vars, &mut stack, mode)).ok();
+    }
+    let vm: Duration = start.elapsed();
+
+    Comparison {
+        iterations,
+        tree,
+        vm,
+        compile,
+    }
+}
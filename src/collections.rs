@@ -0,0 +1,176 @@
+//! Strings, vectors and hash maps.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::tr;
+
+// Strings
+// Texts in Rust are encoded in UTF-8
+// Rust has two different types of strings: String and &str
+// String is a growable, heap-allocated data structure, while &str is a string slice
+// String is mutable, while &str is immutable
+/// `String` and `&str`.
+#[allow(clippy::redundant_slicing, clippy::disallowed_names)]
+pub fn string_example(out: &mut dyn Write) -> io::Result<()> {
+    let s1: &str = "Olá mundo! 🍕";
+    let s2: String = String::from("Olá mundo! 🍕");
+    writeln!(out, "s2: {}", s2)?;
+    let s3: String = "Olá mundo! 🍕".to_string();
+    writeln!(out, "s3: {}", s3)?;
+    let s4: &str = &s1[..];
+    writeln!(out, "s4: {}", s4)?;
+
+    let mut foo: String = String::from("Foo");
+    foo.push_str("bar");
+    writeln!(out, "foo: {}", foo)?;
+
+    foo.replace_range(.., "Baz");
+    writeln!(out, "{}", tr!("string_example.after_replace", "foo", foo))?;
+    Ok(())
+}
+
+// Concatenation
+/// Joining strings with `+`, `format!` and `concat`.
+pub fn concatenation_example(out: &mut dyn Write) -> io::Result<()> {
+    let s1: String = String::from("Olá");
+    let s2: String = String::from("mundo!");
+    let s3: String = s1 + " " + &s2; // Concatenating strings
+    writeln!(out, "s3: {}", s3)?;
+
+    // format macro
+    let s4: String = String::from("Hello");
+    let s5: String = String::from("world");
+    let s6: String = format!("{} {}", s4, s5);
+    writeln!(out, "s6: {}", s6)?;
+
+    let s7: String = ["Hello", " ", "world"].concat();
+    writeln!(out, "s7: {}", s7)?;
+    Ok(())
+}
+
+// Indexing Strings
+/// Why strings can not be indexed by an integer.
+pub fn index_strings(out: &mut dyn Write) -> io::Result<()> {
+    let s1: &str = "🍕🍕🍕🍕🍕";
+    // let s2 = s1[0]; // This would cause an error, because Rust does not allow indexing into strings directly using a integer (E0277, tests/compile_fail/index_string.rs)
+    // let s2: &str = &s1[0..2]; // This is how you can get a slice of the string, however this would cause a panic because the 🍕 has a length of 4 bytes
+    let s2: &str = &s1[0..4];
+    writeln!(out, "s2: {}", s2)?;
+    Ok(())
+}
+
+// Vectors
+// Vectors are a growable array type
+// They are similar to arrays, but they can grow dynamically
+/// Creating, reading and mutating vectors.
+pub fn vector_example(out: &mut dyn Write) -> io::Result<()> {
+    let a: [i32; 5] = [1, 2, 3, 4, 5];
+
+    let mut v: Vec<i32> = Vec::new();
+    v.push(10);
+    v.push(20);
+    v.push(30);
+
+    let v2: Vec<i32> = vec![10, 20, 30];
+
+    // This works because we are using the dereference operator to get a reference to the first element of the vector
+    // Would cause an runtime error if we tried to access an index that is out of bounds of the vector
+    let first: &i32 = &v2[0];
+    writeln!(out, "first: {}", first)?;
+
+    match v.get(20) {
+        Some(value) => writeln!(out, "{}", tr!("vector_example.value_at", 20, value))?,
+        None => writeln!(out, "{}", tr!("vector_example.no_value_at", 20))?,
+    }
+
+    for i in &a {
+        writeln!(out, "{}", tr!("vector_example.element", i))?;
+    }
+
+    for i in &mut v {
+        *i += 10;
+        writeln!(out, "{}", tr!("vector_example.mutated", i))?;
+    }
+    Ok(())
+}
+
+// Vectors with different types
+// We can use enums to store different types in a vector
+/// Storing different types in a vector with an enum.
+#[allow(clippy::approx_constant)]
+pub fn vector_example2(out: &mut dyn Write) -> io::Result<()> {
+    enum SpreadsheetCell {
+        Int(i32),
+        Float(f64),
+        Text(String),
+    }
+
+    let row: Vec<SpreadsheetCell> = vec![
+        SpreadsheetCell::Int(1),
+        SpreadsheetCell::Text(String::from("Hello")),
+        SpreadsheetCell::Float(3.14),
+    ];
+
+    match &row[1] {
+        SpreadsheetCell::Int(i) => writeln!(out, "{}", tr!("vector_example2.integer", i))?,
+        SpreadsheetCell::Float(f) => writeln!(out, "{}", tr!("vector_example2.float", f))?,
+        SpreadsheetCell::Text(t) => writeln!(out, "{}", tr!("vector_example2.text_cell", t))?,
+    }
+    Ok(())
+}
+
+// Hashmaps
+// allow you to store key-value pairs, they can be in any type, uses a hash function to map keys to values
+/// Inserting and reading values from a `HashMap`.
+pub fn hashmap_example(out: &mut dyn Write) -> io::Result<()> {
+    let blue: String = String::from("Blue");
+    let red: String = String::from("Red");
+
+    let mut scores: HashMap<String, i32> = HashMap::new();
+
+    scores.insert(blue, 10);
+    scores.insert(red, 5);
+
+    let team_name: String = String::from("Blue");
+    let score = scores.get(&team_name);
+
+    writeln!(out, "{}", tr!("hashmap_example.score", score.unwrap()))?;
+
+    for (key, value) in &scores {
+        writeln!(out, "{}: {}", key, value)?;
+    }
+    Ok(())
+}
+
+// Update a value in a hashmap
+/// Overwriting values and the `entry` API.
+pub fn hashmap_example2(out: &mut dyn Write) -> io::Result<()> {
+    let mut scores: HashMap<String, i32> = HashMap::new();
+
+    scores.insert(String::from("Blue"), 10);
+    scores.insert(String::from("Blue"), 20); // This will overwrite the previous value for "Blue"
+
+    scores.entry(String::from("Red")).or_insert(5);
+    scores.entry(String::from("Red")).or_insert(40); // This will not overwrite the previous value for "Red"
+
+    for (key, value) in &scores {
+        writeln!(out, "{}: {}", key, value)?;
+    }
+    Ok(())
+}
+
+// Update a value in a hashmap based on the old value
+/// Counting words by updating a value based on the old one.
+pub fn hashmap_example3(out: &mut dyn Write) -> io::Result<()> {
+    let text = "hello world wonderful world";
+    let mut map: HashMap<String, i32> = HashMap::new();
+
+    for word in text.split_whitespace() {
+        let count = map.entry(word.to_string()).or_insert(0);
+        *count += 1; // Increment the count for the word
+    }
+
+    writeln!(out, "{:?}", map)?;
+    Ok(())
+}
@@ -0,0 +1,179 @@
+//! Enums, `Option`, `match`, `if let` and `Result`.
+
+use std::io::{self, Write};
+
+use crate::tr;
+
+// The calculator's expression tree, which the last example runs. It is a
+// recursive enum: the operands of an operation are operations themselves,
+// kept in `Box`es so that it has a known size. See src/calc/operation.rs
+pub use crate::calc::Operation;
+
+// Enums
+/// The version of an IP address, with its parts.
+#[derive(Debug)]
+pub enum IpAddrKind {
+    /// Four octets, e.g. `192.168.1.1`.
+    V4(u8, u8, u8, u8),
+    /// Textual IPv6 address, e.g. `::1`.
+    V6(String),
+}
+
+/// An IP address together with its textual form.
+#[derive(Debug)]
+pub struct IpAddr {
+    /// Parsed version and parts.
+    pub kind: IpAddrKind,
+    /// Address as written.
+    pub address: String,
+}
+
+/// Enums holding data, read back with `if let`.
+pub fn enum_example(out: &mut dyn Write) -> io::Result<()> {
+    let ip_v4: IpAddr = IpAddr {
+        kind: IpAddrKind::V4(192, 168, 1, 1),
+        address: String::from("192.168.1.1"),
+    };
+    let ip_v6: IpAddr = IpAddr {
+        kind: IpAddrKind::V6("::1".to_string()),
+        address: String::from("::1"),
+    };
+
+    writeln!(out, "ip_v4: {:?}", ip_v4)?;
+    writeln!(out, "ip_v6: {:?}", ip_v6)?;
+
+    // Use the fields of IpAddrKind::V4 to avoid dead_code warning
+    if let IpAddrKind::V4(a, b, c, d) = ip_v4.kind {
+        writeln!(out, "{}", tr!("enum_example.v4_fields", a, b, c, d))?;
+        writeln!(out, "{}", tr!("enum_example.v4_address", ip_v4.address))?;
+    }
+
+    if let IpAddrKind::V6(addr) = ip_v6.kind {
+        writeln!(out, "{}", tr!("enum_example.v6_address", addr))?;
+        writeln!(
+            out,
+            "{}",
+            tr!("enum_example.v6_address_field", ip_v6.address)
+        )?;
+    }
+    Ok(())
+}
+
+// Option Enum
+// The Option enum is a powerful way to handle optional values in Rust
+// It can either be Some(T) or None, where T is the type of the value
+// This allows us to avoid null pointer exceptions and handle cases where a value may not be present
+/// A hand written copy of `std::option::Option`.
+#[derive(Debug)]
+pub enum Option<T> {
+    /// A value is present.
+    Some(T),
+    /// No value.
+    None,
+}
+
+/// Matching on the hand written [`Option`].
+pub fn option_example(out: &mut dyn Write) -> io::Result<()> {
+    let some_value: Option<i32> = Option::Some(5);
+    let no_value: Option<i32> = Option::None;
+
+    match some_value {
+        Option::Some(v) => writeln!(out, "{}", tr!("option_example.some_value", v))?,
+        Option::None => writeln!(out, "{}", tr!("option_example.no_value"))?,
+    }
+
+    match no_value {
+        Option::Some(v) => writeln!(out, "{}", tr!("option_example.some_value", v))?,
+        Option::None => writeln!(out, "{}", tr!("option_example.no_value"))?,
+    }
+    Ok(())
+}
+
+// Matching on Option
+
+/// US coins.
+pub enum Coin {
+    /// 1 cent.
+    Penny,
+    /// 5 cents.
+    Nickel,
+    /// 10 cents.
+    Dime,
+    /// 25 cents.
+    Quarter,
+}
+
+/// Prints the name of `coin` and returns its value in cents.
+pub fn value_in_cents(out: &mut dyn Write, coin: Coin) -> io::Result<u8> {
+    let cents: u8 = match coin {
+        Coin::Penny => {
+            writeln!(out, "{}", tr!("value_in_cents.penny"))?;
+            1
+        }
+        Coin::Nickel => {
+            writeln!(out, "{}", tr!("value_in_cents.nickel"))?;
+            5
+        }
+        Coin::Dime => {
+            writeln!(out, "{}", tr!("value_in_cents.dime"))?;
+            10
+        }
+        Coin::Quarter => {
+            writeln!(out, "{}", tr!("value_in_cents.quarter"))?;
+            25
+        }
+    };
+    Ok(cents)
+}
+
+// IF LET
+/// `match` versus `if let` for a single pattern.
+pub fn if_let_example(out: &mut dyn Write) -> io::Result<()> {
+    let some_value = Some(5);
+
+    // Match is more expensive than if let, so if we only care about one case, we can use if let
+    match some_value {
+        Some(5) => writeln!(out, "{}", tr!("if_let_example.some_value"))?,
+        _ => writeln!(out, "{}", tr!("if_let_example.no_value"))?,
+    }
+
+    if let Some(5) = some_value {
+        writeln!(out, "{}", tr!("if_let_example.some_value"))?;
+    }
+    Ok(())
+}
+
+// Result
+// enum Result<T, E> {
+//     OK(T),
+//     Err(E),
+// }
+
+// fn result_example(out: &mut dyn Write) -> io::Result<()> {
+//     let result: Result<i32, String> = Result::OK(5);
+//     let error: Result<i32, String> = Result::Err(String::from("An error occurred"));
+
+//     match result {
+//         Result::OK(v) => writeln!(out, "Result is OK: {}", v)?,
+//         Result::Err(e) => writeln!(out, "Error: {}", e)?,
+//     }
+
+//     match error {
+//         Result::OK(v) => writeln!(out, "Result is OK: {}", v)?,
+//         Result::Err(e) => writeln!(out, "Error: {}", e)?,
+//     }
+//     Ok(())
+// }
+
+/// Running an [`Operation`] and matching on its `Result`.
+pub fn operation_example(out: &mut dyn Write) -> io::Result<()> {
+    let user_input = Operation::Div {
+        divident: Box::new(Operation::Value(20)),
+        divisor: Box::new(Operation::Value(0)),
+    };
+    match user_input.execute() {
+        Ok(res) => writeln!(out, "{}", tr!("operation_example.result", res))?,
+        Err(e) => writeln!(out, "{}", tr!("operation_example.error", e))?,
+    }
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! Rustlings-style exercises, one per lesson.
+//!
+//! Each lesson has a file `<lesson>.rs` in the exercise folder (`exercises/`
+//! by default) with `todo!()`s and tests. Checking an exercise compiles it with
+//! `rustc --test` and runs its tests, [`watch`] does that on every change.
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::lessons::LESSONS;
+
+/// The default exercise folder, `$LEARN_RUST_EXERCISES` or `exercises`.
+pub fn exercise_dir() -> PathBuf {
+    match env::var_os("LEARN_RUST_EXERCISES") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from("exercises"),
+    }
+}
+
+/// The exercise of one lesson.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exercise {
+    /// Name of the lesson.
+    pub lesson: &'static str,
+    /// Path of the exercise file.
+    pub path: PathBuf,
+}
+
+/// The exercises found in `dir`, in lesson order.
+pub fn exercises(dir: &Path) -> Vec<Exercise> {
+    LESSONS
+        .iter()
+        .map(|lesson| Exercise {
+            lesson: lesson.name,
+            path: dir.join(format!("{}.rs", lesson.name)),
+        })
+        .filter(|exercise| exercise.path.is_file())
+        .collect()
+}
+
+/// What checking an exercise found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    /// rustc rejected the file, with its error output.
+    CompileError(String),
+    /// The tests ran and some failed, with the test output.
+    TestsFailed(String),
+    /// Every test passed.
+    Passed,
+}
+
+fn rustc() -> String {
+    env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"))
+}
+
+// The directory one check builds in, removed with what is in it when dropped
+struct BuildDir(PathBuf);
+
+impl BuildDir {
+    // A new directory for every check, so that two checks, in one process
+    // or in two, never write or run the same binary
+    fn new() -> io::Result<BuildDir> {
+        static CHECKS: AtomicUsize = AtomicUsize::new(0);
+        let dir: PathBuf = env::temp_dir().join(format!(
+            "learn_rust_exercises_{}_{}",
+            process::id(),
+            CHECKS.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir)?;
+        Ok(BuildDir(dir))
+    }
+}
+
+impl Drop for BuildDir {
+    fn drop(&mut self) {
+        // Nothing is lost if a file stays behind in the temp directory
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Compiles `exercise` with its tests and runs them.
+///
+/// The binary is built in a directory of its own in the temp directory,
+/// which is removed afterwards.
+pub fn check(exercise: &Exercise) -> io::Result<Check> {
+    let build_dir: BuildDir = BuildDir::new()?;
+    let binary: PathBuf =
+        build_dir
+            .0
+            .join(format!("{}{}", exercise.lesson, env::consts::EXE_SUFFIX));
+
+    let compiled: Output = Command::new(rustc())
+        .arg("--edition=2024")
+        .arg("--test")
+        .arg("-A")
+        .arg("warnings")
+        .arg("-o")
+        .arg(&binary)
+        .arg(&exercise.path)
+        .output()?;
+    if !compiled.status.success() {
+        return Ok(Check::CompileError(
+            String::from_utf8_lossy(&compiled.stderr).into_owned(),
+        ));
+    }
+
+    let tested: Output = Command::new(&binary)
+        .arg("--quiet")
+        .env("RUST_BACKTRACE", "0")
+        .output()?;
+    if tested.status.success() {
+        Ok(Check::Passed)
+    } else {
+        Ok(Check::TestsFailed(
+            String::from_utf8_lossy(&tested.stdout).into_owned(),
+        ))
+    }
+}
+
+// The latest modification time of the .rs files in `dir`
+fn last_change(dir: &Path) -> io::Result<Option<SystemTime>> {
+    let mut latest: Option<SystemTime> = None;
+    for entry in fs::read_dir(dir)? {
+        let path: PathBuf = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "rs") {
+            let modified: SystemTime = fs::metadata(&path)?.modified()?;
+            latest = latest.max(Some(modified));
+        }
+    }
+    Ok(latest)
+}
+
+/// Works through the exercises in `dir` in lesson order.
+///
+/// The current exercise is checked, and checked again each time a file in
+/// `dir` changes (polling every `interval`). Once it passes, the next
+/// exercise becomes current. Returns when every exercise passed.
+pub fn watch(dir: &Path, interval: Duration, out: &mut dyn Write) -> io::Result<()> {
+    let exercises: Vec<Exercise> = exercises(dir);
+    if exercises.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no exercises in {}", dir.display()),
+        ));
+    }
+
+    for (i, exercise) in exercises.iter().enumerate() {
+        let mut seen: Option<SystemTime> = last_change(dir)?;
+
+        loop {
+            match check(exercise)? {
+                Check::Passed => {
+                    writeln!(out, "✓ {} ({}/{})", exercise.lesson, i + 1, exercises.len())?;
+                    break;
+                }
+                Check::CompileError(errors) => {
+                    writeln!(out, "✗ {} does not compile:", exercise.path.display())?;
+                    writeln!(out, "{}", errors.trim_end())?;
+                }
+                Check::TestsFailed(output) => {
+                    writeln!(out, "✗ {} tests failed:", exercise.path.display())?;
+                    writeln!(out, "{}", output.trim_end())?;
+                }
+            }
+            writeln!(out, "Waiting for changes in {}...", dir.display())?;
+            out.flush()?;
+
+            // Poll until something in the folder is saved again
+            loop {
+                thread::sleep(interval);
+                let now: Option<SystemTime> = last_change(dir)?;
+                if now != seen {
+                    seen = now;
+                    break;
+                }
+            }
+        }
+    }
+
+    writeln!(out, "All {} exercises done!", exercises.len())?;
+    Ok(())
+}
@@ -0,0 +1,229 @@
+//! Exports the course as a static book of Markdown or HTML pages.
+//!
+//! The book has an index page with a table of contents grouped by topic, and
+//! one page per lesson with its text, its source and the output it prints.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::lessons::{LESSONS, Lesson, Topic};
+use crate::{i18n, tr};
+
+/// The page format of an exported book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `.md` pages.
+    Markdown,
+    /// `.html` pages.
+    Html,
+}
+
+impl Format {
+    /// Parses `markdown` (or `md`) and `html`.
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "markdown" | "md" => Some(Format::Markdown),
+            "html" => Some(Format::Html),
+            _ => None,
+        }
+    }
+
+    /// The file extension of the pages.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Markdown => "md",
+            Format::Html => "html",
+        }
+    }
+}
+
+/// Writes the book into `dir` and returns the paths of the written pages.
+pub fn export(dir: &Path, format: Format) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut written: Vec<PathBuf> = Vec::new();
+
+    let index: PathBuf = dir.join(format!("index.{}", format.extension()));
+    fs::write(&index, render_index(format))?;
+    written.push(index);
+
+    for (i, lesson) in LESSONS.iter().enumerate() {
+        let previous: Option<&Lesson> = i.checked_sub(1).map(|p| &LESSONS[p]);
+        let next: Option<&Lesson> = LESSONS.get(i + 1);
+        let page: PathBuf = dir.join(page_name(lesson, format));
+        fs::write(&page, render_lesson(lesson, previous, next, format)?)?;
+        written.push(page);
+    }
+    Ok(written)
+}
+
+fn page_name(lesson: &Lesson, format: Format) -> String {
+    format!("{}.{}", lesson.name, format.extension())
+}
+
+/// The index page with the table of contents.
+pub fn render_index(format: Format) -> String {
+    let mut page: String = String::new();
+    match format {
+        Format::Markdown => {
+            page.push_str(&format!("# {}\n", tr!("book.title")));
+            for topic in Topic::ALL {
+                page.push_str(&format!("\n## {}\n\n", title(topic)));
+                for lesson in LESSONS.iter().filter(|l| l.topic == topic) {
+                    page.push_str(&format!(
+                        "- [{}]({}) - {}\n",
+                        lesson.name,
+                        page_name(lesson, format),
+                        lesson.description()
+                    ));
+                }
+            }
+        }
+        Format::Html => {
+            page.push_str(&format!("<h1>{}</h1>\n", tr!("book.title")));
+            for topic in Topic::ALL {
+                page.push_str(&format!("<h2>{}</h2>\n<ul>\n", title(topic)));
+                for lesson in LESSONS.iter().filter(|l| l.topic == topic) {
+                    page.push_str(&format!(
+                        "<li><a href=\"{}\">{}</a> - {}</li>\n",
+                        page_name(lesson, format),
+                        lesson.name,
+                        escape_html(lesson.description())
+                    ));
+                }
+                page.push_str("</ul>\n");
+            }
+            page = html_document(tr!("book.title"), &page);
+        }
+    }
+    page
+}
+
+/// The page of one lesson, linking to the lessons before and after it.
+pub fn render_lesson(
+    lesson: &Lesson,
+    previous: Option<&Lesson>,
+    next: Option<&Lesson>,
+    format: Format,
+) -> io::Result<String> {
+    let output: String = lesson.output()?;
+    let mut page: String = String::new();
+
+    match format {
+        Format::Markdown => {
+            page.push_str(&format!("# {}\n\n", lesson.name));
+            page.push_str(&format!(
+                "*{}* - {}\n\n",
+                title(lesson.topic),
+                lesson.description()
+            ));
+            for paragraph in lesson.paragraphs() {
+                page.push_str(paragraph);
+                page.push_str("\n\n");
+            }
+            page.push_str(&format!("## {}\n\n```rust\n", tr!("book.code")));
+            page.push_str(lesson.source());
+            page.push_str(&format!("```\n\n## {}\n\n```text\n", tr!("book.output")));
+            page.push_str(&output);
+            page.push_str("```\n\n");
+            page.push_str(&navigation(previous, next, format));
+        }
+        Format::Html => {
+            page.push_str(&format!("<h1>{}</h1>\n", lesson.name));
+            page.push_str(&format!(
+                "<p><em>{}</em> - {}</p>\n",
+                title(lesson.topic),
+                escape_html(lesson.description())
+            ));
+            for paragraph in lesson.paragraphs() {
+                page.push_str(&format!("<p>{}</p>\n", inline_code(paragraph)));
+            }
+            page.push_str(&format!(
+                "<h2>{}</h2>\n<pre><code class=\"language-rust\">",
+                tr!("book.code")
+            ));
+            page.push_str(&escape_html(lesson.source()));
+            page.push_str(&format!(
+                "</code></pre>\n<h2>{}</h2>\n<pre><samp>",
+                tr!("book.output")
+            ));
+            page.push_str(&escape_html(&output));
+            page.push_str("</samp></pre>\n");
+            page.push_str(&navigation(previous, next, format));
+            page = html_document(lesson.name, &page);
+        }
+    }
+    Ok(page)
+}
+
+fn navigation(previous: Option<&Lesson>, next: Option<&Lesson>, format: Format) -> String {
+    let link = |label: &str, target: &str| match format {
+        Format::Markdown => format!("[{}]({})", label, target),
+        Format::Html => format!("<a href=\"{}\">{}</a>", target, escape_html(label)),
+    };
+
+    let mut links: Vec<String> = Vec::new();
+    if let Some(lesson) = previous {
+        links.push(link(
+            &format!("← {}", lesson.name),
+            &page_name(lesson, format),
+        ));
+    }
+    links.push(link(
+        tr!("book.contents"),
+        &format!("index.{}", format.extension()),
+    ));
+    if let Some(lesson) = next {
+        links.push(link(
+            &format!("{} →", lesson.name),
+            &page_name(lesson, format),
+        ));
+    }
+
+    match format {
+        Format::Markdown => format!("---\n\n{}\n", links.join(" | ")),
+        Format::Html => format!("<nav>{}</nav>\n", links.join(" | ")),
+    }
+}
+
+// "basics" -> "Basics", in the current locale
+fn title(topic: Topic) -> &'static str {
+    i18n::get(&format!("topic.{}", topic.name())).unwrap_or(topic.name())
+}
+
+fn html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        body
+    )
+}
+
+/// Escapes the characters that have a meaning in HTML.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped: String = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Escapes `text` and turns `code` spans into <code> elements
+fn inline_code(text: &str) -> String {
+    text.split('`')
+        .enumerate()
+        .map(|(i, part)| {
+            if i % 2 == 1 {
+                format!("<code>{}</code>", escape_html(part))
+            } else {
+                escape_html(part)
+            }
+        })
+        .collect()
+}
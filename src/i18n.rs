@@ -0,0 +1,139 @@
+//! Message catalogs for the lesson text and the labels the lessons print.
+//!
+//! Every message has a key like `vars.has_value`. English is the reference
+//! catalog, a key missing from another locale falls back to English, and a
+//! key missing from both is shown as is. Messages use `{}` placeholders,
+//! filled in order by [`format()`] and the [`tr!`](crate::tr) macro.
+
+mod en;
+mod pt_br;
+
+use std::cell::Cell;
+use std::env;
+use std::fmt::Display;
+
+/// A supported locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    /// English (`en`).
+    En,
+    /// Brazilian Portuguese (`pt-BR`).
+    PtBr,
+}
+
+impl Lang {
+    /// Every supported locale.
+    pub const ALL: [Lang; 2] = [Lang::En, Lang::PtBr];
+
+    /// The locale code, e.g. `pt-BR`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::PtBr => "pt-BR",
+        }
+    }
+
+    /// Parses a locale code, also accepting POSIX forms like `pt_BR.UTF-8`.
+    pub fn from_code(code: &str) -> Option<Lang> {
+        let code: String = code.split('.').next().unwrap_or("").replace('_', "-");
+        let language: &str = code.split('-').next().unwrap_or("");
+        match language.to_ascii_lowercase().as_str() {
+            "en" => Some(Lang::En),
+            "pt" => Some(Lang::PtBr),
+            _ => None,
+        }
+    }
+
+    /// The locale from `$LEARN_RUST_LANG`, then `$LANG`.
+    pub fn from_env() -> Option<Lang> {
+        ["LEARN_RUST_LANG", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find_map(|value| Lang::from_code(&value))
+    }
+
+    /// The messages of this locale.
+    pub fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Lang::En => en::MESSAGES,
+            Lang::PtBr => pt_br::MESSAGES,
+        }
+    }
+}
+
+thread_local! {
+    static LANG: Cell<Lang> = const { Cell::new(Lang::En) };
+}
+
+/// Selects the locale used by the current thread, English by default.
+pub fn set_lang(lang: Lang) {
+    LANG.with(|current| current.set(lang));
+}
+
+/// The locale used by the current thread.
+pub fn lang() -> Lang {
+    LANG.with(|current| current.get())
+}
+
+fn lookup(lang: Lang, key: &str) -> Option<&'static str> {
+    lang.catalog()
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, message)| *message)
+}
+
+/// The message for `key` in the current locale, or in English when it is not translated.
+pub fn get(key: &str) -> Option<&'static str> {
+    lookup(lang(), key).or_else(|| lookup(Lang::En, key))
+}
+
+/// The message for `key` in the current locale, `key` itself when no catalog has it.
+pub fn text(key: &str) -> &str {
+    get(key).unwrap_or(key)
+}
+
+/// The message for `key` with its `{}` placeholders replaced by `args`, in order.
+pub fn format(key: &str, args: &[&dyn Display]) -> String {
+    let mut parts = text(key).split("{}");
+    let mut formatted: String = parts.next().unwrap_or("").to_string();
+    for (i, part) in parts.enumerate() {
+        match args.get(i) {
+            Some(arg) => formatted.push_str(&arg.to_string()),
+            None => formatted.push_str("{}"),
+        }
+        formatted.push_str(part);
+    }
+    formatted
+}
+
+/// Keys of the English catalog that `lang` does not translate.
+pub fn missing_keys(lang: Lang) -> Vec<&'static str> {
+    Lang::En
+        .catalog()
+        .iter()
+        .map(|(key, _)| *key)
+        .filter(|key| lookup(lang, key).is_none())
+        .collect()
+}
+
+/// Keys of `lang` that do not exist in the English catalog.
+pub fn unknown_keys(lang: Lang) -> Vec<&'static str> {
+    lang.catalog()
+        .iter()
+        .map(|(key, _)| *key)
+        .filter(|key| lookup(Lang::En, key).is_none())
+        .collect()
+}
+
+/// Looks up a message in the current locale, filling its `{}` placeholders.
+///
+/// `tr!("loops.counter", i)` is `Counter: 1` in English and `Contador: 1` in Portuguese.
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::text($key)
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::format($key, &[$(&$arg as &dyn ::std::fmt::Display),+])
+    };
+}
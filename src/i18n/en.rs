@@ -0,0 +1,382 @@
+//! English messages, the reference catalog every other locale is checked against.
+
+pub(super) const MESSAGES: &[(&str, &str)] = &[
+    // Topics
+    ("topic.basics", "Basics"),
+    ("topic.ownership", "Ownership"),
+    ("topic.structs", "Structs"),
+    ("topic.enums", "Enums"),
+    ("topic.collections", "Collections"),
+    // Exported book
+    ("book.title", "Learn Rust"),
+    ("book.code", "Code"),
+    ("book.output", "Output"),
+    ("book.contents", "Contents"),
+    // Calculator errors
+    ("math.divide_by_zero", "Can not divide by zero"),
+    ("math.overflow", "Result is too large"),
+    ("math.underflow", "Result is too small"),
+    (
+        "math.negative_exponent",
+        "Can not raise to a negative power",
+    ),
+    (
+        "math.fractional_exponent",
+        "Can not raise a rational to a fractional power",
+    ),
+    ("math.undefined_variable", "`{}` is not defined"),
+    (
+        "math.not_an_integer",
+        "Bitwise operations need an integer type",
+    ),
+    (
+        "math.invalid_shift",
+        "Can not shift by a negative amount or by the number of bits or more",
+    ),
+    ("math.dimension_mismatch", "Can not combine `{}` with `{}`"),
+    (
+        "math.fractional_unit",
+        "Can not raise `{}` to a fractional power",
+    ),
+    ("math.unknown_function", "`{}` is not a function"),
+    ("math.argument_count", "`{}` takes {} operand(s)"),
+    ("math.shape_mismatch", "Can not combine a {} with a {}"),
+    ("math.not_square", "A {} is not square"),
+    (
+        "math.singular_matrix",
+        "The matrix is singular and has no inverse",
+    ),
+    ("math.divide_by_matrix", "Can not divide by a matrix"),
+    ("math.matrix_shape", "{}x{} matrix"),
+    ("math.scalar_shape", "number"),
+    ("math.not_real", "`{}` is not a real number"),
+    ("math.not_a_number", "The result is not a number"),
+    // Terminal browser
+    (
+        "tui.help",
+        "↑↓ select  Enter run  / search  PgUp/PgDn scroll  q quit",
+    ),
+    (
+        "tui.search_help",
+        "type to search  ↑↓ select  Enter done  Esc clear",
+    ),
+    ("tui.no_match", "No lesson matches"),
+    ("tui.run_hint", "Press Enter to run this lesson"),
+    ("tui.too_small", "The terminal is too small"),
+    // vars
+    ("vars.description", "Mutability, shadowing and constants"),
+    (
+        "vars.text",
+        "Variables are immutable by default in Rust, declare a variable with `mut` to allow changes.\n\n\
+         Shadowing allows you to reuse a variable name with a new `let`, even with a different type.\n\n\
+         Constants are immutable and must have a type annotation. Underscores make big numbers readable.",
+    ),
+    ("vars.has_value", "{} has the value {}"),
+    // data_types
+    (
+        "data_types.description",
+        "Scalar types and integer literals",
+    ),
+    (
+        "data_types.text",
+        "Integers are signed (`i8` to `i128`, `isize`) or unsigned (`u8` to `u128`, `usize`), and Rust defaults to `i32`.\n\n\
+         Integer literals can be written in decimal, hexadecimal (`0xff`), octal (`0o77`), binary (`0b1111_0000`) or as a byte (`b'A'`). The other scalar types are floats, `bool` and `char`.",
+    ),
+    // compound_data_types
+    ("compound_data_types.description", "Tuples and arrays"),
+    (
+        "compound_data_types.text",
+        "A tuple groups values of different types. Its values are read by destructuring or by indexing with `.0`, `.1`, ...\n\n\
+         Arrays are fixed-size and every element has the same type. For dynamic arrays, use `Vec<T>`.",
+    ),
+    ("compound_data_types.tuple", "Tuple: {}"),
+    (
+        "compound_data_types.destructured",
+        "Destructured: x: {}, y: {}, z: {}",
+    ),
+    (
+        "compound_data_types.indexed",
+        "Indexed: x: {}, y: {}, z: {}",
+    ),
+    ("compound_data_types.array", "Array: {}"),
+    // add
+    ("add.description", "Functions, statements and expressions"),
+    (
+        "add.text",
+        "Functions are declared with the `fn` keyword and named in snake_case.\n\n\
+         The last expression of a function, written without a semicolon, is its return value. The `return` keyword returns early.",
+    ),
+    ("add.adding", "Adding {} and {}"),
+    // control_flow
+    ("control_flow.description", "if / else if / else and match"),
+    (
+        "control_flow.text",
+        "`if` / `else if` / `else` chooses a branch by a `bool` condition. `if` is an expression, so it can also produce a value.\n\n\
+         `match` compares a value against patterns, and `_` is a catch-all pattern.",
+    ),
+    ("control_flow.less_than_10", "Number is less than 10"),
+    (
+        "control_flow.between_10_and_20",
+        "Number is between 10 and 20",
+    ),
+    ("control_flow.20_or_more", "Number is 20 or more"),
+    ("control_flow.numb_is", "numb is {}"),
+    ("control_flow.one", "One"),
+    ("control_flow.two", "Two"),
+    ("control_flow.something_else", "Something else"),
+    // loops
+    ("loops.description", "loop, while and for"),
+    (
+        "loops.text",
+        "Rust has three types of loops: `loop`, `while` and `for`.\n\n\
+         `loop` repeats forever until a `break`, which can return a value. `for` iterates over ranges like `1..=5` and over collections.",
+    ),
+    ("loops.counter", "Counter: {}"),
+    ("loops.element", "Element: {}"),
+    // owner
+    ("owner.description", "Stack, heap and the ownership rules"),
+    (
+        "owner.text",
+        "Ownership is how Rust manages memory, giving memory safety without a garbage collector.\n\n\
+         Each function call pushes a frame on the stack, which holds fixed-size values. Dynamically sized data like the text of a `String` lives on the heap, and the stack only stores a pointer to it.\n\n\
+         The ownership rules: each value has a variable that is its owner, there can only be one owner at a time, and when the owner goes out of scope the value is dropped.",
+    ),
+    // moved
+    ("moved.description", "Copy, move and clone"),
+    (
+        "moved.text",
+        "Assigning an `i32` copies it, because it lives entirely on the stack.\n\n\
+         Assigning a `String` moves it: the new variable becomes the owner and the old one can no longer be used. `clone` makes a deep copy, which is more expensive.",
+    ),
+    // ownership_functions
+    (
+        "ownership_functions.description",
+        "Passing and returning ownership",
+    ),
+    (
+        "ownership_functions.text",
+        "Passing a value to a function moves or copies it, just like an assignment. After `takes_ownership(s)` the `String` can not be used anymore, while an `i32` can.\n\n\
+         Returning a value gives its ownership to the caller.",
+    ),
+    (
+        "ownership_functions.gives_back",
+        "Taking and giving back ownership: {}",
+    ),
+    // takes_ownership
+    (
+        "takes_ownership.description",
+        "A function that takes ownership of a String",
+    ),
+    (
+        "takes_ownership.text",
+        "A function taking a `String` parameter owns it, and the `String` is dropped when the function returns.",
+    ),
+    ("takes_ownership.taking", "Taking ownership: {}"),
+    // makes_copy
+    (
+        "makes_copy.description",
+        "A function that receives a Copy type",
+    ),
+    (
+        "makes_copy.text",
+        "Types like `i32` implement `Copy`, so the function receives a copy and the caller keeps its value.",
+    ),
+    ("makes_copy.making", "Making a copy: {}"),
+    // borrow_example
+    (
+        "borrow_example.description",
+        "Immutable and mutable references",
+    ),
+    (
+        "borrow_example.text",
+        "References borrow a value without taking ownership of it.\n\n\
+         At any given time you can have either one mutable reference or any number of immutable references, and references must always be valid. Modifying a value through an immutable reference does not compile.",
+    ),
+    ("borrow_example.borrowed", "Borrowed: {}"),
+    ("borrow_example.borrowed_again", "Borrowed again: {}"),
+    ("borrow_example.modified", "Modified: {}"),
+    // slice_example
+    ("slice_example.description", "Slicing an array"),
+    (
+        "slice_example.text",
+        "A slice references a contiguous sequence of elements in a collection, like `&a[1..4]`, without owning them.",
+    ),
+    ("slice_example.slice", "Slice: {}"),
+    // string_example
+    ("string_example.description", "String and &str"),
+    (
+        "string_example.text",
+        "Text in Rust is encoded in UTF-8. `String` is a growable, heap-allocated string, while `&str` is an immutable string slice.\n\n\
+         A `String` can be created with `String::from` or `to_string`, and changed with methods like `push_str` and `replace_range`.",
+    ),
+    ("string_example.after_replace", "{} after replace: {}"),
+    // concatenation_example
+    (
+        "concatenation_example.description",
+        "Joining strings with +, format! and concat",
+    ),
+    (
+        "concatenation_example.text",
+        "Strings are joined with `+` (which takes ownership of the left `String`), with the `format!` macro or with `concat` on an array of slices.",
+    ),
+    // index_strings
+    (
+        "index_strings.description",
+        "Why strings can not be indexed by integer",
+    ),
+    (
+        "index_strings.text",
+        "Rust does not allow indexing into a string with an integer, because one character can take several bytes.\n\n\
+         Slicing by byte range works, but the range has to fall on character boundaries: each 🍕 is 4 bytes long.",
+    ),
+    // struct_example
+    (
+        "struct_example.description",
+        "Defining, updating and copying structs",
+    ),
+    (
+        "struct_example.text",
+        "Structs group named fields into a type. `#[derive(Debug)]` lets us print them with `{:?}`.\n\n\
+         A whole struct instance has to be `mut` to change a field, and the struct update syntax `..other` fills the remaining fields from another instance.",
+    ),
+    // build_user
+    ("build_user.description", "Field init shorthand"),
+    (
+        "build_user.text",
+        "When a parameter has the same name as a field, the field init shorthand lets us write `email` instead of `email: email`.",
+    ),
+    // tuple_struct
+    ("tuple_struct.description", "Structs without named fields"),
+    (
+        "tuple_struct.text",
+        "Tuple structs are structs without named fields, their values are read like a tuple's.",
+    ),
+    // calc_area_rect
+    (
+        "calc_area_rect.description",
+        "Passing a struct by reference",
+    ),
+    (
+        "calc_area_rect.text",
+        "Passing a struct by reference lets a function read it without taking ownership.",
+    ),
+    ("calc_area_rect.area", "Area of rectangle: {}"),
+    // calc_area_rect2
+    (
+        "calc_area_rect2.description",
+        "Methods and associated functions",
+    ),
+    (
+        "calc_area_rect2.text",
+        "Methods are functions defined in an `impl` block, which receive the instance as `self`.\n\n\
+         Associated functions like `Rect::square` do not take `self`, and are often used as constructors.",
+    ),
+    ("calc_area_rect2.area", "Area of rectangle using method: {}"),
+    ("calc_area_rect2.can_hold", "Can {} hold {}: {}"),
+    ("calc_area_rect2.square", "Area of square: {}"),
+    // enum_example
+    ("enum_example.description", "Enums with data"),
+    (
+        "enum_example.text",
+        "Enums define a type by listing its variants, and each variant can hold its own data.",
+    ),
+    ("enum_example.v4_fields", "IPv4 fields: {}.{}.{}.{}"),
+    ("enum_example.v4_address", "IPv4 address field: {}"),
+    ("enum_example.v6_address", "IPv6 address: {}"),
+    ("enum_example.v6_address_field", "IPv6 address field: {}"),
+    // option_example
+    ("option_example.description", "A hand written Option<T>"),
+    (
+        "option_example.text",
+        "The Option enum handles optional values: it is either `Some(T)` or `None`.\n\n\
+         This avoids null pointer errors, because the missing case has to be handled before using the value.",
+    ),
+    ("option_example.some_value", "Some value: {}"),
+    ("option_example.no_value", "No value"),
+    // value_in_cents
+    (
+        "value_in_cents.description",
+        "Matching on every Coin variant",
+    ),
+    (
+        "value_in_cents.text",
+        "`match` has to cover every variant of an enum, and each arm can run code before producing its value.",
+    ),
+    ("value_in_cents.penny", "This is a penny"),
+    ("value_in_cents.nickel", "This is a nickel"),
+    ("value_in_cents.dime", "This is a dime"),
+    ("value_in_cents.quarter", "This is a quarter"),
+    // if_let_example
+    ("if_let_example.description", "if let for a single pattern"),
+    (
+        "if_let_example.text",
+        "When only one pattern matters, `if let` is shorter than a `match` with a catch-all arm.",
+    ),
+    ("if_let_example.some_value", "Some value"),
+    ("if_let_example.no_value", "No value"),
+    // operation_example
+    (
+        "operation_example.description",
+        "Returning Result from a method",
+    ),
+    (
+        "operation_example.text",
+        "Methods can return `Result<T, E>`, which is either `Ok(T)` or `Err(E)`, so the caller has to handle failures like dividing by zero. Here `E` is the `MathError` enum, which also reports results that do not fit in an `i32`.\n\nThe operands of an `Operation` are `Operation`s too, so it can hold a whole expression like `(1 + 2) * 3`. Such a recursive enum keeps its operands in a `Box`, otherwise it would contain itself and have no known size.",
+    ),
+    ("operation_example.result", "Result: {}"),
+    ("operation_example.error", "Error: {}"),
+    // vector_example
+    (
+        "vector_example.description",
+        "Creating, reading and mutating vectors",
+    ),
+    (
+        "vector_example.text",
+        "Vectors are a growable array type. Indexing with `[]` panics when out of bounds, while `get` returns an `Option`.\n\n\
+         Iterating over `&mut v` lets us change every element in place.",
+    ),
+    ("vector_example.value_at", "Value at index {}: {}"),
+    ("vector_example.no_value_at", "No value at index {}"),
+    ("vector_example.element", "Element: {}"),
+    ("vector_example.mutated", "Mutated: {}"),
+    // vector_example2
+    (
+        "vector_example2.description",
+        "Storing different types in a vector with an enum",
+    ),
+    (
+        "vector_example2.text",
+        "A vector holds one type only, but an enum can wrap values of different types in one vector.",
+    ),
+    ("vector_example2.integer", "Integer: {}"),
+    ("vector_example2.float", "Float: {}"),
+    ("vector_example2.text_cell", "Text: {}"),
+    // hashmap_example
+    (
+        "hashmap_example.description",
+        "Inserting and reading from a HashMap",
+    ),
+    (
+        "hashmap_example.text",
+        "A `HashMap` stores key-value pairs, using a hash function to map keys to values. `get` returns an `Option` with a reference to the value.",
+    ),
+    ("hashmap_example.score", "Score: {}"),
+    // hashmap_example2
+    (
+        "hashmap_example2.description",
+        "Overwriting values and the entry API",
+    ),
+    (
+        "hashmap_example2.text",
+        "Inserting a key twice overwrites its value, while `entry(..).or_insert(..)` only inserts when the key is missing.",
+    ),
+    // hashmap_example3
+    (
+        "hashmap_example3.description",
+        "Updating a value based on the old value",
+    ),
+    (
+        "hashmap_example3.text",
+        "`or_insert` returns a mutable reference to the value, so it can be updated based on the old value, for example to count words.",
+    ),
+];
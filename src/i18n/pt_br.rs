@@ -0,0 +1,400 @@
+//! Brazilian Portuguese messages.
+
+pub(super) const MESSAGES: &[(&str, &str)] = &[
+    // Topics
+    ("topic.basics", "Básico"),
+    ("topic.ownership", "Posse"),
+    ("topic.structs", "Structs"),
+    ("topic.enums", "Enums"),
+    ("topic.collections", "Coleções"),
+    // Exported book
+    ("book.title", "Aprenda Rust"),
+    ("book.code", "Código"),
+    ("book.output", "Saída"),
+    ("book.contents", "Sumário"),
+    // Calculator errors
+    ("math.divide_by_zero", "Não é possível dividir por zero"),
+    ("math.overflow", "O resultado é grande demais"),
+    ("math.underflow", "O resultado é pequeno demais"),
+    (
+        "math.negative_exponent",
+        "Não é possível elevar a uma potência negativa",
+    ),
+    (
+        "math.fractional_exponent",
+        "Não é possível elevar um racional a uma potência fracionária",
+    ),
+    ("math.undefined_variable", "`{}` não está definida"),
+    (
+        "math.not_an_integer",
+        "Operações bit a bit precisam de um tipo inteiro",
+    ),
+    (
+        "math.invalid_shift",
+        "Não é possível deslocar por um valor negativo ou pelo número de bits ou mais",
+    ),
+    (
+        "math.dimension_mismatch",
+        "Não é possível combinar `{}` com `{}`",
+    ),
+    (
+        "math.fractional_unit",
+        "Não é possível elevar `{}` a uma potência fracionária",
+    ),
+    ("math.unknown_function", "`{}` não é uma função"),
+    ("math.argument_count", "`{}` recebe {} operando(s)"),
+    ("math.shape_mismatch", "Não é possível combinar {} com {}"),
+    ("math.not_square", "Uma {} não é quadrada"),
+    (
+        "math.singular_matrix",
+        "A matriz é singular e não tem inversa",
+    ),
+    (
+        "math.divide_by_matrix",
+        "Não é possível dividir por uma matriz",
+    ),
+    ("math.matrix_shape", "matriz {}x{}"),
+    ("math.scalar_shape", "número"),
+    ("math.not_real", "`{}` não é um número real"),
+    ("math.not_a_number", "O resultado não é um número"),
+    // Terminal browser
+    (
+        "tui.help",
+        "↑↓ selecionar  Enter executar  / buscar  PgUp/PgDn rolar  q sair",
+    ),
+    (
+        "tui.search_help",
+        "digite para buscar  ↑↓ selecionar  Enter pronto  Esc limpar",
+    ),
+    ("tui.no_match", "Nenhuma lição encontrada"),
+    ("tui.run_hint", "Pressione Enter para executar esta lição"),
+    ("tui.too_small", "O terminal é pequeno demais"),
+    // vars
+    (
+        "vars.description",
+        "Mutabilidade, sombreamento e constantes",
+    ),
+    (
+        "vars.text",
+        "Em Rust as variáveis são imutáveis por padrão, declare uma variável com `mut` para permitir mudanças.\n\n\
+         O sombreamento (shadowing) permite reutilizar o nome de uma variável com um novo `let`, até com outro tipo.\n\n\
+         Constantes são imutáveis e precisam de uma anotação de tipo. Underscores deixam números grandes legíveis.",
+    ),
+    ("vars.has_value", "{} tem o valor {}"),
+    // data_types
+    (
+        "data_types.description",
+        "Tipos escalares e literais inteiros",
+    ),
+    (
+        "data_types.text",
+        "Inteiros são com sinal (`i8` a `i128`, `isize`) ou sem sinal (`u8` a `u128`, `usize`), e o padrão do Rust é `i32`.\n\n\
+         Literais inteiros podem ser escritos em decimal, hexadecimal (`0xff`), octal (`0o77`), binário (`0b1111_0000`) ou como byte (`b'A'`). Os outros tipos escalares são floats, `bool` e `char`.",
+    ),
+    // compound_data_types
+    ("compound_data_types.description", "Tuplas e arrays"),
+    (
+        "compound_data_types.text",
+        "Uma tupla agrupa valores de tipos diferentes. Seus valores são lidos por desestruturação ou por índice com `.0`, `.1`, ...\n\n\
+         Arrays têm tamanho fixo e todos os elementos têm o mesmo tipo. Para arrays dinâmicos, use `Vec<T>`.",
+    ),
+    ("compound_data_types.tuple", "Tupla: {}"),
+    (
+        "compound_data_types.destructured",
+        "Desestruturada: x: {}, y: {}, z: {}",
+    ),
+    (
+        "compound_data_types.indexed",
+        "Por índice: x: {}, y: {}, z: {}",
+    ),
+    ("compound_data_types.array", "Array: {}"),
+    // add
+    ("add.description", "Funções, instruções e expressões"),
+    (
+        "add.text",
+        "Funções são declaradas com a palavra-chave `fn` e nomeadas em snake_case.\n\n\
+         A última expressão de uma função, escrita sem ponto e vírgula, é o seu valor de retorno. A palavra-chave `return` retorna antes.",
+    ),
+    ("add.adding", "Somando {} e {}"),
+    // control_flow
+    ("control_flow.description", "if / else if / else e match"),
+    (
+        "control_flow.text",
+        "`if` / `else if` / `else` escolhe um caminho por uma condição `bool`. `if` é uma expressão, então também pode produzir um valor.\n\n\
+         `match` compara um valor com padrões, e `_` é um padrão que aceita qualquer coisa.",
+    ),
+    ("control_flow.less_than_10", "O número é menor que 10"),
+    (
+        "control_flow.between_10_and_20",
+        "O número está entre 10 e 20",
+    ),
+    ("control_flow.20_or_more", "O número é 20 ou mais"),
+    ("control_flow.numb_is", "numb é {}"),
+    ("control_flow.one", "Um"),
+    ("control_flow.two", "Dois"),
+    ("control_flow.something_else", "Outra coisa"),
+    // loops
+    ("loops.description", "loop, while e for"),
+    (
+        "loops.text",
+        "Rust tem três tipos de laço: `loop`, `while` e `for`.\n\n\
+         `loop` repete para sempre até um `break`, que pode retornar um valor. `for` percorre intervalos como `1..=5` e coleções.",
+    ),
+    ("loops.counter", "Contador: {}"),
+    ("loops.element", "Elemento: {}"),
+    // owner
+    ("owner.description", "Stack, heap e as regras de posse"),
+    (
+        "owner.text",
+        "Posse (ownership) é como o Rust gerencia a memória, garantindo segurança de memória sem coletor de lixo.\n\n\
+         Cada chamada de função empilha um frame na stack, que guarda valores de tamanho fixo. Dados de tamanho dinâmico, como o texto de uma `String`, ficam na heap, e a stack guarda apenas um ponteiro para eles.\n\n\
+         As regras de posse: cada valor tem uma variável que é sua dona, só pode existir um dono por vez, e quando o dono sai de escopo o valor é descartado.",
+    ),
+    // moved
+    ("moved.description", "Cópia, movimento e clone"),
+    (
+        "moved.text",
+        "Atribuir um `i32` o copia, porque ele vive inteiro na stack.\n\n\
+         Atribuir uma `String` a move: a nova variável vira a dona e a antiga não pode mais ser usada. `clone` faz uma cópia profunda, que é mais cara.",
+    ),
+    // ownership_functions
+    (
+        "ownership_functions.description",
+        "Passando e devolvendo a posse",
+    ),
+    (
+        "ownership_functions.text",
+        "Passar um valor para uma função o move ou copia, assim como uma atribuição. Depois de `takes_ownership(s)` a `String` não pode mais ser usada, já um `i32` pode.\n\n\
+         Retornar um valor entrega a sua posse para quem chamou.",
+    ),
+    (
+        "ownership_functions.gives_back",
+        "Tomando e devolvendo a posse: {}",
+    ),
+    // takes_ownership
+    (
+        "takes_ownership.description",
+        "Uma função que toma posse de uma String",
+    ),
+    (
+        "takes_ownership.text",
+        "Uma função que recebe um parâmetro `String` é dona dele, e a `String` é descartada quando a função retorna.",
+    ),
+    ("takes_ownership.taking", "Tomando posse: {}"),
+    // makes_copy
+    (
+        "makes_copy.description",
+        "Uma função que recebe um tipo Copy",
+    ),
+    (
+        "makes_copy.text",
+        "Tipos como `i32` implementam `Copy`, então a função recebe uma cópia e quem chamou continua com o seu valor.",
+    ),
+    ("makes_copy.making", "Fazendo uma cópia: {}"),
+    // borrow_example
+    (
+        "borrow_example.description",
+        "Referências imutáveis e mutáveis",
+    ),
+    (
+        "borrow_example.text",
+        "Referências emprestam um valor sem tomar posse dele.\n\n\
+         A qualquer momento pode existir uma referência mutável ou qualquer número de referências imutáveis, e referências precisam ser sempre válidas. Modificar um valor por uma referência imutável não compila.",
+    ),
+    ("borrow_example.borrowed", "Emprestado: {}"),
+    ("borrow_example.borrowed_again", "Emprestado de novo: {}"),
+    ("borrow_example.modified", "Modificado: {}"),
+    // slice_example
+    ("slice_example.description", "Fatiando um array"),
+    (
+        "slice_example.text",
+        "Uma fatia (slice) referencia uma sequência contígua de elementos de uma coleção, como `&a[1..4]`, sem ser dona deles.",
+    ),
+    ("slice_example.slice", "Fatia: {}"),
+    // string_example
+    ("string_example.description", "String e &str"),
+    (
+        "string_example.text",
+        "Textos em Rust são codificados em UTF-8. `String` é uma string que cresce e fica na heap, enquanto `&str` é uma fatia de string imutável.\n\n\
+         Uma `String` pode ser criada com `String::from` ou `to_string`, e alterada com métodos como `push_str` e `replace_range`.",
+    ),
+    ("string_example.after_replace", "{} depois do replace: {}"),
+    // concatenation_example
+    (
+        "concatenation_example.description",
+        "Juntando strings com +, format! e concat",
+    ),
+    (
+        "concatenation_example.text",
+        "Strings são juntadas com `+` (que toma posse da `String` da esquerda), com a macro `format!` ou com `concat` em um array de fatias.",
+    ),
+    // index_strings
+    (
+        "index_strings.description",
+        "Por que strings não podem ser indexadas por inteiro",
+    ),
+    (
+        "index_strings.text",
+        "Rust não permite indexar uma string com um inteiro, porque um caractere pode ocupar vários bytes.\n\n\
+         Fatiar por intervalo de bytes funciona, mas o intervalo precisa cair nos limites dos caracteres: cada 🍕 tem 4 bytes.",
+    ),
+    // struct_example
+    (
+        "struct_example.description",
+        "Definindo, alterando e copiando structs",
+    ),
+    (
+        "struct_example.text",
+        "Structs agrupam campos nomeados em um tipo. `#[derive(Debug)]` permite imprimi-las com `{:?}`.\n\n\
+         A instância inteira precisa ser `mut` para mudar um campo, e a sintaxe de atualização `..outra` preenche os campos restantes a partir de outra instância.",
+    ),
+    // build_user
+    (
+        "build_user.description",
+        "Atalho de inicialização de campos",
+    ),
+    (
+        "build_user.text",
+        "Quando um parâmetro tem o mesmo nome de um campo, o atalho de inicialização permite escrever `email` em vez de `email: email`.",
+    ),
+    // tuple_struct
+    ("tuple_struct.description", "Structs sem campos nomeados"),
+    (
+        "tuple_struct.text",
+        "Tuple structs são structs sem campos nomeados, seus valores são lidos como os de uma tupla.",
+    ),
+    // calc_area_rect
+    (
+        "calc_area_rect.description",
+        "Passando uma struct por referência",
+    ),
+    (
+        "calc_area_rect.text",
+        "Passar uma struct por referência permite que uma função a leia sem tomar posse dela.",
+    ),
+    ("calc_area_rect.area", "Área do retângulo: {}"),
+    // calc_area_rect2
+    (
+        "calc_area_rect2.description",
+        "Métodos e funções associadas",
+    ),
+    (
+        "calc_area_rect2.text",
+        "Métodos são funções definidas em um bloco `impl`, que recebem a instância como `self`.\n\n\
+         Funções associadas como `Rect::square` não recebem `self`, e são muito usadas como construtores.",
+    ),
+    (
+        "calc_area_rect2.area",
+        "Área do retângulo usando método: {}",
+    ),
+    ("calc_area_rect2.can_hold", "{} comporta {}: {}"),
+    ("calc_area_rect2.square", "Área do quadrado: {}"),
+    // enum_example
+    ("enum_example.description", "Enums com dados"),
+    (
+        "enum_example.text",
+        "Enums definem um tipo listando suas variantes, e cada variante pode guardar seus próprios dados.",
+    ),
+    ("enum_example.v4_fields", "Campos IPv4: {}.{}.{}.{}"),
+    ("enum_example.v4_address", "Campo de endereço IPv4: {}"),
+    ("enum_example.v6_address", "Endereço IPv6: {}"),
+    (
+        "enum_example.v6_address_field",
+        "Campo de endereço IPv6: {}",
+    ),
+    // option_example
+    ("option_example.description", "Um Option<T> escrito à mão"),
+    (
+        "option_example.text",
+        "O enum Option trata valores opcionais: ele é `Some(T)` ou `None`.\n\n\
+         Isso evita erros de ponteiro nulo, porque o caso sem valor precisa ser tratado antes de usar o valor.",
+    ),
+    ("option_example.some_value", "Algum valor: {}"),
+    ("option_example.no_value", "Nenhum valor"),
+    // value_in_cents
+    (
+        "value_in_cents.description",
+        "Fazendo match em todas as variantes de Coin",
+    ),
+    (
+        "value_in_cents.text",
+        "`match` precisa cobrir todas as variantes de um enum, e cada braço pode rodar código antes de produzir o seu valor.",
+    ),
+    ("value_in_cents.penny", "Isto é um penny"),
+    ("value_in_cents.nickel", "Isto é um nickel"),
+    ("value_in_cents.dime", "Isto é um dime"),
+    ("value_in_cents.quarter", "Isto é um quarter"),
+    // if_let_example
+    ("if_let_example.description", "if let para um único padrão"),
+    (
+        "if_let_example.text",
+        "Quando só um padrão importa, `if let` é mais curto que um `match` com um braço que aceita qualquer coisa.",
+    ),
+    ("if_let_example.some_value", "Algum valor"),
+    ("if_let_example.no_value", "Nenhum valor"),
+    // operation_example
+    (
+        "operation_example.description",
+        "Retornando Result de um método",
+    ),
+    (
+        "operation_example.text",
+        "Métodos podem retornar `Result<T, E>`, que é `Ok(T)` ou `Err(E)`, então quem chama precisa tratar falhas como a divisão por zero. Aqui `E` é o enum `MathError`, que também indica resultados que não cabem em um `i32`.\n\nOs operandos de uma `Operation` também são `Operation`s, então ela pode guardar uma expressão inteira como `(1 + 2) * 3`. Um enum recursivo assim guarda seus operandos em uma `Box`, senão ele conteria a si mesmo e não teria um tamanho conhecido.",
+    ),
+    ("operation_example.result", "Resultado: {}"),
+    ("operation_example.error", "Erro: {}"),
+    // vector_example
+    (
+        "vector_example.description",
+        "Criando, lendo e alterando vetores",
+    ),
+    (
+        "vector_example.text",
+        "Vetores são um tipo de array que cresce. Indexar com `[]` entra em pânico fora dos limites, enquanto `get` retorna um `Option`.\n\n\
+         Percorrer `&mut v` permite alterar cada elemento no lugar.",
+    ),
+    ("vector_example.value_at", "Valor no índice {}: {}"),
+    ("vector_example.no_value_at", "Nenhum valor no índice {}"),
+    ("vector_example.element", "Elemento: {}"),
+    ("vector_example.mutated", "Alterado: {}"),
+    // vector_example2
+    (
+        "vector_example2.description",
+        "Guardando tipos diferentes em um vetor com um enum",
+    ),
+    (
+        "vector_example2.text",
+        "Um vetor guarda um único tipo, mas um enum pode embrulhar valores de tipos diferentes em um só vetor.",
+    ),
+    ("vector_example2.integer", "Inteiro: {}"),
+    ("vector_example2.float", "Float: {}"),
+    ("vector_example2.text_cell", "Texto: {}"),
+    // hashmap_example
+    (
+        "hashmap_example.description",
+        "Inserindo e lendo de um HashMap",
+    ),
+    (
+        "hashmap_example.text",
+        "Um `HashMap` guarda pares de chave e valor, usando uma função de hash para mapear chaves a valores. `get` retorna um `Option` com uma referência ao valor.",
+    ),
+    ("hashmap_example.score", "Pontuação: {}"),
+    // hashmap_example2
+    (
+        "hashmap_example2.description",
+        "Sobrescrevendo valores e a API entry",
+    ),
+    (
+        "hashmap_example2.text",
+        "Inserir uma chave duas vezes sobrescreve o seu valor, enquanto `entry(..).or_insert(..)` só insere quando a chave não existe.",
+    ),
+    // hashmap_example3
+    (
+        "hashmap_example3.description",
+        "Atualizando um valor a partir do valor antigo",
+    ),
+    (
+        "hashmap_example3.text",
+        "`or_insert` retorna uma referência mutável ao valor, então ele pode ser atualizado a partir do valor antigo, por exemplo para contar palavras.",
+    ),
+];
@@ -0,0 +1,342 @@
+//! The lesson registry.
+//!
+//! Every example is registered here with the topic it belongs to,
+//! the order of [`LESSONS`] is the order used by `run --all`.
+
+use std::io::{self, Write};
+
+use crate::enums::Coin;
+use crate::structs::{Rect, User};
+use crate::{basics, collections, enums, i18n, ownership, structs};
+
+/// The chapter a lesson belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topic {
+    /// Variables, types, functions and control flow.
+    Basics,
+    /// Ownership, borrowing and slices.
+    Ownership,
+    /// Structs and methods.
+    Structs,
+    /// Enums and pattern matching.
+    Enums,
+    /// Strings, vectors and hash maps.
+    Collections,
+}
+
+impl Topic {
+    /// Every topic, in course order.
+    pub const ALL: [Topic; 5] = [
+        Topic::Basics,
+        Topic::Ownership,
+        Topic::Structs,
+        Topic::Enums,
+        Topic::Collections,
+    ];
+
+    /// The lowercase name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Topic::Basics => "basics",
+            Topic::Ownership => "ownership",
+            Topic::Structs => "structs",
+            Topic::Enums => "enums",
+            Topic::Collections => "collections",
+        }
+    }
+
+    /// Names of all topics, in course order.
+    pub fn names() -> Vec<&'static str> {
+        Topic::ALL.iter().map(|t| t.name()).collect()
+    }
+
+    /// Looks a topic up by its [`name`](Topic::name).
+    pub fn from_name(name: &str) -> Option<Topic> {
+        Topic::ALL.into_iter().find(|t| t.name() == name)
+    }
+
+    /// The source code of the topic module, which holds its lessons.
+    pub fn source(self) -> &'static str {
+        match self {
+            Topic::Basics => include_str!("basics.rs"),
+            Topic::Ownership => include_str!("ownership.rs"),
+            Topic::Structs => include_str!("structs.rs"),
+            Topic::Enums => include_str!("enums.rs"),
+            Topic::Collections => include_str!("collections.rs"),
+        }
+    }
+}
+
+/// A runnable example.
+///
+/// Lessons write to `out` instead of using `println!`, so the caller decides
+/// where the output goes (stdout for the CLI, a `Vec<u8>` in the tests).
+pub struct Lesson {
+    /// Unique name, the same as the example function.
+    pub name: &'static str,
+    /// Chapter the lesson belongs to.
+    pub topic: Topic,
+    /// Runs the example, writing everything it prints to the given writer.
+    pub run: fn(&mut dyn Write) -> io::Result<()>,
+}
+
+impl Lesson {
+    /// One line summary, in the current locale.
+    pub fn description(&self) -> &'static str {
+        i18n::get(&format!("{}.description", self.name)).unwrap_or("")
+    }
+
+    /// What the lesson teaches in the current locale, paragraphs separated by a blank line.
+    pub fn text(&self) -> &'static str {
+        i18n::get(&format!("{}.text", self.name)).unwrap_or("")
+    }
+
+    /// The paragraphs of [`text`](Lesson::text).
+    pub fn paragraphs(&self) -> Vec<&'static str> {
+        self.text().split("\n\n").collect()
+    }
+
+    /// The source of the lesson function, with the comments right above it.
+    pub fn source(&self) -> &'static str {
+        excerpt(self.topic.source(), self.name).unwrap_or("")
+    }
+
+    /// Runs the lesson and returns everything it printed.
+    pub fn output(&self) -> io::Result<String> {
+        let mut buf: Vec<u8> = Vec::new();
+        (self.run)(&mut buf)?;
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+}
+
+// Finds `pub fn <name>(` in `source` and returns it up to its closing brace,
+// together with the comment and attribute lines right above it
+fn excerpt(source: &'static str, name: &str) -> Option<&'static str> {
+    let signature: String = format!("pub fn {}(", name);
+    let mut offset: usize = 0;
+    let mut start: Option<usize> = None;
+    let mut prefix_start: usize = 0;
+
+    for line in source.split_inclusive('\n') {
+        let trimmed: &str = line.trim_start();
+        match start {
+            None if line.starts_with(&signature) => start = Some(prefix_start),
+            None if trimmed.starts_with("//") || trimmed.starts_with("#[") => {}
+            None => prefix_start = offset + line.len(),
+            Some(from) if line.trim_end() == "}" => {
+                return Some(&source[from..offset + line.len()]);
+            }
+            Some(_) => {}
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// All lessons, in course order.
+pub const LESSONS: &[Lesson] = &[
+    Lesson {
+        name: "vars",
+        topic: Topic::Basics,
+        run: basics::vars,
+    },
+    Lesson {
+        name: "data_types",
+        topic: Topic::Basics,
+        run: basics::data_types,
+    },
+    Lesson {
+        name: "compound_data_types",
+        topic: Topic::Basics,
+        run: basics::compound_data_types,
+    },
+    Lesson {
+        name: "add",
+        topic: Topic::Basics,
+        run: |out| {
+            basics::add(out, 1, 2)?;
+            Ok(())
+        },
+    },
+    Lesson {
+        name: "control_flow",
+        topic: Topic::Basics,
+        run: basics::control_flow,
+    },
+    Lesson {
+        name: "loops",
+        topic: Topic::Basics,
+        run: basics::loops,
+    },
+    Lesson {
+        name: "owner",
+        topic: Topic::Ownership,
+        run: ownership::owner,
+    },
+    Lesson {
+        name: "moved",
+        topic: Topic::Ownership,
+        run: ownership::moved,
+    },
+    Lesson {
+        name: "ownership_functions",
+        topic: Topic::Ownership,
+        run: ownership::ownership_functions,
+    },
+    Lesson {
+        name: "takes_ownership",
+        topic: Topic::Ownership,
+        run: |out| ownership::takes_ownership(out, String::from("Hello")),
+    },
+    Lesson {
+        name: "makes_copy",
+        topic: Topic::Ownership,
+        run: |out| ownership::makes_copy(out, 5),
+    },
+    Lesson {
+        name: "borrow_example",
+        topic: Topic::Ownership,
+        run: ownership::borrow_example,
+    },
+    Lesson {
+        name: "slice_example",
+        topic: Topic::Ownership,
+        run: ownership::slice_example,
+    },
+    Lesson {
+        name: "string_example",
+        topic: Topic::Collections,
+        run: collections::string_example,
+    },
+    Lesson {
+        name: "concatenation_example",
+        topic: Topic::Collections,
+        run: collections::concatenation_example,
+    },
+    Lesson {
+        name: "index_strings",
+        topic: Topic::Collections,
+        run: collections::index_strings,
+    },
+    Lesson {
+        name: "struct_example",
+        topic: Topic::Structs,
+        run: structs::struct_example,
+    },
+    Lesson {
+        name: "build_user",
+        topic: Topic::Structs,
+        run: |out| {
+            let user: User = structs::build_user(
+                String::from("johndoe@example.com"),
+                String::from("John Doe"),
+            );
+            writeln!(out, "{:?}", user)
+        },
+    },
+    Lesson {
+        name: "tuple_struct",
+        topic: Topic::Structs,
+        run: structs::tuple_struct,
+    },
+    Lesson {
+        name: "calc_area_rect",
+        topic: Topic::Structs,
+        run: |out| {
+            structs::calc_area_rect(
+                out,
+                &Rect {
+                    width: 30,
+                    height: 50,
+                },
+            )?;
+            Ok(())
+        },
+    },
+    Lesson {
+        name: "calc_area_rect2",
+        topic: Topic::Structs,
+        run: |out| {
+            structs::calc_area_rect2(
+                out,
+                &Rect {
+                    width: 30,
+                    height: 50,
+                },
+            )?;
+            Ok(())
+        },
+    },
+    Lesson {
+        name: "enum_example",
+        topic: Topic::Enums,
+        run: enums::enum_example,
+    },
+    Lesson {
+        name: "option_example",
+        topic: Topic::Enums,
+        run: enums::option_example,
+    },
+    Lesson {
+        name: "value_in_cents",
+        topic: Topic::Enums,
+        run: |out| {
+            enums::value_in_cents(out, Coin::Dime)?;
+            enums::value_in_cents(out, Coin::Quarter)?;
+            enums::value_in_cents(out, Coin::Nickel)?;
+            enums::value_in_cents(out, Coin::Penny)?;
+            Ok(())
+        },
+    },
+    Lesson {
+        name: "if_let_example",
+        topic: Topic::Enums,
+        run: enums::if_let_example,
+    },
+    Lesson {
+        name: "operation_example",
+        topic: Topic::Enums,
+        run: enums::operation_example,
+    },
+    Lesson {
+        name: "vector_example",
+        topic: Topic::Collections,
+        run: collections::vector_example,
+    },
+    Lesson {
+        name: "vector_example2",
+        topic: Topic::Collections,
+        run: collections::vector_example2,
+    },
+    Lesson {
+        name: "hashmap_example",
+        topic: Topic::Collections,
+        run: collections::hashmap_example,
+    },
+    Lesson {
+        name: "hashmap_example2",
+        topic: Topic::Collections,
+        run: collections::hashmap_example2,
+    },
+    Lesson {
+        name: "hashmap_example3",
+        topic: Topic::Collections,
+        run: collections::hashmap_example3,
+    },
+];
+
+/// Finds a lesson by name.
+pub fn find(name: &str) -> Option<&'static Lesson> {
+    LESSONS.iter().find(|l| l.name == name)
+}
+
+/// Resolves a lesson name, a topic name or `--all` to the matching lessons.
+pub fn select(selector: &str) -> Vec<&'static Lesson> {
+    if selector == "--all" {
+        return LESSONS.iter().collect();
+    }
+    if let Some(topic) = Topic::from_name(selector) {
+        return LESSONS.iter().filter(|l| l.topic == topic).collect();
+    }
+    find(selector).into_iter().collect()
+}
@@ -0,0 +1,22 @@
+//! Small, self-contained examples written while learning Rust.
+//!
+//! Each topic module holds the examples of one chapter, and [`lessons`]
+//! registers them so they can be listed and run by name or by topic.
+//! Every example writes its output to a caller-supplied [`std::io::Write`].
+
+#![warn(missing_docs)]
+
+pub mod basics;
+pub mod calc;
+pub mod collections;
+pub mod enums;
+pub mod exercises;
+pub mod export;
+pub mod i18n;
+pub mod lessons;
+pub mod ownership;
+pub mod progress;
+pub mod quiz;
+pub mod store;
+pub mod structs;
+pub mod tui;
@@ -1,742 +1,455 @@
-use std::collections::HashMap;
-
-fn main() {
-    vars();
-    data_types();
-    compound_data_types();
-    add(1, 2);
-    control_flow();
-    loops();
-    owner();
-    moved();
-    ownership_functions();
-    takes_ownership(String::from("Hello"));
-    makes_copy(5);
-    borrow_example();
-    slice_example();
-    string_example();
-    concatenation_example();
-    index_strings();
-    struct_example();
-    build_user(
-        String::from("johndoe@example.com"),
-        String::from("John Doe"),
-    );
-    tuple_struct();
-    calc_area_rect(&Rect {
-        width: 30,
-        height: 50,
-    });
-    calc_area_rect2(&Rect {
-        width: 30,
-        height: 50,
-    });
-    enum_example();
-    option_example();
-    value_in_cents(Coin::Dime);
-    value_in_cents(Coin::Quarter);
-    value_in_cents(Coin::Nickel);
-    value_in_cents(Coin::Penny);
-    if_let_example();
-    // result_example();
-    operation_example();
-    vector_example();
-    vector_example2();
-    hashmap_example();
-    hashmap_example2();
-    hashmap_example3();
-}
-
-fn vars() {
-    // Variables are immutable by default in Rust
-    // Declare a mutable(mut) variable to allow changes
-    let mut x: i32 = 5;
-    println!("x has the value {}", x);
-    x = 6;
-    println!("x has the value {}", x);
-
-    // Shadowing allows you to reuse a variable name
-    let y: i32 = 10;
-    println!("y has the value {}", y);
-    let y: &str = "ten";
-    println!("y has the value {}", y);
-
-    // Constants are immutable and must have a type annotation
-    const EXAMPLE: i32 = 100_000_000; // Using underscores for readability
-    println!("EXAMPLE has the value {}", EXAMPLE);
+use std::env;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use learn_rust::calc::diagnostic::Style;
+use learn_rust::calc::number::Base;
+use learn_rust::calc::{self, Calculator, Mode, Notation, NumberType};
+use learn_rust::exercises;
+use learn_rust::export::{self, Format};
+use learn_rust::i18n::{self, Lang};
+use learn_rust::lessons::{self, LESSONS, Lesson, Topic};
+use learn_rust::progress::{Progress, Status, TopicSummary};
+use learn_rust::quiz::{self, Outcome, Question, Score};
+use learn_rust::{store, tui};
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    // --lang works with every command and wins over the environment
+    let lang: Option<Lang> = match take_option(&mut args, "--lang") {
+        Ok(Some(code)) => match Lang::from_code(&code) {
+            Some(lang) => Some(lang),
+            None => {
+                eprintln!(
+                    "unknown language `{}`, expected one of: {}",
+                    code,
+                    Lang::ALL.map(Lang::code).join(", ")
+                );
+                return ExitCode::from(2);
+            }
+        },
+        Ok(None) => Lang::from_env(),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+    i18n::set_lang(lang.unwrap_or(Lang::En));
+
+    match args.first().map(String::as_str) {
+        Some("list") => list_command(&args[1..]),
+        Some("run") => run_command(&args[1..]),
+        Some("quiz") => quiz_command(&args[1..]),
+        Some("progress") => progress_command(&args[1..]),
+        Some("watch") => watch_command(&args[1..]),
+        Some("export") => export_command(&args[1..]),
+        Some("browse") => browse_command(),
+        Some("calc") => calc_command(&args[1..]),
+        Some("help") | Some("--help") | Some("-h") | None => {
+            print_usage();
+            ExitCode::SUCCESS
+        }
+        Some(other) => {
+            eprintln!("unknown command `{}`", other);
+            print_usage();
+            ExitCode::from(2)
+        }
+    }
 }
 
-fn data_types() {
-    // Integers
-    // Rust defaults to i32
-    // Signed integers can be positive or negative, Unsigned integers can only be positive
-    let a: i32 = 42; // i8, i16, i32, i64, i128, isize(architecture dependent 32/64 bit)
-    let a1: i32 = 98_222; // Decimal
-    let a2: i32 = 0xff; // Hexadecimal
-    let a3: i32 = 0o77; // Octal
-    let a4: i32 = 0b1111_0000; // Binary
-    let a5: u8 = b'A'; // Byte (ASCII value of 'A')
-    let b: f64 = 3.14; // Floating point
-
-    let c: bool = true; // Boolean
-    let d: char = 'z'; // Character
-    let e: &str = "Hello"; // String slice
-
+fn print_usage() {
+    println!("Usage:");
+    println!("  learn_rust list [topic]            List the lessons, optionally of one topic");
+    println!("  learn_rust run <name|topic>...     Run lessons by name or by topic");
+    println!("  learn_rust run --all               Run every lesson in order");
+    println!("  learn_rust quiz [name|topic|--all] Answer questions about the lessons");
+    println!("  learn_rust progress [topic]        Show completed lessons per topic");
+    println!("  learn_rust watch [--dir DIR]       Work through the exercises, rechecking on save");
+    println!("  learn_rust export --format markdown|html [--out DIR]");
+    println!("                                     Write the course as a book (default: book/)");
+    println!("  learn_rust browse                  Browse and run the lessons full-screen");
     println!(
-        "a: {}, a1: {}, a2: {}, a3: {}, a4: {}, a5: {}, b: {}, c: {}, d: {}, e: {}",
-        a, a1, a2, a3, a4, a5, b, c, d, e
+        "  learn_rust calc [options]          Evaluate operations like `20 / 0`, one per line"
     );
-}
-
-fn compound_data_types() {
-    // Tuples
-    let tuple: (i32, f64, &str) = (42, 3.14, "Hello");
-    println!("Tuple: {:?}", tuple);
-    // We can get values from a tuple by two ways
-    // 1. Destructuring
-    let (x, y, z) = tuple;
-    println!("Destructured: x: {}, y: {}, z: {}", x, y, z);
-
-    // 2. Indexing
-    println!("Indexed: x: {}, y: {}, z: {}", tuple.0, tuple.1, tuple.2);
-
-    // Arrays
-    // Arrays are fixed-size and must have the same type for all elements
-    // For Dynamic arrays, use Vec<T>
-    let array: [i32; 3] = [1, 2, 3];
-    println!("Array: {:?}", array);
-}
-
-// Functions
-// In Rust, functions are declared using the `fn` keyword
-// Are in snake_case
-fn add(x: i32, y: i32) -> i32 {
-    println!("Adding {} and {}", x, y); // This is a statement, because this dont return anything
-    // return x + y; // We can return the value of a function using the `return` keyword
-    x + y // Or just return the value
-}
-
-// Control flow
-fn control_flow() {
-    let number: i32 = 5;
-
-    if number < 10 {
-        println!("Number is less than 10");
-    } else if number < 20 {
-        println!("Number is between 10 and 20");
-    } else {
-        println!("Number is 20 or more");
+    println!();
+    println!("run, quiz and progress accept --user NAME (default: $LEARN_RUST_USER or $USER)");
+    println!("Every command accepts --lang en|pt-BR (default: $LEARN_RUST_LANG or $LANG)");
+    println!();
+    // The same settings are commands at the calc prompt, like `mode wrapping`
+    println!("calc options:");
+    let options: [(&str, Vec<&str>, &str); 5] = [
+        (
+            "--type",
+            NumberType::ALL.map(NumberType::name).to_vec(),
+            "i32",
+        ),
+        ("--mode", Mode::ALL.map(Mode::name).to_vec(), "checked"),
+        ("--base", Base::ALL.map(Base::name).to_vec(), "dec"),
+        (
+            "--notation",
+            Notation::ALL.map(Notation::name).to_vec(),
+            "infix",
+        ),
+        (
+            "--errors",
+            Style::ALL.map(Style::name).to_vec(),
+            "color on a terminal, plain otherwise",
+        ),
+    ];
+    for (flag, names, default) in options {
+        println!("  {:<10} {} (default: {})", flag, names.join("|"), default);
     }
+    println!();
+    println!("Topics: {}", Topic::names().join(", "));
+}
+
+// `list` prints one line per lesson: name, topic and a short description
+fn list_command(args: &[String]) -> ExitCode {
+    let topic: Option<Topic> = match args.first() {
+        Some(name) => match Topic::from_name(name) {
+            Some(topic) => Some(topic),
+            None => {
+                eprintln!("unknown topic `{}`", name);
+                return ExitCode::from(2);
+            }
+        },
+        None => None,
+    };
 
-    let condition: bool = true;
-    let numb: i32 = if condition { 5 } else { 10 }; // if expressions can return values
-    println!("numb is {}", numb);
-
-    // Match statement
-    match number {
-        1 => println!("One"),
-        2 => println!("Two"),
-        _ => println!("Something else"), // _ is a catch-all pattern
+    for lesson in LESSONS {
+        if topic.is_none_or(|t| t == lesson.topic) {
+            println!(
+                "{:<24} {:<12} {}",
+                lesson.name,
+                lesson.topic.name(),
+                lesson.description()
+            );
+        }
     }
+    ExitCode::SUCCESS
 }
 
-// Loops
-// Rust has three types of loops: loop, while, and for
-fn loops() {
-    // loop {
-    //     println!("Looping forever");
-    // }
-
-    // let mut counter = 0;
-    // let result = loop {
-    //     counter += 1;
-    //     if counter > 5 {
-    //         break counter;
-    //     }
-    //     println!("Counter: {}", counter);
-    // };
-
-    // println!("Result: {}", result);
-
-    // while counter != 5 {
-    //     counter += 1;
-    //     println!("Counter: {}", counter);
-    // }
-
-    for i in 1..=5 {
-        println!("Counter: {}", i);
-    }
-
-    let a: [i32; 5] = [10, 20, 30, 40, 50];
-    for element in a.iter() {
-        println!("Element: {}", element);
+// `run` accepts lesson names and topic names, or `--all`
+fn run_command(args: &[String]) -> ExitCode {
+    let mut args: Vec<String> = args.to_vec();
+    let user: String = match take_option(&mut args, "--user") {
+        Ok(flag) => store::user_name(flag.as_deref()),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
+    if args.is_empty() {
+        eprintln!("`run` expects a lesson name, a topic or --all");
+        return ExitCode::from(2);
     }
-}
-
-// Memory Safety
 
-// Ownership -> ownership is a way to manage memory in Rust
-// It allows us to have memory safety without a garbage collector
-// During runtime, rust makes certain decisions based on if our memory is stored on the stack or the heap
-// During runtime, we have access to both the stack and the heap
-// Stack: Fixed size(calculated at compile time), fast access, LIFO (Last In First Out)
-// Heap: Dynamic size, slower access, FIFO (First In First Out)
-fn owner() {
-    // A gets executed first, then we push A onto the stack
-    fn a() {
-        // A inicializes x and y
-        // X is a reference to a string literal, wich is stored in our binary
-        let x: &str = "Hello";
-        println!("A: {}", x);
-        // Y is a signed 32 bit integer which is a fixed size, so can be stored directly on the stack frame
-        let y: u32 = 22;
-        println!("A: {}", y);
-        // A executes B, then we push B onto the stack
-        b();
+    let mut selected: Vec<&Lesson> = Vec::new();
+    for arg in &args {
+        let found: Vec<&Lesson> = lessons::select(arg);
+        if found.is_empty() {
+            eprintln!("no lesson or topic named `{}`, try `learn_rust list`", arg);
+            return ExitCode::from(2);
+        }
+        selected.extend(found);
     }
-    a();
 
-    // Another stack frame is created for B
-    fn b() {
-        // B has its on variable x
-        // X is a String, which is a dynamic size, so can be stored on the heap
-        // The heap passes back a pointer, this is what we actually store on the stack
-        let x: String = String::from("World");
-        // When B finishes executing, it pops off the stack
-        println!("B: {}", x);
+    let mut out = io::stdout().lock();
+    for lesson in &selected {
+        let result: io::Result<()> =
+            writeln!(out, "=== {} ({}) ===", lesson.name, lesson.topic.name())
+                .and_then(|_| (lesson.run)(&mut out));
+        if let Err(e) = result {
+            eprintln!("failed to write lesson output: {}", e);
+            return ExitCode::FAILURE;
+        }
     }
-    b();
-
-    // ----- OWNERSHIP RULES -----
-    // 1. Each value has a variable that`s called its owner
-    // 2. There can only be one owner at a time
-    // 3. When the owner goes out of scope, the value is dropped
 
-    {
-        // s is not valid here, its not yet declared
-        // s ⬇️ is a reference to a string literal, wich is stored in our binary
-        let s0: &str = "Hello"; // s is valid from this point forward
-        println!("s0: {}", s0);
-
-        // s ⬇️ is a String, which is a dynamic size, so can be stored on the heap
-        let s: String = String::from("World"); // s is valid from this point forward
-        // do stuff with s
-        println!("s: {}", s);
-    } // this scope is now over, s is no longer valid
-}
-
-fn moved() {
-    let x: i32 = 5;
-    let y: i32 = x; // Copying the value of x into y
-    println!("y: {}", y);
-
-    // Rust default to moving the value, cloning its more expensive
-    let s1: String = String::from("Hello");
-    let s2: String = s1; // Move (not shallow copy)
-    let s3: String = s2.clone(); // Cloning the value of s2 into s3
-    println!("s2: {}, s3: {}", s2, s3);
-}
-
-// Ownership & Functions
-fn ownership_functions() {
-    let s: String = String::from("Hello");
-    takes_ownership(s);
-    // println!("s: {}", s); // This would cause an error, because s is no longer valid after being moved
-
-    let x: i32 = 5;
-    makes_copy(x);
-    println!("x: {}", x); // This is valid, because i32 is a Copy type
-
-    let s2: String = gives_ownership(); // s2 takes ownership of the returned value
-    println!("s2: {}", s2); // s2 is valid here, because it owns the value
-
-    let s3: String = takes_and_gives_back_ownership(s2); // s3 takes ownership of the returned value
-    println!("s3: {}", s3); // s3 is valid here, because it owns the value
-}
-
-fn takes_ownership(some_string: String) {
-    println!("Taking ownership: {}", some_string);
-}
-
-fn makes_copy(some_integer: i32) {
-    println!("Making a copy: {}", some_integer);
-}
-
-fn gives_ownership() -> String {
-    let some_string: String = String::from("Hello");
-    some_string
-}
-
-fn takes_and_gives_back_ownership(some_string: String) -> String {
-    println!("Taking and giving back ownership: {}", some_string);
-    some_string // 
-}
-
-// References
-// References are a way to borrow values without taking ownership
-// Rules of References
-// 1. At any given time, you can have either (but not both) a mutable reference or any number of immutable references.
-// 2. References must always be valid.
-fn borrow_example() {
-    let mut s: String = String::from("Hello");
-    let r: &String = &s; // Borrowing a reference to s
-    // r.push_str(" World"); // This would cause an error, because r is a reference and cannot be modified
-    println!("Borrowed: {}", r);
-
-    let r2: &String = &s; // Multiple immutable references are allowed
-    println!("Borrowed again: {}", r2);
-
-    let r3: &mut String = &mut s; // Mutable references are allowed, but if we have a immutable reference being used after, will cause an error
-    r3.push_str(" World"); // We can modify the value through the mutable reference
-    println!("Modified: {}", r3);
-}
-
-// Slices
-// Slices are a way to reference a contiguous sequence of elements in a collection
-fn slice_example() {
-    let a: [i32; 5] = [1, 2, 3, 4, 5];
-
-    let slice: &[i32] = &a[1..4]; // Slicing the array to get a reference to a part of it
-    println!("Slice: {:?}", slice);
-}
-
-// Strings
-// Texts in Rust are encoded in UTF-8
-// Rust has two different types of strings: String and &str
-// String is a growable, heap-allocated data structure, while &str is a string slice
-// String is mutable, while &str is immutable
-fn string_example() {
-    let s1: &str = "Olá mundo! 🍕";
-    let s2: String = String::from("Olá mundo! 🍕");
-    println!("s2: {}", s2);
-    let s3: String = "Olá mundo! 🍕".to_string();
-    println!("s3: {}", s3);
-    let s4: &str = &s1[..];
-    println!("s4: {}", s4);
-
-    let mut foo: String = String::from("Foo");
-    foo.push_str("bar");
-    println!("foo: {}", foo);
-
-    foo.replace_range(.., "Baz");
-    println!("foo after replace: {}", foo);
-}
-
-// Concatenation
-fn concatenation_example() {
-    let s1: String = String::from("Olá");
-    let s2: String = String::from("mundo!");
-    let s3: String = s1 + " " + &s2; // Concatenating strings
-    println!("s3: {}", s3);
-
-    // format macro
-    let s4: String = String::from("Hello");
-    let s5: String = String::from("world");
-    let s6: String = format!("{} {}", s4, s5);
-    println!("s6: {}", s6);
-
-    let s7: String = ["Hello", " ", "world"].concat();
-    println!("s7: {}", s7);
-}
-
-// Indexing Strings
-fn index_strings() {
-    let s1: &str = "🍕🍕🍕🍕🍕";
-    // let s2 = s1[0]; // This would cause an error, because Rust does not allow indexing into strings directly using a integer
-    // let s2: &str = &s1[0..2]; // This is how you can get a slice of the string, however this would cause a panic because the 🍕 has a length of 4 bytes
-    let s2: &str = &s1[0..4];
-    println!("s2: {}", s2);
+    update_progress(&user, |progress, now| {
+        for lesson in &selected {
+            progress.record_run(lesson.name, now);
+        }
+    });
+    ExitCode::SUCCESS
 }
 
-// Structs
-// Structs are like Types/Interface in typescript
-// Derive allow us to automatically implement traits for our struct
-#[derive(Debug)]
-struct User {
-    username: String,
-    email: String,
-    sign_in_count: u64,
-    active: bool,
+// Loads the learner's progress, applies `update` and saves it again
+// A failure only warns, the lesson or quiz itself already happened
+fn update_progress(user: &str, update: impl FnOnce(&mut Progress, u64)) {
+    let path = store::user_file("progress", user);
+    let result: io::Result<()> = Progress::load(&path).and_then(|mut progress| {
+        update(&mut progress, store::unix_time());
+        progress.save(&path)
+    });
+    if let Err(e) = result {
+        eprintln!("warning: could not update {}: {}", path.display(), e);
+    }
 }
 
-fn struct_example() {
-    let mut user: User = User {
-        username: String::from("John Doe"),
-        email: String::from("john.doe@example.com"),
-        sign_in_count: 1,
-        active: true,
+// Removes `flag VALUE` from the arguments and returns VALUE
+fn take_option(args: &mut Vec<String>, flag: &str) -> Result<Option<String>, String> {
+    let Some(i) = args.iter().position(|a| a == flag) else {
+        return Ok(None);
     };
-
-    let name: String = user.username;
-    println!("name: {}", name);
-    println!("sign_in_count: {}", user.sign_in_count);
-    println!("active: {}", user.active);
-    user.email = String::from("john.doe.mutable@example.com");
-
-    let user2: User = build_user(
-        String::from("jane.doe@example.com"),
-        String::from("Jane Doe"),
-    );
-    println!("user2: {:?}", user2);
-    println!("sign_in_count: {}", user2.sign_in_count);
-    println!("active: {}", user2.active);
-
-    let user3: User = User { ..user2 };
-    println!("user3: {:?}", user3);
-    println!("sign_in_count: {}", user3.sign_in_count);
-    println!("active: {}", user3.active);
-}
-
-fn build_user(email: String, username: String) -> User {
-    User {
-        email,
-        username,
-        sign_in_count: 0,
-        active: true,
+    if i + 1 >= args.len() {
+        return Err(format!("{} expects a value", flag));
     }
-}
-
-// Tuple Structs are similar to structs, but they are similar to tuples in that they do not have named fields
-fn tuple_struct() {
-    struct Color(i32, i32, i32);
-    let black: Color = Color(0, 0, 0);
-
-    let r: i32 = black.0;
-    let g: i32 = black.1;
-    let b: i32 = black.2;
-    println!("r: {}, g: {}, b: {}", r, g, b);
-}
-
-struct Rect {
-    width: u32,
-    height: u32,
-}
-
-fn calc_area_rect(dimenstions: &Rect) -> u32 {
-    let rect: Rect = Rect {
-        width: dimenstions.width,
-        height: dimenstions.height,
+    let value: String = args.remove(i + 1);
+    args.remove(i);
+    Ok(Some(value))
+}
+
+// `quiz` asks the questions of the selected lessons and records the score
+fn quiz_command(args: &[String]) -> ExitCode {
+    let mut args: Vec<String> = args.to_vec();
+    let user: String = match take_option(&mut args, "--user") {
+        Ok(flag) => store::user_name(flag.as_deref()),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
     };
+    let selector: &str = args.first().map(String::as_str).unwrap_or("--all");
 
-    println!("Area of rectangle: {}", rect.width * rect.height);
-    rect.width * rect.height
-}
-
-// Methods
-// Methods are functions that are associated with a struct
-// They are defined inside the struct and have access to all the fields of the struct
-impl Rect {
-    fn area(&self) -> u32 {
-        self.width * self.height
+    let questions: Vec<&Question> = quiz::questions_for(selector);
+    if questions.is_empty() {
+        eprintln!("no questions for `{}`, try `learn_rust list`", selector);
+        return ExitCode::from(2);
     }
 
-    fn can_hold(&self, other: &Rect) -> bool {
-        self.width >= other.width && self.height >= other.height
-    }
-}
+    let outcomes: Vec<Outcome> =
+        match quiz::ask(&questions, &mut io::stdin().lock(), &mut io::stdout()) {
+            Ok(outcomes) => outcomes,
+            Err(e) => {
+                eprintln!("quiz failed: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+    let score: Score = Score::of(&outcomes);
+
+    update_progress(&user, |progress, now| {
+        let mut asked: Vec<&str> = outcomes.iter().map(|o| o.lesson).collect();
+        asked.dedup();
+        for lesson in asked {
+            progress.record_quiz(lesson, Score::of_lesson(&outcomes, lesson), now);
+        }
+    });
 
-fn calc_area_rect2(dimenstions: &Rect) -> u32 {
-    let rect: Rect = Rect {
-        width: dimenstions.width,
-        height: dimenstions.height,
+    let path = store::user_file("quiz", &user);
+    if let Err(e) = quiz::save_result(&path, store::unix_time(), selector, score) {
+        eprintln!("could not save the result to {}: {}", path.display(), e);
+        return ExitCode::FAILURE;
+    }
+    println!("Result saved to {}", path.display());
+    ExitCode::SUCCESS
+}
+
+// `progress` summarises completion per topic, or lists the lessons of one topic
+fn progress_command(args: &[String]) -> ExitCode {
+    let mut args: Vec<String> = args.to_vec();
+    let user: String = match take_option(&mut args, "--user") {
+        Ok(flag) => store::user_name(flag.as_deref()),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
     };
-
-    let rect1: Rect = Rect {
-        width: 40,
-        height: 60,
+    let topic: Option<Topic> = match args.first() {
+        Some(name) => match Topic::from_name(name) {
+            Some(topic) => Some(topic),
+            None => {
+                eprintln!("unknown topic `{}`", name);
+                return ExitCode::from(2);
+            }
+        },
+        None => None,
     };
 
-    let rect2: Rect = Rect {
-        width: 20,
-        height: 30,
+    let path = store::user_file("progress", &user);
+    let progress: Progress = match Progress::load(&path) {
+        Ok(progress) => progress,
+        Err(e) => {
+            eprintln!("could not read {}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
     };
 
-    let rect3: Rect = Rect::square(10);
-
-    println!("Area of rectangle using method: {}", rect.area());
-    println!("Can rect hold rect1: {}", rect.can_hold(&rect1));
-    println!("Can rect hold rect2: {}", rect.can_hold(&rect2));
-    println!("Can rect1 hold rect2: {}", rect1.can_hold(&rect2));
-    println!("Area of square: {}", rect3.area());
-    rect.area()
-}
-
-// Methods, use the `self` keyword to access fields of the struct, while associated functions do not use `self`
-// Associated functions are functions that are associated with a struct
-// They are defined inside the `impl` block and do not have access to the fields of the struct
-impl Rect {
-    fn square(size: u32) -> Rect {
-        Rect {
-            width: size,
-            height: size,
+    println!("Progress of {}", user);
+    let Some(topic) = topic else {
+        let summaries: Vec<TopicSummary> = progress.summary();
+        for summary in &summaries {
+            println!(
+                "{:<12} {:>2}/{:<2} completed  {:>2} started",
+                summary.topic.name(),
+                summary.completed,
+                summary.total,
+                summary.started
+            );
         }
-    }
-}
-
-// Enums
-#[derive(Debug)]
-enum IpAddrKind {
-    V4(u8, u8, u8, u8),
-    V6(String),
-}
-
-#[derive(Debug)]
-struct IpAddr {
-    kind: IpAddrKind,
-    address: String,
-}
-
-fn enum_example() {
-    let ip_v4: IpAddr = IpAddr {
-        kind: IpAddrKind::V4(192, 168, 1, 1),
-        address: String::from("192.168.1.1"),
-    };
-    let ip_v6: IpAddr = IpAddr {
-        kind: IpAddrKind::V6("::1".to_string()),
-        address: String::from("::1"),
+        let completed: usize = summaries.iter().map(|s| s.completed).sum();
+        println!(
+            "{:<12} {:>2}/{:<2} completed",
+            "total",
+            completed,
+            LESSONS.len()
+        );
+        return ExitCode::SUCCESS;
     };
 
-    println!("ip_v4: {:?}", ip_v4);
-    println!("ip_v6: {:?}", ip_v6);
-
-    // Use the fields of IpAddrKind::V4 to avoid dead_code warning
-    if let IpAddrKind::V4(a, b, c, d) = ip_v4.kind {
-        println!("IPv4 fields: {}.{}.{}.{}", a, b, c, d);
-        println!("IPv4 address field: {}", ip_v4.address);
-    }
-
-    if let IpAddrKind::V6(addr) = ip_v6.kind {
-        println!("IPv6 address: {}", addr);
-        println!("IPv6 address field: {}", ip_v6.address);
-    }
-}
-
-// Option Enum
-// The Option enum is a powerful way to handle optional values in Rust
-// It can either be Some(T) or None, where T is the type of the value
-// This allows us to avoid null pointer exceptions and handle cases where a value may not be present
-#[derive(Debug)]
-enum Option<T> {
-    Some(T),
-    None,
-}
-
-fn option_example() {
-    let some_value: Option<i32> = Option::Some(5);
-    let no_value: Option<i32> = Option::None;
-
-    match some_value {
-        Option::Some(v) => println!("Some value: {}", v),
-        Option::None => println!("No value"),
+    for lesson in LESSONS.iter().filter(|l| l.topic == topic) {
+        match progress.get(lesson.name) {
+            Some(entry) => {
+                let quiz: String = match entry.quiz {
+                    Some(score) => format!("quiz {}/{}", score.correct, score.total),
+                    None => String::from("no quiz"),
+                };
+                let mark: &str = match entry.status {
+                    Status::Completed => "x",
+                    Status::Started => "~",
+                };
+                println!(
+                    "[{}] {:<24} {} attempts, {}",
+                    mark, lesson.name, entry.attempts, quiz
+                );
+            }
+            None => println!("[ ] {}", lesson.name),
+        }
     }
+    ExitCode::SUCCESS
+}
+
+// `watch` checks the exercises one by one, rechecking when a file is saved
+fn watch_command(args: &[String]) -> ExitCode {
+    let mut args: Vec<String> = args.to_vec();
+    let dir: PathBuf = match take_option(&mut args, "--dir") {
+        Ok(Some(dir)) => PathBuf::from(dir),
+        Ok(None) => exercises::exercise_dir(),
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    };
 
-    match no_value {
-        Option::Some(v) => println!("Some value: {}", v),
-        Option::None => println!("No value"),
+    match exercises::watch(&dir, Duration::from_millis(500), &mut io::stdout()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("watch failed: {}", e);
+            ExitCode::FAILURE
+        }
     }
 }
 
-// Matching on Option
-
-enum Coin {
-    Penny,
-    Nickel,
-    Dime,
-    Quarter,
-}
-
-fn value_in_cents(coin: Coin) -> u8 {
-    match coin {
-        Coin::Penny => {
-            println!("This is a penny");
-            1
+// `export` writes the course book in the chosen format
+fn export_command(args: &[String]) -> ExitCode {
+    let mut args: Vec<String> = args.to_vec();
+    let (format, dir) = match (
+        take_option(&mut args, "--format"),
+        take_option(&mut args, "--out"),
+    ) {
+        (Ok(format), Ok(dir)) => (format, dir),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
         }
-        Coin::Nickel => {
-            println!("This is a nickel");
-            5
+    };
+    let format: Format = match format.as_deref().map(Format::from_name) {
+        Some(Some(format)) => format,
+        _ => {
+            eprintln!("`export` expects --format markdown or --format html");
+            return ExitCode::from(2);
         }
-        Coin::Dime => {
-            println!("This is a dime");
-            10
+    };
+    let dir: PathBuf = PathBuf::from(dir.unwrap_or_else(|| String::from("book")));
+
+    match export::export(&dir, format) {
+        Ok(pages) => {
+            println!("Wrote {} pages to {}", pages.len(), dir.display());
+            ExitCode::SUCCESS
         }
-        Coin::Quarter => {
-            println!("This is a quarter");
-            25
+        Err(e) => {
+            eprintln!("export failed: {}", e);
+            ExitCode::FAILURE
         }
     }
 }
 
-// IF LET
-fn if_let_example() {
-    let some_value = Some(5);
-
-    // Match is more expensive than if let, so if we only care about one case, we can use if let
-    match some_value {
-        Some(5) => println!("Some value"),
-        _ => println!("No value"),
-    }
-
-    if let Some(5) = some_value {
-        println!("Some value");
-    }
-}
-
-// Result
-// enum Result<T, E> {
-//     OK(T),
-//     Err(E),
-// }
-
-// fn result_example() {
-//     let result: Result<i32, String> = Result::OK(5);
-//     let error: Result<i32, String> = Result::Err(String::from("An error occurred"));
-
-//     match result {
-//         Result::OK(v) => println!("Result is OK: {}", v),
-//         Result::Err(e) => println!("Error: {}", e),
-//     }
-
-//     match error {
-//         Result::OK(v) => println!("Result is OK: {}", v),
-//         Result::Err(e) => println!("Error: {}", e),
-//     }
-// }
-
-enum Operation {
-    Add(i32, i32),
-    Mul(i32, i32),
-    Sub { first: i32, second: i32 },
-    Div { divident: i32, divisor: i32 },
-}
-
-impl Operation {
-    fn execute(self) -> Result<i32, String> {
-        match self {
-            Self::Add(a, b) => Ok(a + b),
-            Self::Mul(a, b) => Ok(a * b),
-            Self::Sub { first, second } => Ok(first - second),
-            Self::Div { divident, divisor } => {
-                if divisor == 0 {
-                    Err(String::from("Can not divide by zero"))
-                } else {
-                    Ok(divident / divisor)
-                }
-            }
+// `browse` opens the full-screen lesson browser
+fn browse_command() -> ExitCode {
+    match tui::run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("can not open the lesson browser: {}", e);
+            ExitCode::FAILURE
         }
     }
 }
 
-fn operation_example() {
-    let user_input = Operation::Div {
-        divident: 20,
-        divisor: 0,
-    };
-    match user_input.execute() {
-        Ok(res) => println!("Result: {}", res),
-        Err(e) => println!("Error: {}", e),
+// Takes `flag` and its value out of `args`, read with `from_name`. `what`
+// names the kind of value for the error, like `unknown mode `fast``
+fn parse_option<T>(
+    args: &mut Vec<String>,
+    flag: &str,
+    what: &str,
+    from_name: fn(&str) -> Option<T>,
+) -> Result<Option<T>, String> {
+    match take_option(args, flag)? {
+        Some(name) => match from_name(&name) {
+            Some(value) => Ok(Some(value)),
+            None => Err(format!("unknown {} `{}`", what, name)),
+        },
+        None => Ok(None),
     }
 }
 
-// Vectors
-// Vectors are a growable array type
-// They are similar to arrays, but they can grow dynamically
-fn vector_example() {
-    let a: [i32; 5] = [1, 2, 3, 4, 5];
-
-    let mut v: Vec<i32> = Vec::new();
-    v.push(10);
-    v.push(20);
-    v.push(30);
-
-    let v2: Vec<i32> = vec![10, 20, 30];
-
-    // This works because we are using the dereference operator to get a reference to the first element of the vector
-    // Would cause an runtime error if we tried to access an index that is out of bounds of the vector
-    let first: &i32 = &v2[0];
-    println!("first: {}", first);
-
-    match v.get(20) {
-        Some(value) => println!("Value at index 20: {}", value),
-        None => println!("No value at index 20"),
+// Sets up the calculator from the options of `calc`, which are all the
+// arguments it takes
+fn calc_options(args: &[String], calculator: &mut Calculator) -> Result<(), String> {
+    let mut args: Vec<String> = args.to_vec();
+    if let Some(mode) = parse_option(&mut args, "--mode", "mode", Mode::from_name)? {
+        calculator.mode = mode;
     }
-
-    for i in &a {
-        println!("Element: {}", i);
+    if let Some(number) = parse_option(&mut args, "--type", "number type", NumberType::from_name)? {
+        calculator.set_number(number);
     }
-
-    for i in &mut v {
-        *i += 10;
-        println!("Mutated: {}", i);
+    if let Some(base) = parse_option(&mut args, "--base", "base", Base::from_name)? {
+        calculator.base = base;
     }
-}
-
-// Vectors with different types
-// We can use enums to store different types in a vector
-fn vector_example2() {
-    enum SpreadsheetCell {
-        Int(i32),
-        Float(f64),
-        Text(String),
-    }
-
-    let row: Vec<SpreadsheetCell> = vec![
-        SpreadsheetCell::Int(1),
-        SpreadsheetCell::Text(String::from("Hello")),
-        SpreadsheetCell::Float(3.14),
-    ];
-
-    match &row[1] {
-        SpreadsheetCell::Int(i) => println!("Integer: {}", i),
-        SpreadsheetCell::Float(f) => println!("Float: {}", f),
-        SpreadsheetCell::Text(t) => println!("Text: {}", t),
+    if let Some(notation) = parse_option(&mut args, "--notation", "notation", Notation::from_name)?
+    {
+        calculator.notation = notation;
     }
-}
-
-// Hashmaps
-// allow you to store key-value pairs, they can be in any type, uses a hash function to map keys to values
-fn hashmap_example() {
-    let blue: String = String::from("Blue");
-    let red: String = String::from("Red");
-
-    let mut scores: HashMap<String, i32> = HashMap::new();
-
-    scores.insert(blue, 10);
-    scores.insert(red, 5);
-
-    let team_name: String = String::from("Blue");
-    let score = scores.get(&team_name);
-
-    println!("Score: {}", score.unwrap());
-
-    for (key, value) in &scores {
-        println!("{}: {}", key, value);
+    // Full reports by default, coloured when a person reads them on a terminal
+    calculator.errors = match parse_option(&mut args, "--errors", "error style", Style::from_name)?
+    {
+        Some(style) => style,
+        None if io::stdout().is_terminal() => Style::Color,
+        None => Style::Plain,
+    };
+    match args.first() {
+        Some(arg) => Err(format!(
+            "`calc` does not take `{}`, see `learn_rust help`",
+            arg
+        )),
+        None => Ok(()),
     }
 }
 
-// Update a value in a hashmap
-fn hashmap_example2() {
-    let mut scores: HashMap<String, i32> = HashMap::new();
-
-    scores.insert(String::from("Blue"), 10);
-    scores.insert(String::from("Blue"), 20); // This will overwrite the previous value for "Blue"
-
-    scores.entry(String::from("Red")).or_insert(5);
-    scores.entry(String::from("Red")).or_insert(40); // This will not overwrite the previous value for "Red"
-
-    for (key, value) in &scores {
-        println!("{}: {}", key, value);
+// `calc` evaluates one operation per line of stdin
+fn calc_command(args: &[String]) -> ExitCode {
+    let mut calculator: Calculator = Calculator::new();
+    if let Err(e) = calc_options(args, &mut calculator) {
+        eprintln!("{}", e);
+        return ExitCode::from(2);
     }
-}
-
-// Update a value in a hashmap based on the old value
-fn hashmap_example3() {
-    let text = "hello world wonderful world";
-    let mut map: HashMap<String, i32> = HashMap::new();
 
-    for word in text.split_whitespace() {
-        let count = map.entry(word.to_string()).or_insert(0);
-        *count += 1; // Increment the count for the word
+    match calc::repl(&mut calculator, &mut io::stdin().lock(), &mut io::stdout()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("failed to run the calculator: {}", e);
+            ExitCode::FAILURE
+        }
     }
-
-    println!("{:?}", map)
 }
@@ -0,0 +1,161 @@
+//! Ownership, moves, borrowing and slices.
+
+use std::io::{self, Write};
+
+use crate::tr;
+
+// Memory Safety
+
+// Ownership -> ownership is a way to manage memory in Rust
+// It allows us to have memory safety without a garbage collector
+// During runtime, rust makes certain decisions based on if our memory is stored on the stack or the heap
+// During runtime, we have access to both the stack and the heap
+// Stack: Fixed size(calculated at compile time), fast access, LIFO (Last In First Out)
+// Heap: Dynamic size, slower access, FIFO (First In First Out)
+/// Stack frames, the heap and the ownership rules.
+pub fn owner(out: &mut dyn Write) -> io::Result<()> {
+    // A gets executed first, then we push A onto the stack
+    fn a(out: &mut dyn Write) -> io::Result<()> {
+        // A inicializes x and y
+        // X is a reference to a string literal, wich is stored in our binary
+        let x: &str = "Hello";
+        writeln!(out, "A: {}", x)?;
+        // Y is a signed 32 bit integer which is a fixed size, so can be stored directly on the stack frame
+        let y: u32 = 22;
+        writeln!(out, "A: {}", y)?;
+        // A executes B, then we push B onto the stack
+        b(out)?;
+        Ok(())
+    }
+    a(out)?;
+
+    // Another stack frame is created for B
+    fn b(out: &mut dyn Write) -> io::Result<()> {
+        // B has its on variable x
+        // X is a String, which is a dynamic size, so can be stored on the heap
+        // The heap passes back a pointer, this is what we actually store on the stack
+        let x: String = String::from("World");
+        // When B finishes executing, it pops off the stack
+        writeln!(out, "B: {}", x)?;
+        Ok(())
+    }
+    b(out)?;
+
+    // ----- OWNERSHIP RULES -----
+    // 1. Each value has a variable that`s called its owner
+    // 2. There can only be one owner at a time
+    // 3. When the owner goes out of scope, the value is dropped
+
+    {
+        // s is not valid here, its not yet declared
+        // s ⬇️ is a reference to a string literal, wich is stored in our binary
+        let s0: &str = "Hello"; // s is valid from this point forward
+        writeln!(out, "s0: {}", s0)?;
+
+        // s ⬇️ is a String, which is a dynamic size, so can be stored on the heap
+        let s: String = String::from("World"); // s is valid from this point forward
+        // do stuff with s
+        writeln!(out, "s: {}", s)?;
+    } // this scope is now over, s is no longer valid
+    Ok(())
+}
+
+/// Copying an `i32` versus moving and cloning a `String`.
+pub fn moved(out: &mut dyn Write) -> io::Result<()> {
+    let x: i32 = 5;
+    let y: i32 = x; // Copying the value of x into y
+    writeln!(out, "y: {}", y)?;
+
+    // Rust default to moving the value, cloning its more expensive
+    let s1: String = String::from("Hello");
+    let s2: String = s1; // Move (not shallow copy)
+    let s3: String = s2.clone(); // Cloning the value of s2 into s3
+    writeln!(out, "s2: {}, s3: {}", s2, s3)?;
+    Ok(())
+}
+
+// Ownership & Functions
+/// Passing ownership into and out of functions.
+pub fn ownership_functions(out: &mut dyn Write) -> io::Result<()> {
+    let s: String = String::from("Hello");
+    takes_ownership(out, s)?;
+    // writeln!(out, "s: {}", s)?; // This would cause an error, because s is no longer valid after being moved (E0382, tests/compile_fail/use_after_move.rs)
+
+    let x: i32 = 5;
+    makes_copy(out, x)?;
+    writeln!(out, "x: {}", x)?; // This is valid, because i32 is a Copy type
+
+    let s2: String = gives_ownership(); // s2 takes ownership of the returned value
+    writeln!(out, "s2: {}", s2)?; // s2 is valid here, because it owns the value
+
+    let s3: String = takes_and_gives_back_ownership(out, s2)?; // s3 takes ownership of the returned value
+    writeln!(out, "s3: {}", s3)?; // s3 is valid here, because it owns the value
+    Ok(())
+}
+
+/// Takes ownership of `some_string`, which is dropped at the end.
+pub fn takes_ownership(out: &mut dyn Write, some_string: String) -> io::Result<()> {
+    writeln!(out, "{}", tr!("takes_ownership.taking", some_string))?;
+    Ok(())
+}
+
+/// Receives a copy of `some_integer`.
+pub fn makes_copy(out: &mut dyn Write, some_integer: i32) -> io::Result<()> {
+    writeln!(out, "{}", tr!("makes_copy.making", some_integer))?;
+    Ok(())
+}
+
+/// Moves a newly created `String` out to the caller.
+pub fn gives_ownership() -> String {
+    let some_string: String = String::from("Hello");
+    some_string
+}
+
+/// Takes ownership of `some_string` and gives it back.
+pub fn takes_and_gives_back_ownership(
+    out: &mut dyn Write,
+    some_string: String,
+) -> io::Result<String> {
+    writeln!(
+        out,
+        "{}",
+        tr!("ownership_functions.gives_back", some_string)
+    )?;
+    Ok(some_string)
+}
+
+// References
+// References are a way to borrow values without taking ownership
+// Rules of References
+// 1. At any given time, you can have either (but not both) a mutable reference or any number of immutable references.
+// 2. References must always be valid.
+/// Immutable and mutable references.
+pub fn borrow_example(out: &mut dyn Write) -> io::Result<()> {
+    let mut s: String = String::from("Hello");
+    let r: &String = &s; // Borrowing a reference to s
+    // r.push_str(" World"); // This would cause an error, because r is a reference and cannot be modified (E0596, tests/compile_fail/mutate_through_shared_ref.rs)
+    writeln!(out, "{}", tr!("borrow_example.borrowed", r))?;
+
+    let r2: &String = &s; // Multiple immutable references are allowed
+    writeln!(out, "{}", tr!("borrow_example.borrowed_again", r2))?;
+
+    let r3: &mut String = &mut s; // Mutable references are allowed, but if we have a immutable reference being used after, will cause an error
+    r3.push_str(" World"); // We can modify the value through the mutable reference
+    writeln!(out, "{}", tr!("borrow_example.modified", r3))?;
+    Ok(())
+}
+
+// Slices
+// Slices are a way to reference a contiguous sequence of elements in a collection
+/// Slicing part of an array.
+pub fn slice_example(out: &mut dyn Write) -> io::Result<()> {
+    let a: [i32; 5] = [1, 2, 3, 4, 5];
+
+    let slice: &[i32] = &a[1..4]; // Slicing the array to get a reference to a part of it
+    writeln!(
+        out,
+        "{}",
+        tr!("slice_example.slice", format!("{:?}", slice))
+    )?;
+    Ok(())
+}
@@ -0,0 +1,233 @@
+//! Per-learner progress through the lessons.
+//!
+//! Progress is kept in a tab separated file, one line per lesson:
+//! `lesson<TAB>status<TAB>attempts<TAB>ran<TAB>first_seen<TAB>last_seen<TAB>correct<TAB>total`,
+//! where the quiz columns are `-` until the lesson's quiz was taken.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::lessons::{LESSONS, Topic};
+use crate::quiz::Score;
+
+/// How far a learner got with a lesson.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    /// The lesson was run or quizzed, but its quiz is not fully right yet.
+    Started,
+    /// The lesson was run and every question of its last quiz was right.
+    Completed,
+}
+
+impl Status {
+    /// The name written to the progress file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Started => "started",
+            Status::Completed => "completed",
+        }
+    }
+
+    /// Parses a [`name`](Status::name).
+    pub fn from_name(name: &str) -> Option<Status> {
+        match name {
+            "started" => Some(Status::Started),
+            "completed" => Some(Status::Completed),
+            _ => None,
+        }
+    }
+}
+
+/// What is recorded for one lesson.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LessonProgress {
+    /// Current status.
+    pub status: Status,
+    /// Times the lesson was run or quizzed.
+    pub attempts: u32,
+    /// Whether the lesson was ever run.
+    pub ran: bool,
+    /// Unix time of the first attempt.
+    pub first_seen: u64,
+    /// Unix time of the last attempt.
+    pub last_seen: u64,
+    /// Score of the last quiz about this lesson.
+    pub quiz: Option<Score>,
+}
+
+/// Progress of one learner, keyed by lesson name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// The recorded lessons.
+    pub lessons: BTreeMap<String, LessonProgress>,
+}
+
+/// Completion counts for one topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopicSummary {
+    /// The topic.
+    pub topic: Topic,
+    /// Lessons of the topic that were completed.
+    pub completed: usize,
+    /// Lessons of the topic that were started but not completed.
+    pub started: usize,
+    /// All lessons of the topic.
+    pub total: usize,
+}
+
+impl Progress {
+    /// Reads the progress file at `path`, an empty progress when it does not exist yet.
+    pub fn load(path: &Path) -> io::Result<Progress> {
+        let text: String = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Progress::default()),
+            Err(e) => return Err(e),
+        };
+
+        let mut progress: Progress = Progress::default();
+        for (n, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (name, entry) = parse_line(line).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}:{}: malformed progress line", path.display(), n + 1),
+                )
+            })?;
+            progress.lessons.insert(name, entry);
+        }
+        Ok(progress)
+    }
+
+    /// Writes the progress file at `path`, creating its folder when needed.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::File::create(path)?;
+        for (name, entry) in &self.lessons {
+            let (correct, total) = match entry.quiz {
+                Some(score) => (score.correct.to_string(), score.total.to_string()),
+                None => (String::from("-"), String::from("-")),
+            };
+            writeln!(
+                file,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                name,
+                entry.status.name(),
+                entry.attempts,
+                entry.ran,
+                entry.first_seen,
+                entry.last_seen,
+                correct,
+                total
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Progress of `lesson`, if it was ever attempted.
+    pub fn get(&self, lesson: &str) -> Option<&LessonProgress> {
+        self.lessons.get(lesson)
+    }
+
+    /// Records that `lesson` was run at time `now`.
+    pub fn record_run(&mut self, lesson: &str, now: u64) {
+        let entry: &mut LessonProgress = self.attempt(lesson, now);
+        entry.ran = true;
+        entry.status = status_of(entry);
+    }
+
+    /// Records the quiz `score` of `lesson` at time `now`.
+    pub fn record_quiz(&mut self, lesson: &str, score: Score, now: u64) {
+        let entry: &mut LessonProgress = self.attempt(lesson, now);
+        entry.quiz = Some(score);
+        entry.status = status_of(entry);
+    }
+
+    fn attempt(&mut self, lesson: &str, now: u64) -> &mut LessonProgress {
+        let entry: &mut LessonProgress =
+            self.lessons
+                .entry(lesson.to_string())
+                .or_insert(LessonProgress {
+                    status: Status::Started,
+                    attempts: 0,
+                    ran: false,
+                    first_seen: now,
+                    last_seen: now,
+                    quiz: None,
+                });
+        entry.attempts += 1;
+        entry.last_seen = now;
+        entry
+    }
+
+    /// Completion per topic, counting only the lessons in the registry.
+    pub fn summary(&self) -> Vec<TopicSummary> {
+        Topic::ALL
+            .iter()
+            .map(|&topic| {
+                let mut summary: TopicSummary = TopicSummary {
+                    topic,
+                    completed: 0,
+                    started: 0,
+                    total: 0,
+                };
+                for lesson in LESSONS.iter().filter(|l| l.topic == topic) {
+                    summary.total += 1;
+                    match self.get(lesson.name).map(|p| p.status) {
+                        Some(Status::Completed) => summary.completed += 1,
+                        Some(Status::Started) => summary.started += 1,
+                        None => {}
+                    }
+                }
+                summary
+            })
+            .collect()
+    }
+}
+
+fn status_of(entry: &LessonProgress) -> Status {
+    match entry.quiz {
+        Some(score) if entry.ran && score.total > 0 && score.correct == score.total => {
+            Status::Completed
+        }
+        _ => Status::Started,
+    }
+}
+
+fn parse_line(line: &str) -> Option<(String, LessonProgress)> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [
+        name,
+        status,
+        attempts,
+        ran,
+        first_seen,
+        last_seen,
+        correct,
+        total,
+    ] = fields[..]
+    else {
+        return None;
+    };
+    let quiz: Option<Score> = match (correct, total) {
+        ("-", "-") => None,
+        _ => Some(Score {
+            correct: correct.parse().ok()?,
+            total: total.parse().ok()?,
+        }),
+    };
+    let entry: LessonProgress = LessonProgress {
+        status: Status::from_name(status)?,
+        attempts: attempts.parse().ok()?,
+        ran: ran.parse().ok()?,
+        first_seen: first_seen.parse().ok()?,
+        last_seen: last_seen.parse().ok()?,
+        quiz,
+    };
+    Some((name.to_string(), entry))
+}
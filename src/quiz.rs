@@ -0,0 +1,403 @@
+//! Quiz questions attached to the lessons.
+//!
+//! Questions are either multiple choice or "predict the output", where the
+//! expected answer is not written down but taken from running the lesson.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::lessons::{self, Lesson};
+
+/// How a question is answered.
+pub enum Answer {
+    /// Pick one of `options` by its number, `correct` is the 0-based index.
+    Choice {
+        /// The options shown to the learner.
+        options: &'static [&'static str],
+        /// Index of the right option.
+        correct: usize,
+    },
+    /// Type line `line` (0-based) of what the lesson prints.
+    OutputLine {
+        /// Which printed line is asked for.
+        line: usize,
+    },
+}
+
+/// A question about one lesson.
+pub struct Question {
+    /// Name of the lesson the question belongs to.
+    pub lesson: &'static str,
+    /// The question text.
+    pub prompt: &'static str,
+    /// How it is answered.
+    pub answer: Answer,
+}
+
+impl Question {
+    /// The expected answer as the learner would type it.
+    pub fn expected(&self) -> io::Result<String> {
+        match &self.answer {
+            Answer::Choice { options, correct } => Ok(options[*correct].to_string()),
+            Answer::OutputLine { line } => {
+                let lesson: &Lesson = lessons::find(self.lesson).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::NotFound, "question for unknown lesson")
+                })?;
+                let output: String = lesson.output()?;
+                Ok(output.lines().nth(*line).unwrap_or("").to_string())
+            }
+        }
+    }
+
+    /// Whether `reply` is right, ignoring surrounding whitespace.
+    ///
+    /// Choices can be answered by number (1-based) or by the option text.
+    pub fn check(&self, reply: &str) -> io::Result<bool> {
+        let reply: &str = reply.trim();
+        if let Answer::Choice { correct, .. } = self.answer
+            && reply.parse::<usize>() == Ok(correct + 1)
+        {
+            return Ok(true);
+        }
+        Ok(reply == self.expected()?.trim())
+    }
+}
+
+/// Every question, in lesson order.
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        lesson: "vars",
+        prompt: "What is the second line `vars` prints?",
+        answer: Answer::OutputLine { line: 1 },
+    },
+    Question {
+        lesson: "vars",
+        prompt: "What lets `y` change from an i32 to a &str?",
+        answer: Answer::Choice {
+            options: &["mut", "Shadowing", "const", "A type cast"],
+            correct: 1,
+        },
+    },
+    Question {
+        lesson: "data_types",
+        prompt: "What is the value of the literal `0o77`?",
+        answer: Answer::Choice {
+            options: &["77", "63", "255", "7"],
+            correct: 1,
+        },
+    },
+    Question {
+        lesson: "compound_data_types",
+        prompt: "What is the last line `compound_data_types` prints?",
+        answer: Answer::OutputLine { line: 3 },
+    },
+    Question {
+        lesson: "add",
+        prompt: "Why does `x + y` have no semicolon at the end of `add`?",
+        answer: Answer::Choice {
+            options: &[
+                "It is a statement",
+                "It is the returned expression",
+                "Semicolons are optional",
+            ],
+            correct: 1,
+        },
+    },
+    Question {
+        lesson: "control_flow",
+        prompt: "What does the `match number` in `control_flow` print?",
+        answer: Answer::OutputLine { line: 2 },
+    },
+    Question {
+        lesson: "loops",
+        prompt: "How many `Counter:` lines does `for i in 1..=5` print?",
+        answer: Answer::Choice {
+            options: &["4", "5", "6"],
+            correct: 1,
+        },
+    },
+    Question {
+        lesson: "owner",
+        prompt: "Where is the text of `String::from(\"World\")` stored?",
+        answer: Answer::Choice {
+            options: &["On the stack", "On the heap", "In the binary"],
+            correct: 1,
+        },
+    },
+    Question {
+        lesson: "moved",
+        prompt: "What is the second line `moved` prints?",
+        answer: Answer::OutputLine { line: 1 },
+    },
+    Question {
+        lesson: "ownership_functions",
+        prompt: "Why can `x` still be printed after `makes_copy(x)`?",
+        answer: Answer::Choice {
+            options: &[
+                "i32 is a Copy type",
+                "x is borrowed",
+                "x is cloned by makes_copy",
+            ],
+            correct: 0,
+        },
+    },
+    Question {
+        lesson: "takes_ownership",
+        prompt: "What does `takes_ownership` print?",
+        answer: Answer::OutputLine { line: 0 },
+    },
+    Question {
+        lesson: "makes_copy",
+        prompt: "What does `makes_copy(5)` print?",
+        answer: Answer::OutputLine { line: 0 },
+    },
+    Question {
+        lesson: "borrow_example",
+        prompt: "How many mutable references to a value can exist at the same time?",
+        answer: Answer::Choice {
+            options: &["None", "One", "Any number"],
+            correct: 1,
+        },
+    },
+    Question {
+        lesson: "slice_example",
+        prompt: "What does `slice_example` print?",
+        answer: Answer::OutputLine { line: 0 },
+    },
+    Question {
+        lesson: "string_example",
+        prompt: "Which string type can grow?",
+        answer: Answer::Choice {
+            options: &["&str", "String", "Both"],
+            correct: 1,
+        },
+    },
+    Question {
+        lesson: "concatenation_example",
+        prompt: "What is the first line `concatenation_example` prints?",
+        answer: Answer::OutputLine { line: 0 },
+    },
+    Question {
+        lesson: "index_strings",
+        prompt: "How many bytes long is the 🍕 character?",
+        answer: Answer::Choice {
+            options: &["1", "2", "4"],
+            correct: 2,
+        },
+    },
+    Question {
+        lesson: "struct_example",
+        prompt: "What is the first line `struct_example` prints?",
+        answer: Answer::OutputLine { line: 0 },
+    },
+    Question {
+        lesson: "build_user",
+        prompt: "What `sign_in_count` does `build_user` give a new user?",
+        answer: Answer::Choice {
+            options: &["0", "1", "It is not set"],
+            correct: 0,
+        },
+    },
+    Question {
+        lesson: "tuple_struct",
+        prompt: "How is the first field of `Color(0, 0, 0)` read?",
+        answer: Answer::Choice {
+            options: &["black.r", "black[0]", "black.0"],
+            correct: 2,
+        },
+    },
+    Question {
+        lesson: "calc_area_rect",
+        prompt: "What does `calc_area_rect` print for a 30x50 rectangle?",
+        answer: Answer::OutputLine { line: 0 },
+    },
+    Question {
+        lesson: "calc_area_rect2",
+        prompt: "Is `rect1.can_hold(&rect2)` true or false?",
+        answer: Answer::Choice {
+            options: &["true", "false"],
+            correct: 0,
+        },
+    },
+    Question {
+        lesson: "calc_area_rect2",
+        prompt: "What is the last line `calc_area_rect2` prints?",
+        answer: Answer::OutputLine { line: 4 },
+    },
+    Question {
+        lesson: "enum_example",
+        prompt: "What is the fifth line `enum_example` prints?",
+        answer: Answer::OutputLine { line: 4 },
+    },
+    Question {
+        lesson: "option_example",
+        prompt: "What does matching `Option::None` print?",
+        answer: Answer::OutputLine { line: 1 },
+    },
+    Question {
+        lesson: "value_in_cents",
+        prompt: "What does `value_in_cents(Coin::Dime)` return?",
+        answer: Answer::Choice {
+            options: &["1", "5", "10", "25"],
+            correct: 2,
+        },
+    },
+    Question {
+        lesson: "if_let_example",
+        prompt: "When is `if let` preferred over `match`?",
+        answer: Answer::Choice {
+            options: &[
+                "When only one pattern matters",
+                "When every case must be handled",
+                "Never, they are the same",
+            ],
+            correct: 0,
+        },
+    },
+    Question {
+        lesson: "operation_example",
+        prompt: "What does `operation_example` print?",
+        answer: Answer::OutputLine { line: 0 },
+    },
+    Question {
+        lesson: "vector_example",
+        prompt: "What does `v.get(20)` return on a vector of three elements?",
+        answer: Answer::Choice {
+            options: &["It panics", "None", "Some(0)"],
+            correct: 1,
+        },
+    },
+    Question {
+        lesson: "vector_example2",
+        prompt: "What does `vector_example2` print?",
+        answer: Answer::OutputLine { line: 0 },
+    },
+    Question {
+        lesson: "hashmap_example",
+        prompt: "What is the first line `hashmap_example` prints?",
+        answer: Answer::OutputLine { line: 0 },
+    },
+    Question {
+        lesson: "hashmap_example2",
+        prompt: "What is the value of \"Red\" after the two `or_insert` calls?",
+        answer: Answer::Choice {
+            options: &["5", "40", "45"],
+            correct: 0,
+        },
+    },
+    Question {
+        lesson: "hashmap_example3",
+        prompt: "How many times is \"world\" counted?",
+        answer: Answer::Choice {
+            options: &["1", "2", "3"],
+            correct: 1,
+        },
+    },
+];
+
+/// The questions of the lessons matched by `selector` (see [`lessons::select`]).
+pub fn questions_for(selector: &str) -> Vec<&'static Question> {
+    let names: Vec<&str> = lessons::select(selector).iter().map(|l| l.name).collect();
+    QUESTIONS
+        .iter()
+        .filter(|q| names.contains(&q.lesson))
+        .collect()
+}
+
+/// How many questions were answered right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Score {
+    /// Right answers.
+    pub correct: usize,
+    /// Questions asked.
+    pub total: usize,
+}
+
+impl Score {
+    /// Adds up `outcomes`.
+    pub fn of(outcomes: &[Outcome]) -> Score {
+        Score {
+            correct: outcomes.iter().filter(|o| o.correct).count(),
+            total: outcomes.len(),
+        }
+    }
+
+    /// Adds up the outcomes of the questions about `lesson`.
+    pub fn of_lesson(outcomes: &[Outcome], lesson: &str) -> Score {
+        let mine: Vec<Outcome> = outcomes
+            .iter()
+            .filter(|o| o.lesson == lesson)
+            .copied()
+            .collect();
+        Score::of(&mine)
+    }
+}
+
+/// The result of one answered question.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// Lesson the question belongs to.
+    pub lesson: &'static str,
+    /// Whether the answer was right.
+    pub correct: bool,
+}
+
+/// Asks each question on `out`, reading one answer per line from `input`.
+///
+/// Stops early when `input` runs out, only returning the questions answered.
+pub fn ask(
+    questions: &[&Question],
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> io::Result<Vec<Outcome>> {
+    let mut outcomes: Vec<Outcome> = Vec::new();
+
+    for (i, question) in questions.iter().enumerate() {
+        writeln!(out, "Q{} [{}] {}", i + 1, question.lesson, question.prompt)?;
+        if let Answer::Choice { options, .. } = question.answer {
+            for (n, option) in options.iter().enumerate() {
+                writeln!(out, "  {}) {}", n + 1, option)?;
+            }
+        }
+        write!(out, "> ")?;
+        out.flush()?;
+
+        let mut reply: String = String::new();
+        if input.read_line(&mut reply)? == 0 {
+            writeln!(out)?;
+            break;
+        }
+
+        let correct: bool = question.check(&reply)?;
+        if correct {
+            writeln!(out, "Correct!")?;
+        } else {
+            writeln!(out, "Wrong, the answer is: {}", question.expected()?)?;
+        }
+        writeln!(out)?;
+        outcomes.push(Outcome {
+            lesson: question.lesson,
+            correct,
+        });
+    }
+
+    let score: Score = Score::of(&outcomes);
+    writeln!(out, "Score: {}/{}", score.correct, score.total)?;
+    Ok(outcomes)
+}
+
+/// Appends one quiz result to the results file at `path`.
+///
+/// Each line is `timestamp<TAB>selector<TAB>correct<TAB>total`.
+pub fn save_result(path: &Path, timestamp: u64, selector: &str, score: Score) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        file,
+        "{}\t{}\t{}\t{}",
+        timestamp, selector, score.correct, score.total
+    )
+}
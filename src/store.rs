@@ -0,0 +1,51 @@
+//! Where learner data (quiz results, progress) is kept on disk.
+//!
+//! Everything lives under one directory, `$LEARN_RUST_HOME` when set and
+//! `.learn_rust` in the current directory otherwise, with one file per user.
+
+use std::env;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The directory holding all learner data.
+pub fn data_dir() -> PathBuf {
+    match env::var_os("LEARN_RUST_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(".learn_rust"),
+    }
+}
+
+/// The learner name: `flag` when given, then `$LEARN_RUST_USER`, `$USER`, or `learner`.
+pub fn user_name(flag: Option<&str>) -> String {
+    if let Some(name) = flag {
+        return name.to_string();
+    }
+    env::var("LEARN_RUST_USER")
+        .or_else(|_| env::var("USER"))
+        .unwrap_or_else(|_| String::from("learner"))
+}
+
+/// The file of `user` inside the `kind` folder, e.g. `quiz/jane.tsv`.
+///
+/// Characters that are not safe in a file name are replaced with `_`.
+pub fn user_file(kind: &str, user: &str) -> PathBuf {
+    let safe: String = user
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    data_dir().join(kind).join(format!("{}.tsv", safe))
+}
+
+/// Seconds since the Unix epoch.
+pub fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,179 @@
+//! Structs, tuple structs, methods and associated functions.
+
+use std::io::{self, Write};
+
+use crate::tr;
+
+// Structs
+// Structs are like Types/Interface in typescript
+// Derive allow us to automatically implement traits for our struct
+/// A user account.
+#[derive(Debug)]
+pub struct User {
+    /// Display name.
+    pub username: String,
+    /// Contact email.
+    pub email: String,
+    /// How many times the user signed in.
+    pub sign_in_count: u64,
+    /// Whether the account is active.
+    pub active: bool,
+}
+
+/// Creating, reading, updating and copying a [`User`].
+pub fn struct_example(out: &mut dyn Write) -> io::Result<()> {
+    let mut user: User = User {
+        username: String::from("John Doe"),
+        email: String::from("john.doe@example.com"),
+        sign_in_count: 1,
+        active: true,
+    };
+
+    let name: String = user.username;
+    writeln!(out, "name: {}", name)?;
+    writeln!(out, "sign_in_count: {}", user.sign_in_count)?;
+    writeln!(out, "active: {}", user.active)?;
+    user.email = String::from("john.doe.mutable@example.com");
+
+    let user2: User = build_user(
+        String::from("jane.doe@example.com"),
+        String::from("Jane Doe"),
+    );
+    writeln!(out, "user2: {:?}", user2)?;
+    writeln!(out, "sign_in_count: {}", user2.sign_in_count)?;
+    writeln!(out, "active: {}", user2.active)?;
+
+    let user3: User = User { ..user2 };
+    writeln!(out, "user3: {:?}", user3)?;
+    writeln!(out, "sign_in_count: {}", user3.sign_in_count)?;
+    writeln!(out, "active: {}", user3.active)?;
+    Ok(())
+}
+
+/// Builds an active [`User`] using the field init shorthand.
+pub fn build_user(email: String, username: String) -> User {
+    User {
+        email,
+        username,
+        sign_in_count: 0,
+        active: true,
+    }
+}
+
+// Tuple Structs are similar to structs, but they are similar to tuples in that they do not have named fields
+/// A tuple struct without named fields.
+pub fn tuple_struct(out: &mut dyn Write) -> io::Result<()> {
+    struct Color(i32, i32, i32);
+    let black: Color = Color(0, 0, 0);
+
+    let r: i32 = black.0;
+    let g: i32 = black.1;
+    let b: i32 = black.2;
+    writeln!(out, "r: {}, g: {}, b: {}", r, g, b)?;
+    Ok(())
+}
+
+/// A rectangle with integer sides.
+pub struct Rect {
+    /// Horizontal side.
+    pub width: u32,
+    /// Vertical side.
+    pub height: u32,
+}
+
+/// Prints and returns the area of `dimenstions`.
+pub fn calc_area_rect(out: &mut dyn Write, dimenstions: &Rect) -> io::Result<u32> {
+    let rect: Rect = Rect {
+        width: dimenstions.width,
+        height: dimenstions.height,
+    };
+
+    writeln!(
+        out,
+        "{}",
+        tr!("calc_area_rect.area", rect.width * rect.height)
+    )?;
+    Ok(rect.width * rect.height)
+}
+
+// Methods
+// Methods are functions that are associated with a struct
+// They are defined inside the struct and have access to all the fields of the struct
+impl Rect {
+    /// Width times height.
+    pub fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    /// Whether `other` fits inside `self` without rotating it.
+    pub fn can_hold(&self, other: &Rect) -> bool {
+        self.width >= other.width && self.height >= other.height
+    }
+}
+
+/// Same as [`calc_area_rect`] but using [`Rect`] methods.
+pub fn calc_area_rect2(out: &mut dyn Write, dimenstions: &Rect) -> io::Result<u32> {
+    let rect: Rect = Rect {
+        width: dimenstions.width,
+        height: dimenstions.height,
+    };
+
+    let rect1: Rect = Rect {
+        width: 40,
+        height: 60,
+    };
+
+    let rect2: Rect = Rect {
+        width: 20,
+        height: 30,
+    };
+
+    let rect3: Rect = Rect::square(10);
+
+    writeln!(out, "{}", tr!("calc_area_rect2.area", rect.area()))?;
+    writeln!(
+        out,
+        "{}",
+        tr!(
+            "calc_area_rect2.can_hold",
+            "rect",
+            "rect1",
+            rect.can_hold(&rect1)
+        )
+    )?;
+    writeln!(
+        out,
+        "{}",
+        tr!(
+            "calc_area_rect2.can_hold",
+            "rect",
+            "rect2",
+            rect.can_hold(&rect2)
+        )
+    )?;
+    writeln!(
+        out,
+        "{}",
+        tr!(
+            "calc_area_rect2.can_hold",
+            "rect1",
+            "rect2",
+            rect1.can_hold(&rect2)
+        )
+    )?;
+    writeln!(out, "{}", tr!("calc_area_rect2.square", rect3.area()))?;
+    Ok(rect.area())
+}
+
+// Methods, use the `self` keyword to access fields of the struct, while associated functions do not use `self`
+// Associated functions are functions that are associated with a struct
+// They are defined inside the `impl` block and do not have access to the fields of the struct
+impl Rect {
+    /// A rectangle whose sides are both `size`.
+    pub fn square(size: u32) -> Rect {
+        Rect {
+            width: size,
+            height: size,
+        }
+    }
+}
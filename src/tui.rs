@@ -0,0 +1,392 @@
+//! A full-screen lesson browser for the terminal.
+//!
+//! The sidebar lists the lessons, the main pane shows the text and source of
+//! the selected one, and the bottom pane its output once it was run. The
+//! browser state lives in [`App`], which turns [`Key`]s into changes and
+//! renders itself as plain lines, so it can be driven without a terminal.
+//! [`run`] puts the terminal in raw mode with `stty` and draws with ANSI
+//! escape codes.
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+
+use crate::lessons::{LESSONS, Lesson};
+use crate::tr;
+
+/// A key press.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// Arrow up.
+    Up,
+    /// Arrow down.
+    Down,
+    /// Page up.
+    PageUp,
+    /// Page down.
+    PageDown,
+    /// Home.
+    Home,
+    /// End.
+    End,
+    /// Enter or Return.
+    Enter,
+    /// Escape.
+    Esc,
+    /// Backspace.
+    Backspace,
+    /// Ctrl-C.
+    Interrupt,
+    /// A printable character.
+    Char(char),
+}
+
+/// Decodes the bytes read from a raw mode terminal into keys.
+///
+/// Escape sequences the browser does not use are dropped.
+pub fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let input = String::from_utf8_lossy(bytes);
+    let mut chars = input.chars().peekable();
+    let mut keys: Vec<Key> = Vec::new();
+
+    while let Some(c) = chars.next() {
+        let key: Option<Key> = match c {
+            '\x1b' => match chars.peek() {
+                // CSI (`ESC [`) and SS3 (`ESC O`) sequences
+                Some('[') | Some('O') => {
+                    chars.next();
+                    let mut sequence: String = String::new();
+                    while let Some(&next) = chars.peek() {
+                        chars.next();
+                        sequence.push(next);
+                        if next.is_ascii_alphabetic() || next == '~' {
+                            break;
+                        }
+                    }
+                    match sequence.as_str() {
+                        "A" => Some(Key::Up),
+                        "B" => Some(Key::Down),
+                        "H" | "1~" | "7~" => Some(Key::Home),
+                        "F" | "4~" | "8~" => Some(Key::End),
+                        "5~" => Some(Key::PageUp),
+                        "6~" => Some(Key::PageDown),
+                        _ => None,
+                    }
+                }
+                _ => Some(Key::Esc),
+            },
+            '\r' | '\n' => Some(Key::Enter),
+            '\x7f' | '\x08' => Some(Key::Backspace),
+            '\x03' => Some(Key::Interrupt),
+            c if c.is_control() => None,
+            c => Some(Key::Char(c)),
+        };
+        keys.extend(key);
+    }
+    keys
+}
+
+/// The state of the browser.
+#[derive(Debug, Clone, Default)]
+pub struct App {
+    /// Text the lesson names are filtered by.
+    pub query: String,
+    /// Whether key presses are typed into the query.
+    pub searching: bool,
+    /// Index of the selected lesson among the [`visible`](App::visible) ones.
+    pub selected: usize,
+    /// Lines the main pane is scrolled down by.
+    pub scroll: usize,
+    /// The last lesson that was run and what it printed.
+    pub output: Option<(&'static str, String)>,
+    /// Set once the user asked to quit.
+    pub quit: bool,
+}
+
+impl App {
+    /// A browser with the first lesson selected.
+    pub fn new() -> App {
+        App::default()
+    }
+
+    /// The lessons whose name contains the query, ignoring case.
+    pub fn visible(&self) -> Vec<&'static Lesson> {
+        let query: String = self.query.to_lowercase();
+        LESSONS
+            .iter()
+            .filter(|lesson| lesson.name.contains(&query))
+            .collect()
+    }
+
+    /// The selected lesson, `None` when no lesson matches the query.
+    pub fn current(&self) -> Option<&'static Lesson> {
+        self.visible().get(self.selected).copied()
+    }
+
+    /// Runs the selected lesson and keeps its output.
+    pub fn run_current(&mut self) -> io::Result<()> {
+        if let Some(lesson) = self.current() {
+            self.output = Some((lesson.name, lesson.output()?));
+        }
+        Ok(())
+    }
+
+    /// Applies one key press.
+    pub fn handle_key(&mut self, key: Key) -> io::Result<()> {
+        let count: usize = self.visible().len();
+        match key {
+            Key::Interrupt => self.quit = true,
+            Key::Up => self.select(self.selected.saturating_sub(1)),
+            Key::Down => self.select((self.selected + 1).min(count.saturating_sub(1))),
+            Key::Home => self.select(0),
+            Key::End => self.select(count.saturating_sub(1)),
+            Key::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            Key::PageDown => self.scroll += 10,
+            Key::Enter if self.searching => self.searching = false,
+            Key::Enter => self.run_current()?,
+            Key::Esc => {
+                self.searching = false;
+                self.query.clear();
+                self.select(0);
+            }
+            Key::Backspace if self.searching => {
+                self.query.pop();
+                self.select(0);
+            }
+            Key::Char(c) if self.searching => {
+                self.query.push(c);
+                self.select(0);
+            }
+            Key::Char('/') => self.searching = true,
+            Key::Char('q') => self.quit = true,
+            Key::Char('k') => self.handle_key(Key::Up)?,
+            Key::Char('j') => self.handle_key(Key::Down)?,
+            Key::Char('r') => self.run_current()?,
+            Key::Backspace | Key::Char(_) => {}
+        }
+        Ok(())
+    }
+
+    fn select(&mut self, index: usize) {
+        if index != self.selected {
+            self.scroll = 0;
+        }
+        self.selected = index;
+    }
+
+    /// The screen as `height` lines of `width` characters.
+    pub fn render(&self, width: usize, height: usize) -> Vec<String> {
+        if width < 40 || height < 10 {
+            let mut lines: Vec<String> = vec![fit("", width); height];
+            if let Some(first) = lines.first_mut() {
+                *first = fit(tr!("tui.too_small"), width);
+            }
+            return lines;
+        }
+
+        let body: usize = height - 1;
+        let sidebar_width: usize = (width / 3).min(26);
+        let main_width: usize = width - sidebar_width - 1;
+        let sidebar: Vec<String> = self.sidebar(sidebar_width, body);
+
+        // The output pane takes the bottom third, the text and source the rest
+        let output_height: usize = body / 3;
+        let mut main: Vec<String> = self
+            .details(main_width)
+            .into_iter()
+            .skip(self.scroll)
+            .take(body - output_height)
+            .collect();
+        main.resize(body - output_height, String::new());
+        main.extend(self.output_pane(main_width, output_height));
+
+        let mut lines: Vec<String> = sidebar
+            .iter()
+            .zip(&main)
+            .map(|(left, right)| format!("{}│{}", left, fit(right, main_width)))
+            .collect();
+        let help: &str = if self.searching {
+            tr!("tui.search_help")
+        } else {
+            tr!("tui.help")
+        };
+        lines.push(fit(help, width));
+        lines
+    }
+
+    fn sidebar(&self, width: usize, height: usize) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        if self.searching || !self.query.is_empty() {
+            let cursor: &str = if self.searching { "_" } else { "" };
+            lines.push(format!("/{}{}", self.query, cursor));
+        }
+
+        let visible: Vec<&Lesson> = self.visible();
+        if visible.is_empty() {
+            lines.push(tr!("tui.no_match").to_string());
+        }
+        // Keep the selected lesson on screen
+        let room: usize = height - lines.len();
+        let first: usize = (self.selected + 1).saturating_sub(room);
+        for (i, lesson) in visible.iter().enumerate().skip(first).take(room) {
+            let marker: &str = if i == self.selected { "▶ " } else { "  " };
+            lines.push(format!("{}{}", marker, lesson.name));
+        }
+
+        lines.resize(height, String::new());
+        lines.iter().map(|line| fit(line, width)).collect()
+    }
+
+    // Title, text and source of the selected lesson
+    fn details(&self, width: usize) -> Vec<String> {
+        let Some(lesson) = self.current() else {
+            return Vec::new();
+        };
+        let mut lines: Vec<String> = vec![
+            format!(
+                " {} ({})",
+                lesson.name,
+                tr!(&format!("topic.{}", lesson.topic.name()))
+            ),
+            format!(" {}", lesson.description()),
+            String::new(),
+        ];
+        for paragraph in lesson.paragraphs() {
+            lines.extend(
+                wrap(paragraph, width - 2)
+                    .into_iter()
+                    .map(|l| format!(" {}", l)),
+            );
+            lines.push(String::new());
+        }
+        lines.push(rule(tr!("book.code"), width));
+        lines.extend(lesson.source().lines().map(|l| format!(" {}", l)));
+        lines
+    }
+
+    fn output_pane(&self, width: usize, height: usize) -> Vec<String> {
+        let mut lines: Vec<String> = vec![rule(tr!("book.output"), width)];
+        match (&self.output, self.current()) {
+            (Some((name, output)), Some(lesson)) if *name == lesson.name => {
+                lines.extend(output.lines().map(|l| format!(" {}", l)));
+            }
+            (_, Some(_)) => lines.push(format!(" {}", tr!("tui.run_hint"))),
+            (_, None) => {}
+        }
+        lines.resize(height, String::new());
+        lines
+    }
+}
+
+// Pads or cuts `text` to exactly `width` characters
+fn fit(text: &str, width: usize) -> String {
+    let mut fitted: String = text.chars().take(width).collect();
+    let len: usize = fitted.chars().count();
+    fitted.extend(std::iter::repeat_n(' ', width - len));
+    fitted
+}
+
+// "── Code ────"
+fn rule(title: &str, width: usize) -> String {
+    let head: String = format!("── {} ", title);
+    let len: usize = head.chars().count();
+    format!("{}{}", head, "─".repeat(width.saturating_sub(len)))
+}
+
+// Breaks `text` into lines of at most `width` characters, between words
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line: String = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+// Runs `stty` on the controlling terminal
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::from(File::open("/dev/tty")?))
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// Raw mode and the alternate screen, both undone when dropped
+struct Terminal {
+    saved: String,
+}
+
+impl Terminal {
+    fn enter() -> io::Result<Terminal> {
+        let saved: String = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        // Alternate screen, hidden cursor
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush()?;
+        Ok(Terminal { saved })
+    }
+
+    // (rows, columns)
+    fn size(&self) -> (usize, usize) {
+        let size: String = stty(&["size"]).unwrap_or_default();
+        let mut parts = size.split_whitespace().map(|n| n.parse::<usize>().ok());
+        match (parts.next().flatten(), parts.next().flatten()) {
+            (Some(rows), Some(columns)) => (rows, columns),
+            _ => (24, 80),
+        }
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+/// Runs the browser on the controlling terminal until the user quits.
+pub fn run() -> io::Result<()> {
+    let terminal: Terminal = Terminal::enter()?;
+    let mut app: App = App::new();
+    let mut tty: File = File::open("/dev/tty")?;
+    let mut buf: [u8; 64] = [0; 64];
+
+    while !app.quit {
+        let (rows, columns) = terminal.size();
+        let mut screen: String = String::from("\x1b[H");
+        for (i, line) in app.render(columns, rows).iter().enumerate() {
+            if i > 0 {
+                screen.push_str("\r\n");
+            }
+            screen.push_str(line);
+        }
+        let mut out = io::stdout().lock();
+        out.write_all(screen.as_bytes())?;
+        out.flush()?;
+
+        let n: usize = tty.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        for key in parse_keys(&buf[..n]) {
+            app.handle_key(key)?;
+        }
+    }
+    Ok(())
+}
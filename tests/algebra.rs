@@ -0,0 +1,136 @@
+use learn_rust::calc::algebra::{self, DeriveError};
+use learn_rust::calc::rational::Rational;
+use learn_rust::calc::{Calculator, Operation, parse_as};
+
+fn simplified(input: &str) -> String {
+    algebra::simplify(&parse_as::<i64>(input).unwrap()).to_string()
+}
+
+fn derived(input: &str, var: &str) -> Result<String, DeriveError> {
+    algebra::derive(&parse_as::<i64>(input).unwrap(), var).map(|d| d.to_string())
+}
+
+// Printing a simplified operation and parsing it again gives the same tree,
+// which simplifying does not change any more
+fn round_trips(input: &str) {
+    let simple: Operation<i64> = algebra::simplify(&parse_as(input).unwrap());
+    let reparsed: Operation<i64> = parse_as(&simple.to_string()).unwrap();
+    assert_eq!(reparsed, simple, "{}", input);
+    assert_eq!(algebra::simplify(&reparsed), simple, "{}", input);
+}
+
+#[test]
+fn folds_constants() {
+    assert_eq!(simplified("2 * 3 + 4"), "10");
+    assert_eq!(simplified("x + 2 * 3"), "x + 6");
+    assert_eq!(simplified("2 + x + 3"), "x + 5");
+    assert_eq!(simplified("2 ^ 3 * x"), "8 * x");
+    assert_eq!(simplified("-(2 * x)"), "-2 * x");
+    // Failing parts are kept so that executing still reports the error
+    assert_eq!(simplified("x + 1 / 0"), "1 / 0 + x");
+}
+
+#[test]
+fn eliminates_identities() {
+    assert_eq!(simplified("x * 1"), "x");
+    assert_eq!(simplified("1 * x"), "x");
+    assert_eq!(simplified("x + 0"), "x");
+    assert_eq!(simplified("0 + x"), "x");
+    assert_eq!(simplified("x - 0"), "x");
+    assert_eq!(simplified("0 - x"), "-x");
+    assert_eq!(simplified("x / 1"), "x");
+    assert_eq!(simplified("x ^ 1"), "x");
+    assert_eq!(simplified("x ^ 0"), "1");
+    assert_eq!(simplified("x * 0 + y"), "y");
+    assert_eq!(simplified("x - x"), "0");
+    assert_eq!(simplified("--x"), "x");
+}
+
+#[test]
+fn orders_commutative_operands() {
+    assert_eq!(simplified("1 * y + 2 + x * 3"), "3 * x + y + 2");
+    assert_eq!(simplified("x * 3 + y + 2"), "3 * x + y + 2");
+    assert_eq!(simplified("y * x * z"), "x * y * z");
+    assert_eq!(simplified("x^2 + x * y + x"), "x + x * y + x^2");
+    assert_eq!(
+        simplified("(b - a) * (a - b)"),
+        simplified("(a - b) * (b - a)")
+    );
+}
+
+#[test]
+fn differentiates() {
+    assert_eq!(derived("5", "x").unwrap(), "0");
+    assert_eq!(derived("x", "x").unwrap(), "1");
+    assert_eq!(derived("x^3 + 2 * x", "x").unwrap(), "3 * x^2 + 2");
+    assert_eq!(derived("x * y", "x").unwrap(), "y");
+    assert_eq!(derived("-x^2", "x").unwrap(), "-2 * x");
+    assert_eq!(derived("(x + 1)^2", "x").unwrap(), "2 * (x + 1)");
+    let quotient: Operation<Rational> = parse_as("1 / x").unwrap();
+    assert_eq!(
+        algebra::derive(&quotient, "x").unwrap().to_string(),
+        "-1 / x^2"
+    );
+    assert_eq!(derived("y^2", "x").unwrap(), "0");
+}
+
+#[test]
+fn reports_what_can_not_be_differentiated() {
+    assert_eq!(derived("x % 2", "x"), Err(DeriveError::Remainder));
+    assert_eq!(
+        derived("2 ^ x", "x"),
+        Err(DeriveError::VariableExponent(String::from("x")))
+    );
+    assert_eq!(
+        derived("x ^ y", "y").unwrap_err().to_string(),
+        "the exponent of a power can not depend on `y`"
+    );
+    // Integers truncate `x / 2`, the quotient rule would fold to 0
+    assert_eq!(
+        derived("x / 2", "x"),
+        Err(DeriveError::TruncatingDivision("i64"))
+    );
+    assert_eq!(derived("y / 2 + x", "x").unwrap(), "1");
+}
+
+#[test]
+fn round_trips_through_the_printer() {
+    for input in [
+        "1 * y + 2 + x * 3",
+        "(x + 1) * (x - 1) / 2",
+        "-(2 * x) ^ 2 - -3",
+        "x ^ (y + 1) % 7",
+        "(a + b) * c + 0 * d",
+        "x + 1 / 0",
+    ] {
+        round_trips(input);
+    }
+    let derivative: Operation<f64> =
+        algebra::derive(&parse_as("x^3 * y - x / y").unwrap(), "x").unwrap();
+    assert_eq!(
+        parse_as::<f64>(&derivative.to_string()).unwrap(),
+        derivative
+    );
+}
+
+#[test]
+fn calculator_commands() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(calculator.eval_line("simplify x * 1 + 0"), "x");
+    assert_eq!(calculator.eval_line("derive x x^2 + y"), "2 * x");
+    assert_eq!(
+        calculator.eval_line("derive x"),
+        "Error: `derive` expects a name and an operation"
+    );
+    assert_eq!(
+        calculator.eval_line("derive x x % 3"),
+        "Error: `%` can not be differentiated"
+    );
+    assert_eq!(calculator.eval_line("type i32"), "type: i32");
+    assert_eq!(
+        calculator.eval_line("derive x x/2"),
+        "Error: `/` truncates i32 numbers, use `type rational` or `type f64` to differentiate it"
+    );
+    assert_eq!(calculator.eval_line("type rational"), "type: rational");
+    assert_eq!(calculator.eval_line("derive x x/2"), "1/2");
+}
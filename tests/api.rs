@@ -0,0 +1,157 @@
+// The example types are usable from outside the crate
+
+use learn_rust::calc::{MathError, Mode};
+use learn_rust::enums::{Coin, Operation, value_in_cents};
+use learn_rust::structs::{Rect, build_user};
+
+#[test]
+fn rect_methods() {
+    let rect1: Rect = Rect {
+        width: 40,
+        height: 60,
+    };
+    let rect2: Rect = Rect {
+        width: 20,
+        height: 30,
+    };
+    assert!(rect1.can_hold(&rect2));
+    assert!(!rect2.can_hold(&rect1));
+    assert_eq!(Rect::square(10).area(), 100);
+}
+
+#[test]
+fn build_user_starts_active() {
+    let user = build_user(String::from("jane@example.com"), String::from("Jane"));
+    assert!(user.active);
+    assert_eq!(user.sign_in_count, 0);
+}
+
+#[test]
+fn coin_values() {
+    let mut out: Vec<u8> = Vec::new();
+    assert_eq!(value_in_cents(&mut out, Coin::Dime).unwrap(), 10);
+    assert_eq!(String::from_utf8(out).unwrap(), "This is a dime\n");
+}
+
+fn value(v: i32) -> Box<Operation> {
+    Box::new(Operation::Value(v))
+}
+
+#[test]
+fn operation_execute() {
+    assert_eq!(Operation::Add(value(1), value(2)).execute(), Ok(3));
+    assert_eq!(
+        Operation::Div {
+            divident: value(20),
+            divisor: value(0)
+        }
+        .execute(),
+        Err(MathError::DivideByZero)
+    );
+}
+
+#[test]
+fn checked_operations_report_overflow() {
+    let min_by_minus_one: Operation = Operation::Div {
+        divident: value(i32::MIN),
+        divisor: value(-1),
+    };
+    assert_eq!(min_by_minus_one.execute(), Err(MathError::Overflow));
+    assert_eq!(
+        Operation::Add(value(i32::MAX), value(1)).execute(),
+        Err(MathError::Overflow)
+    );
+    assert_eq!(
+        Operation::Mul(value(i32::MIN), value(2)).execute(),
+        Err(MathError::Underflow)
+    );
+    assert_eq!(
+        Operation::Sub {
+            first: value(i32::MIN),
+            second: value(1)
+        }
+        .execute(),
+        Err(MathError::Underflow)
+    );
+    assert_eq!(
+        Operation::Mul(value(-46341), value(46341)).execute(),
+        Err(MathError::Underflow)
+    );
+    assert_eq!(
+        Operation::Mul(value(-46340), value(46340)).execute(),
+        Ok(-2147395600)
+    );
+
+    let error: &dyn std::error::Error = &MathError::Overflow;
+    assert_eq!(error.to_string(), "Result is too large");
+}
+
+#[test]
+fn wrapping_and_saturating_modes() {
+    let min_by_minus_one: Operation = Operation::Div {
+        divident: value(i32::MIN),
+        divisor: value(-1),
+    };
+    assert_eq!(min_by_minus_one.execute_with(Mode::Wrapping), Ok(i32::MIN));
+    assert_eq!(
+        min_by_minus_one.execute_with(Mode::Saturating),
+        Ok(i32::MAX)
+    );
+
+    for (a, b) in [(i32::MAX, 7), (i32::MIN, 3), (-9, 4), (123456, -98765)] {
+        let mul: Operation = Operation::Mul(value(a), value(b));
+        assert_eq!(mul.execute_with(Mode::Wrapping), Ok(a.wrapping_mul(b)));
+        assert_eq!(mul.execute_with(Mode::Saturating), Ok(a.saturating_mul(b)));
+        let add: Operation = Operation::Add(value(a), value(b));
+        assert_eq!(add.execute_with(Mode::Wrapping), Ok(a.wrapping_add(b)));
+        assert_eq!(add.execute_with(Mode::Saturating), Ok(a.saturating_add(b)));
+    }
+
+    let by_zero: Operation = Operation::Div {
+        divident: value(1),
+        divisor: value(0),
+    };
+    for mode in Mode::ALL {
+        assert_eq!(by_zero.execute_with(mode), Err(MathError::DivideByZero));
+    }
+}
+
+#[test]
+fn nested_operations() {
+    // (1 + 2) * -(10 % 4)^3
+    let operation: Operation = Operation::Mul(
+        Box::new(Operation::Add(value(1), value(2))),
+        Box::new(Operation::Neg(Box::new(Operation::Pow {
+            base: Box::new(Operation::Rem {
+                divident: value(10),
+                divisor: value(4),
+            }),
+            exponent: value(3),
+        }))),
+    );
+    assert_eq!(operation.execute(), Ok(-24));
+    assert_eq!(operation.to_string(), "(1 + 2) * -(10 % 4)^3");
+
+    let negative_power: Operation = Operation::Pow {
+        base: value(2),
+        exponent: value(-1),
+    };
+    assert_eq!(negative_power.execute(), Err(MathError::NegativeExponent));
+    assert_eq!(
+        Operation::Neg(value(i32::MIN)).execute(),
+        Err(MathError::Overflow)
+    );
+    let huge: Operation = Operation::Pow {
+        base: value(-2),
+        exponent: value(33),
+    };
+    assert_eq!(huge.execute(), Err(MathError::Underflow));
+    assert_eq!(huge.execute_with(Mode::Saturating), Ok(i32::MIN));
+    assert_eq!(huge.execute_with(Mode::Wrapping), Ok(0));
+    // The one remainder Rust's `%` panics on is fine here
+    let rem: Operation = Operation::Rem {
+        divident: value(i32::MIN),
+        divisor: value(-1),
+    };
+    assert_eq!(rem.execute(), Ok(0));
+}
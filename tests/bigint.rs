@@ -0,0 +1,258 @@
+use learn_rust::calc::bigint::BigInt;
+use learn_rust::calc::number::Number;
+use learn_rust::calc::{Calculator, MathError, Mode, parse_as};
+
+const FACTORIAL_100: &str = "93326215443944152681699238856266700490715968264381621468592963895217599993229915608941463976156518286253697920827223758251185210916864000000000000000000000000";
+
+fn big(text: &str) -> BigInt {
+    text.parse().unwrap()
+}
+
+fn factorial(n: i64) -> BigInt {
+    (1..=n).fold(BigInt::from(1), |product, k| {
+        product.mul(&BigInt::from(k), Mode::Checked).unwrap()
+    })
+}
+
+#[test]
+fn parses_and_formats() {
+    for text in [
+        "0",
+        "7",
+        "-42",
+        "1000000000",
+        "-999999999999999999",
+        FACTORIAL_100,
+    ] {
+        assert_eq!(big(text).to_string(), text);
+    }
+    assert_eq!(big("+0012").to_string(), "12");
+    assert_eq!(big("-0").to_string(), "0");
+    assert!("12a".parse::<BigInt>().is_err());
+    assert!("-".parse::<BigInt>().is_err());
+    assert_eq!(BigInt::from(i64::MIN).to_string(), i64::MIN.to_string());
+    assert_eq!(big(FACTORIAL_100).digits(), 158);
+}
+
+#[test]
+fn computes_known_large_values() {
+    assert_eq!(
+        factorial(30).to_string(),
+        "265252859812191058636308480000000"
+    );
+    assert_eq!(factorial(100).to_string(), FACTORIAL_100);
+    assert_eq!(
+        BigInt::from(2)
+            .pow(&BigInt::from(100), Mode::Checked)
+            .unwrap()
+            .to_string(),
+        "1267650600228229401496703205376"
+    );
+    let mersenne: BigInt = big("170141183460469231731687303715884105727");
+    assert_eq!(
+        mersenne.mul(&mersenne, Mode::Checked).unwrap().to_string(),
+        "28948022309329048855892746252171976962977213799489202546401021394546514198529"
+    );
+}
+
+#[test]
+fn adds_and_subtracts_with_signs() {
+    let a: BigInt = big("1000000000000000000000000000000");
+    assert_eq!(
+        a.sub(
+            &a.add(&BigInt::from(1), Mode::Checked).unwrap(),
+            Mode::Checked
+        )
+        .unwrap(),
+        BigInt::from(-1)
+    );
+    assert_eq!(
+        BigInt::from(-5)
+            .add(&BigInt::from(3), Mode::Checked)
+            .unwrap(),
+        BigInt::from(-2)
+    );
+    assert_eq!(
+        BigInt::from(-5)
+            .sub(&BigInt::from(-5), Mode::Checked)
+            .unwrap(),
+        BigInt::zero()
+    );
+    assert_eq!(
+        big("999999999")
+            .add(&BigInt::from(1), Mode::Checked)
+            .unwrap()
+            .to_string(),
+        "1000000000"
+    );
+}
+
+#[test]
+fn divides_like_integers() {
+    let three: BigInt = BigInt::from(3)
+        .pow(&BigInt::from(200), Mode::Checked)
+        .unwrap();
+    let seven: BigInt = BigInt::from(7)
+        .pow(&BigInt::from(50), Mode::Checked)
+        .unwrap();
+    let (quotient, remainder) = three.div_rem(&seven).unwrap();
+    assert_eq!(
+        quotient.to_string(),
+        "147689269781346654697366079240021362541982658661987020"
+    );
+    assert_eq!(
+        remainder.to_string(),
+        "1043054234746676783066714664998769142256021"
+    );
+    // Truncated towards zero, the remainder has the sign of the divident
+    let negative: BigInt = three.neg(Mode::Checked).unwrap();
+    assert_eq!(
+        negative.div(&seven, Mode::Checked).unwrap(),
+        quotient.neg(Mode::Checked).unwrap()
+    );
+    assert_eq!(
+        negative.rem(&seven, Mode::Checked).unwrap(),
+        remainder.neg(Mode::Checked).unwrap()
+    );
+    assert_eq!(
+        factorial(100).div(&factorial(98), Mode::Checked).unwrap(),
+        BigInt::from(9900)
+    );
+    assert_eq!(
+        three.div(&BigInt::zero(), Mode::Checked),
+        Err(MathError::DivideByZero)
+    );
+    // Quotient limbs whose first estimate is too large, and large operands
+    let (dividend, divisor) = (
+        big("1000000000100000000000000000000000001"),
+        big("1000000000000000001"),
+    );
+    let (quotient, remainder) = dividend.div_rem(&divisor).unwrap();
+    assert_eq!(
+        (quotient.to_string(), remainder.to_string()),
+        (
+            "1000000000099999998".to_string(),
+            "999999999900000003".to_string()
+        )
+    );
+    let big_divisor: BigInt = big("1000000000000000000000000000007");
+    for dividend in [
+        factorial(100),
+        big("999999999999999999999999999999999999999999999"),
+        big("1000000000000000000000000000006999999999999999999"),
+        factorial(100).mul(&big_divisor, Mode::Checked).unwrap(),
+    ] {
+        for divisor in [
+            big_divisor.clone(),
+            big("500000000999999999"),
+            factorial(40),
+        ] {
+            let (quotient, remainder) = dividend.div_rem(&divisor).unwrap();
+            assert!(remainder < divisor);
+            let product: BigInt = quotient.mul(&divisor, Mode::Checked).unwrap();
+            assert_eq!(product.add(&remainder, Mode::Checked).unwrap(), dividend);
+        }
+    }
+    let ten: BigInt = BigInt::from(10);
+    let huge: BigInt = ten.pow(&BigInt::from(20_000), Mode::Checked).unwrap();
+    let half: BigInt = ten.pow(&BigInt::from(10_000), Mode::Checked).unwrap();
+    let divisor: BigInt = half.add(&BigInt::from(7), Mode::Checked).unwrap();
+    let (quotient, remainder) = huge.div_rem(&divisor).unwrap();
+    assert_eq!(quotient.digits(), 10_000);
+    assert_eq!(
+        quotient
+            .mul(&divisor, Mode::Checked)
+            .unwrap()
+            .add(&remainder, Mode::Checked)
+            .unwrap(),
+        huge
+    );
+    for (a, b) in [(17i64, 5i64), (-17, 5), (17, -5), (-17, -5), (4, 9)] {
+        assert_eq!(
+            BigInt::from(a)
+                .div(&BigInt::from(b), Mode::Checked)
+                .unwrap(),
+            BigInt::from(a / b)
+        );
+        assert_eq!(
+            BigInt::from(a)
+                .rem(&BigInt::from(b), Mode::Checked)
+                .unwrap(),
+            BigInt::from(a % b)
+        );
+    }
+}
+
+#[test]
+fn compares() {
+    assert!(big("-1000000000000") < BigInt::from(-1));
+    assert!(BigInt::from(-1) < BigInt::zero());
+    assert!(big(FACTORIAL_100) > factorial(99));
+}
+
+#[test]
+fn refuses_impossible_powers() {
+    let two: BigInt = BigInt::from(2);
+    assert_eq!(
+        two.pow(&BigInt::from(-1), Mode::Checked),
+        Err(MathError::NegativeExponent)
+    );
+    assert_eq!(
+        two.pow(&big("1000000000000"), Mode::Checked),
+        Err(MathError::Overflow)
+    );
+    // An even power of a negative number is positive
+    assert_eq!(
+        BigInt::from(-10).pow(&big("1000000000000"), Mode::Checked),
+        Err(MathError::Overflow)
+    );
+    assert_eq!(
+        BigInt::from(-10).pow(&big("1000000000001"), Mode::Checked),
+        Err(MathError::Underflow)
+    );
+    assert_eq!(
+        BigInt::from(-10).pow(&BigInt::from(1_000_000), Mode::Checked),
+        Err(MathError::Overflow)
+    );
+    assert_eq!(
+        BigInt::from(-1).pow(&big("1000000000001"), Mode::Checked),
+        Ok(BigInt::from(-1))
+    );
+    assert_eq!(
+        BigInt::zero().pow(&BigInt::zero(), Mode::Checked),
+        Ok(BigInt::from(1))
+    );
+    // The limit is on the size of the result, which is small for base 2
+    let large: BigInt = two.pow(&BigInt::from(100_001), Mode::Checked).unwrap();
+    assert_eq!(large.digits(), 30_104);
+    assert_eq!(
+        BigInt::from(10).pow(&BigInt::from(1_000_000), Mode::Checked),
+        Err(MathError::Overflow)
+    );
+}
+
+#[test]
+fn calculator_never_overflows() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(calculator.eval_line("2^100"), "Error: Result is too large");
+    assert_eq!(calculator.eval_line("type bigint"), "type: bigint");
+    assert_eq!(
+        calculator.eval_line("2^100"),
+        "1267650600228229401496703205376"
+    );
+    assert_eq!(
+        calculator.eval_line("x = 2^64 - 1"),
+        "x = 18446744073709551615"
+    );
+    assert_eq!(calculator.eval_line("x * x / x"), "18446744073709551615");
+    assert_eq!(
+        calculator.eval_line("1.5"),
+        "Error: bigint numbers have no fraction at byte 0"
+    );
+    assert_eq!(
+        calculator.eval_line("type i64"),
+        "type: i64 (dropped x, not a valid i64)"
+    );
+    let operation = parse_as::<BigInt>("-(10^20) % 7").unwrap();
+    assert_eq!(operation.execute().unwrap(), BigInt::from(-2));
+}
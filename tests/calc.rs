@@ -0,0 +1,457 @@
+use std::fs;
+use std::path::PathBuf;
+
+use learn_rust::calc::env::Env;
+use learn_rust::calc::rational::Rational;
+use learn_rust::calc::{
+    self, Calculator, MathError, Mode, NumberType, Operation, ParseError, ParseErrorKind,
+};
+
+mod common;
+
+use common::ScratchDir;
+
+fn error(kind: ParseErrorKind, offset: usize) -> Result<Operation, ParseError> {
+    Err(ParseError { kind, offset })
+}
+
+fn error_as<T>(kind: ParseErrorKind, offset: usize) -> Result<Operation<T>, ParseError> {
+    Err(ParseError { kind, offset })
+}
+
+fn value(v: i32) -> Box<Operation> {
+    Box::new(Operation::Value(v))
+}
+
+fn eval(input: &str) -> String {
+    Calculator::new().eval_line(input)
+}
+
+#[test]
+fn parses_operations() {
+    assert_eq!(
+        calc::parse("20 / 0"),
+        Ok(Operation::Div {
+            divident: value(20),
+            divisor: value(0)
+        })
+    );
+    assert_eq!(calc::parse("3*4"), Ok(Operation::Mul(value(3), value(4))));
+    assert_eq!(
+        calc::parse("-7 - 2"),
+        Ok(Operation::Sub {
+            first: value(-7),
+            second: value(2)
+        })
+    );
+    assert_eq!(
+        calc::parse(" +1 + -2 "),
+        Ok(Operation::Add(value(1), value(-2)))
+    );
+    assert_eq!(calc::parse("-2147483648"), Ok(Operation::Value(i32::MIN)));
+    assert_eq!(calc::parse("-(5)"), Ok(Operation::Neg(value(5))));
+}
+
+#[test]
+fn precedence_and_associativity() {
+    assert_eq!(eval("1 + 2 * 3"), "7");
+    assert_eq!(eval("(1 + 2) * 3"), "9");
+    assert_eq!(eval("10 - 4 - 3"), "3");
+    assert_eq!(eval("10 - (4 - 3)"), "9");
+    assert_eq!(eval("100 / 10 / 5"), "2");
+    assert_eq!(eval("17 % 5 * 2"), "4");
+    assert_eq!(eval("2^3^2"), "512");
+    assert_eq!(eval("(2^3)^2"), "64");
+    assert_eq!(eval("-2^2"), "-4");
+    assert_eq!(eval("(-2)^2"), "4");
+    assert_eq!(eval("2^-1"), "Error: Can not raise to a negative power");
+    assert_eq!(eval("- -3"), "3");
+    assert_eq!(eval("-(1 + 2) * 3"), "-9");
+    assert_eq!(eval("((((7))))"), "7");
+}
+
+#[test]
+fn display_uses_minimal_parentheses() {
+    for input in [
+        "1 + 2 * 3",
+        "(1 + 2) * 3",
+        "1 - (2 - 3)",
+        "1 - 2 - 3",
+        "1 + (2 + 3)",
+        "2 * (3 / 4) % 5",
+        "2^3^2",
+        "(2^3)^2",
+        "-2^2",
+        "(-2)^2",
+        "2^-1",
+        "-(1 + 2)",
+        "- -3",
+        "3 - -2",
+        "(1 + 2) * -(10 % 4)^3",
+    ] {
+        let operation: Operation = calc::parse(input).unwrap();
+        assert_eq!(operation.to_string(), input);
+        assert_eq!(calc::parse(&operation.to_string()), Ok(operation));
+    }
+    assert_eq!(
+        calc::parse("((1) + (2 * 3))").unwrap().to_string(),
+        "1 + 2 * 3"
+    );
+    assert_eq!(calc::parse("(1 - 2) - 3").unwrap().to_string(), "1 - 2 - 3");
+}
+
+#[test]
+fn errors_point_at_the_problem() {
+    use ParseErrorKind::*;
+
+    assert_eq!(calc::parse(""), error(ExpectedNumber, 0));
+    assert_eq!(calc::parse("3 $ 4"), error(UnexpectedChar('$'), 2));
+    assert_eq!(calc::parse("3 4"), error(ExpectedOperator, 2));
+    assert_eq!(calc::parse("3 *"), error(ExpectedNumber, 3));
+    assert_eq!(calc::parse("3 * / 4"), error(ExpectedNumber, 4));
+    assert_eq!(
+        calc::parse("1 + 2147483648"),
+        error(NumberTooLarge("i32"), 4)
+    );
+    assert_eq!(
+        calc::parse("1 + -2147483649"),
+        error(NumberTooLarge("i32"), 4)
+    );
+    assert_eq!(calc::parse("2 * (3 + 4"), error(UnclosedParen, 4));
+    assert_eq!(calc::parse("(3 + 4))"), error(UnmatchedParen, 7));
+    assert_eq!(calc::parse("(3 4)"), error(ExpectedOperator, 3));
+    assert_eq!(calc::parse("()"), error(ExpectedNumber, 1));
+    // Offsets count bytes, not characters
+    assert_eq!(calc::parse("é + 1"), error(UnexpectedChar('é'), 0));
+    assert_eq!(calc::parse("1 + é"), error(UnexpectedChar('é'), 4));
+    assert_eq!(calc::parse("1 ÷ 2"), error(UnexpectedChar('÷'), 2));
+    assert_eq!(calc::parse("(é)").unwrap_err().offset, 1);
+    assert_eq!(calc::parse("é (").unwrap_err().offset, 0);
+
+    let e: ParseError = calc::parse("3 *").unwrap_err();
+    assert_eq!(e.to_string(), "expected a number, a name or `(` at byte 3");
+}
+
+#[test]
+fn deep_nesting_is_an_error() {
+    let nested = |levels: usize| format!("{}1{}", "(".repeat(levels), ")".repeat(levels));
+    // The outermost `unary` is the first level, each `(` adds one
+    assert_eq!(calc::parse(&nested(99)).unwrap().execute(), Ok(1));
+    assert_eq!(
+        calc::parse(&nested(50_000)),
+        error(ParseErrorKind::TooDeep(100), 100)
+    );
+    assert_eq!(
+        calc::parse(&"-".repeat(50_000)).unwrap_err().kind,
+        ParseErrorKind::TooDeep(100)
+    );
+    assert_eq!(
+        calc::parse(&"2^".repeat(50_000)).unwrap_err().kind,
+        ParseErrorKind::TooDeep(100)
+    );
+    // A row of operators is no nesting, but computing it recurses once per
+    // operator, so its tree may only grow so tall
+    let sum = |terms: usize| format!("1{}", " + 1".repeat(terms - 1));
+    assert_eq!(calc::parse(&sum(501)).unwrap().execute(), Ok(501));
+    assert_eq!(
+        calc::parse(&sum(502)),
+        error(ParseErrorKind::TooDeep(500), 2002)
+    );
+    assert_eq!(
+        calc::parse(&sum(50_000)).unwrap_err().kind,
+        ParseErrorKind::TooDeep(500)
+    );
+    // Rows inside rows add up
+    let rows: String = format!("({}){}", sum(300), " * 2".repeat(250));
+    assert_eq!(
+        calc::parse(&rows).unwrap_err().kind,
+        ParseErrorKind::TooDeep(500)
+    );
+    assert_eq!(
+        calc::parse(&format!("-({}) * 2", sum(500)))
+            .unwrap_err()
+            .kind,
+        ParseErrorKind::TooDeep(500)
+    );
+    assert_eq!(
+        eval(&nested(200)),
+        "Error: nested deeper than 100 levels at byte 100"
+    );
+}
+
+#[test]
+fn repl_prints_results_and_errors() {
+    let mut input: &[u8] = b"20 / 0\n3*4\n\n-7 - 2\n3 4\nquit\n1 + 1\n";
+    let mut out: Vec<u8> = Vec::new();
+    calc::repl(&mut Calculator::new(), &mut input, &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "> Error: Can not divide by zero\n\
+         > 12\n\
+         > > -9\n\
+         > Error: expected one of `+`, `-`, `*`, `/`, `%`, `^`, `&`, `|`, `xor`, `<<`, `>>` or `)` at byte 2\n\
+         > "
+    );
+}
+
+#[test]
+fn mode_can_be_changed_in_the_repl() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(
+        calculator.eval_line("2147483647 + 1"),
+        "Error: Result is too large"
+    );
+    assert_eq!(calculator.eval_line("mode wrapping"), "mode: wrapping");
+    assert_eq!(calculator.eval_line("2147483647 + 1"), "-2147483648");
+    assert_eq!(calculator.eval_line("2^31"), "-2147483648");
+    assert_eq!(calculator.eval_line("mode saturating"), "mode: saturating");
+    assert_eq!(calculator.eval_line("-2147483648 - 1"), "-2147483648");
+    assert_eq!(calculator.eval_line("(-2)^31 * 2"), "-2147483648");
+    assert_eq!(calculator.eval_line("mode"), "mode: saturating");
+    assert!(
+        calculator
+            .eval_line("mode fast")
+            .starts_with("Error: unknown mode `fast`")
+    );
+}
+
+#[test]
+fn division_depends_on_the_number_type() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(calculator.eval_line("20 / 3"), "6");
+    assert_eq!(calculator.eval_line("type f64"), "type: f64");
+    assert_eq!(calculator.eval_line("20 / 3"), "6.666666666666667");
+    assert_eq!(calculator.eval_line("1.5 * 2^-1"), "0.75");
+    assert_eq!(calculator.eval_line("7.5 % 2"), "1.5");
+    assert_eq!(
+        calculator.eval_line("1 / 0"),
+        "Error: Can not divide by zero"
+    );
+    assert_eq!(calculator.eval_line("type rational"), "type: rational");
+    assert_eq!(calculator.eval_line("20 / 3"), "20/3");
+    assert_eq!(calculator.eval_line("1/2 + 1/3"), "5/6");
+    assert_eq!(calculator.eval_line("(2/3)^-2"), "9/4");
+    assert_eq!(calculator.eval_line("0.1 + 0.2"), "3/10");
+    assert_eq!(calculator.eval_line("7/2 % 1"), "1/2");
+    assert_eq!(
+        calculator.eval_line("4^(1/2)"),
+        "Error: Can not raise a rational to a fractional power"
+    );
+    assert_eq!(calculator.eval_line("type i64"), "type: i64");
+    assert_eq!(calculator.eval_line("2147483647 + 1"), "2147483648");
+    assert_eq!(calculator.eval_line("2^63"), "Error: Result is too large");
+    assert_eq!(
+        calculator.eval_line("1.5"),
+        "Error: i64 numbers have no fraction at byte 0"
+    );
+    assert_eq!(calculator.eval_line("type"), "type: i64");
+    assert!(
+        calculator
+            .eval_line("type u8")
+            .starts_with("Error: unknown type `u8`")
+    );
+    assert_eq!(
+        NumberType::from_name("rational"),
+        Some(NumberType::Rational)
+    );
+}
+
+#[test]
+fn typed_parsing() {
+    let operation = calc::parse_as::<f64>("1.25 * -2").unwrap();
+    assert_eq!(operation.execute(), Ok(-2.5));
+    assert_eq!(operation.to_string(), "1.25 * -2");
+
+    let operation = calc::parse_as::<Rational>("1.25 ^ 2").unwrap();
+    assert_eq!(operation.execute(), Rational::new(25, 16));
+    // Fractions print like divisions, so they keep their parentheses
+    let squared = Operation::Pow {
+        base: Box::new(Operation::Value(Rational::new(1, 2).unwrap())),
+        exponent: Box::new(Operation::Value(Rational::from_integer(2))),
+    };
+    assert_eq!(squared.to_string(), "(1/2)^2");
+    assert_eq!(
+        calc::parse_as::<Rational>("(1/2)^2").unwrap().execute(),
+        Rational::new(1, 4)
+    );
+
+    assert_eq!(
+        calc::parse_as::<i64>("9223372036854775807 + 1")
+            .unwrap()
+            .execute(),
+        Err(MathError::Overflow)
+    );
+    assert_eq!(
+        calc::parse_as::<i64>("-9223372036854775808 - 1")
+            .unwrap()
+            .execute_with(Mode::Saturating),
+        Ok(i64::MIN)
+    );
+    assert_eq!(
+        calc::parse_as::<f64>("10^400").unwrap().execute(),
+        Err(MathError::Overflow)
+    );
+    assert_eq!(
+        calc::parse_as::<f64>("10^400")
+            .unwrap()
+            .execute_with(Mode::Wrapping),
+        Ok(f64::INFINITY)
+    );
+
+    // Exponents above `u32::MAX` are not negative
+    let huge = |input: &str, mode: Mode| calc::parse_as::<i64>(input).unwrap().execute_with(mode);
+    assert_eq!(huge("1 ^ 5000000000", Mode::Checked), Ok(1));
+    assert_eq!(huge("(-1) ^ 5000000001", Mode::Checked), Ok(-1));
+    assert_eq!(huge("0 ^ 5000000000", Mode::Checked), Ok(0));
+    assert_eq!(
+        huge("2 ^ 5000000000", Mode::Checked),
+        Err(MathError::Overflow)
+    );
+    assert_eq!(
+        huge("(-2) ^ 5000000001", Mode::Checked),
+        Err(MathError::Underflow)
+    );
+    assert_eq!(huge("(-2) ^ 5000000001", Mode::Saturating), Ok(i64::MIN));
+    assert_eq!(huge("2 ^ 5000000000", Mode::Wrapping), Ok(0));
+    assert_eq!(
+        huge("3 ^ 5000000000", Mode::Wrapping),
+        Ok(3i64.wrapping_pow(2500000000).wrapping_pow(2))
+    );
+}
+
+#[test]
+fn checked_floats_are_numbers_within_range() {
+    let f64_in = |input: &str, mode: Mode| calc::parse_as::<f64>(input).unwrap().execute_with(mode);
+    assert_eq!(
+        f64_in("(-8) ^ 0.5", Mode::Checked),
+        Err(MathError::NotANumber)
+    );
+    assert!(f64_in("(-8) ^ 0.5", Mode::Wrapping).unwrap().is_nan());
+    assert_eq!(f64_in("(-8) ^ 3", Mode::Checked), Ok(-512.0));
+    assert_eq!(
+        calc::parse_as::<f64>("1e999"),
+        error_as(ParseErrorKind::NumberTooLarge("f64"), 0)
+    );
+    assert_eq!(
+        calc::parse_as::<f64>("2 * -1e999"),
+        error_as(ParseErrorKind::NumberTooLarge("f64"), 4)
+    );
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("type f64");
+    calculator.eval_line("errors plain");
+    assert_eq!(
+        calculator.eval_line("1 + (-8)^0.5"),
+        "error[E118]: The result is not a number\n  |\n  | 1 + (-8)^0.5\n  |     ^^^^^^^^\n  \
+         = help: a negative number has no real fractional power, `type complex` has it"
+    );
+    calculator.eval_line("type quantity");
+    assert!(calculator.eval_line("1e400 m").starts_with("error[E004]"));
+}
+
+#[test]
+fn rationals_stay_in_lowest_terms() {
+    let r: Rational = Rational::new(6, -4).unwrap();
+    assert_eq!((r.numer(), r.denom()), (-3, 2));
+    assert_eq!(r.to_string(), "-3/2");
+    assert_eq!(Rational::new(1, 0), Err(MathError::DivideByZero));
+    assert_eq!(Rational::new(0, -5).unwrap(), Rational::from_integer(0));
+    assert_eq!(Rational::new(i64::MIN, -1), Err(MathError::Overflow));
+}
+
+#[test]
+fn variables_and_let_bindings() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(calculator.eval_line("vars"), "no variables");
+    assert_eq!(calculator.eval_line("x = 20"), "x = 20");
+    assert_eq!(calculator.eval_line("let y = x / 4"), "y = 5");
+    assert_eq!(calculator.eval_line("y * 3"), "15");
+    assert_eq!(calculator.eval_line("x = x + 1"), "x = 21");
+    assert_eq!(calculator.eval_line("vars"), "x = 21\ny = 5");
+    assert_eq!(calculator.eval_line("z + 1"), "Error: `z` is not defined");
+    // A failed assignment keeps the old value
+    assert_eq!(
+        calculator.eval_line("y = y / 0"),
+        "Error: Can not divide by zero"
+    );
+    assert_eq!(calculator.eval_line("y"), "5");
+    assert_eq!(
+        calculator.eval_line("let 5 = 3"),
+        "Error: expected `name = value` after `let` at byte 4"
+    );
+    assert_eq!(
+        calculator.eval_line("x = = 3"),
+        "Error: expected a number, a name or `(` at byte 4"
+    );
+    // Commands are whole words, so names may start like them
+    assert_eq!(calculator.eval_line("model = 3"), "model = 3");
+    // and may be command names, which are assignments when `=` follows
+    assert_eq!(calculator.eval_line("save = 3"), "save = 3");
+    assert_eq!(calculator.eval_line("type = 1"), "type = 1");
+    assert_eq!(calculator.eval_line("(save + type)"), "4");
+    assert!(!PathBuf::from("= 3").exists());
+    assert_eq!(
+        calculator.eval_line("mode = "),
+        "Error: expected a number, a name or `(` at byte 6"
+    );
+}
+
+#[test]
+fn variables_follow_the_number_type() {
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("type rational");
+    calculator.eval_line("third = 20 / 3");
+    assert_eq!(calculator.eval_line("third"), "20/3");
+    // Values are read back as input in the new type: 20/3 is 6 as an i32
+    assert_eq!(calculator.eval_line("type i32"), "type: i32");
+    assert_eq!(calculator.eval_line("vars"), "third = 6");
+
+    calculator.eval_line("type f64");
+    calculator.eval_line("half = 1 / 2");
+    assert_eq!(
+        calculator.eval_line("type i64"),
+        "type: i64 (dropped half, not a valid i64)"
+    );
+    assert_eq!(calculator.eval_line("vars"), "third = 6");
+}
+
+#[test]
+fn bindings_are_saved_and_loaded() {
+    let dir: ScratchDir = common::scratch_dir("calc_vars");
+    let path: PathBuf = dir.join("session.txt");
+    let path_text: String = path.display().to_string();
+
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("type rational");
+    calculator.eval_line("x = 20");
+    calculator.eval_line("y = x / 6");
+    assert_eq!(
+        calculator.eval_line(&format!("save {}", path_text)),
+        format!("saved 2 variables to {}", path_text)
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "x = 20\ny = 10/3\n");
+
+    let mut restored: Calculator = Calculator::new();
+    restored.eval_line("type rational");
+    restored.eval_line("z = 1");
+    assert_eq!(
+        restored.eval_line(&format!("load {}", path_text)),
+        format!("loaded 2 variables from {}", path_text)
+    );
+    assert_eq!(restored.eval_line("x + y + z"), "73/3");
+
+    // Saved files are calculator input, so they may compute values
+    fs::write(&path, "# comment\na = 2\nb = a ^ 10\n").unwrap();
+    let env: Env<i64> = Env::load(&path, Default::default()).unwrap();
+    assert_eq!(env.get("b"), Some(&1024));
+
+    fs::write(&path, "a = 2\nb + 1\n").unwrap();
+    let error: String = Env::<i64>::load(&path, Default::default())
+        .unwrap_err()
+        .to_string();
+    assert!(error.ends_with(":2: expected `name = value`"), "{}", error);
+    assert!(
+        restored
+            .eval_line("load /no/such/file")
+            .starts_with("Error: can not load /no/such/file")
+    );
+}
@@ -0,0 +1,49 @@
+// Helpers shared by the integration tests. Every test binary compiles its
+// own copy and uses only some of them
+#![allow(dead_code)]
+
+use std::env;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use learn_rust::calc::number::Number;
+use learn_rust::calc::{MathError, Mode, parse_as};
+
+/// A directory for the files of one test, deleted when it is dropped.
+pub struct ScratchDir(PathBuf);
+
+impl Deref for ScratchDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// An empty directory for the files of one test, named after it. The process
+/// id keeps test binaries and CI jobs that run at the same time apart.
+pub fn scratch_dir(name: &str) -> ScratchDir {
+    let dir: PathBuf = env::temp_dir().join(format!("learn_rust_{}_{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    ScratchDir(dir)
+}
+
+/// Parses `input` as an expression over `T` and computes it in checked mode.
+/// The input must parse, the result may be an error.
+pub fn compute<T: Number>(input: &str) -> Result<T, MathError> {
+    parse_as::<T>(input).unwrap().execute_with(Mode::Checked)
+}
+
+/// The result of `compute` as the calculator prints it.
+pub fn shown<T: Number>(input: &str) -> String {
+    compute::<T>(input).unwrap().to_string()
+}
@@ -0,0 +1,70 @@
+// Compile-fail exercises
+// The lessons keep some lines commented out because they would not compile
+// Each file in tests/compile_fail/ is one of those lines in a standalone snippet,
+// its first line names the error rustc must report, e.g. `// error: E0382`
+// The snippets are compiled with the local rustc (or $RUSTC) to check the claim
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+mod common;
+
+use common::ScratchDir;
+
+fn expected_code(source: &str) -> String {
+    let first_line: &str = source.lines().next().unwrap_or("");
+    match first_line.strip_prefix("// error: ") {
+        Some(code) => code.trim().to_string(),
+        None => panic!("snippet must start with `// error: EXXXX`"),
+    }
+}
+
+fn compile(snippet: &Path) -> Output {
+    let rustc: String = env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let name: &str = snippet.file_stem().unwrap().to_str().unwrap();
+    let out_dir: ScratchDir = common::scratch_dir(&format!("compile_fail_{}", name));
+
+    Command::new(rustc)
+        .arg("--edition=2024")
+        .arg("--crate-type=lib")
+        .arg("--emit=metadata")
+        .arg("--error-format=short")
+        .arg("--out-dir")
+        .arg(&*out_dir)
+        .arg(snippet)
+        .output()
+        .expect("failed to run rustc")
+}
+
+#[test]
+fn snippets_fail_with_the_expected_error() {
+    let dir: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/compile_fail");
+    let mut snippets: Vec<PathBuf> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    snippets.sort();
+    assert!(!snippets.is_empty(), "no snippets in {}", dir.display());
+
+    let mut failures: Vec<String> = Vec::new();
+    for snippet in &snippets {
+        let code: String = expected_code(&fs::read_to_string(snippet).unwrap());
+        let output: Output = compile(snippet);
+        let stderr: String = String::from_utf8_lossy(&output.stderr).into_owned();
+        let name: String = snippet.file_name().unwrap().to_string_lossy().into_owned();
+
+        if output.status.success() {
+            failures.push(format!("{}: compiled, expected {}", name, code));
+        } else if !stderr.contains(&format!("error[{}]", code)) {
+            failures.push(format!(
+                "{}: expected {}, rustc said:\n{}",
+                name, code, stderr
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,10 @@
+// error: E0384
+// From basics::vars
+// Variables are immutable by default, so x can not be assigned twice
+
+pub fn vars() {
+    let x: i32 = 5;
+    println!("x has the value {}", x);
+    x = 6;
+    println!("x has the value {}", x);
+}
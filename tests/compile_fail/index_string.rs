@@ -0,0 +1,9 @@
+// error: E0277
+// From collections::index_strings
+// Strings can not be indexed by an integer
+
+pub fn index_strings() {
+    let s1: &str = "🍕🍕🍕🍕🍕";
+    let s2 = s1[0];
+    println!("s2: {}", s2);
+}
@@ -0,0 +1,11 @@
+// error: E0502
+// From ownership::borrow_example
+// A mutable reference can not be taken while an immutable one is still used
+
+pub fn borrow_example() {
+    let mut s: String = String::from("Hello");
+    let r: &String = &s;
+    let r3: &mut String = &mut s;
+    r3.push_str(" World");
+    println!("Borrowed: {}", r);
+}
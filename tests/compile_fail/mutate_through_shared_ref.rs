@@ -0,0 +1,11 @@
+// error: E0596
+// From ownership::borrow_example
+// r is an immutable reference, so the String can not be modified through it
+
+pub fn borrow_example() {
+    let mut s: String = String::from("Hello");
+    let r: &String = &s;
+    r.push_str(" World");
+    println!("Borrowed: {}", r);
+    s.push('!');
+}
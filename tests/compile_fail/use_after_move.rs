@@ -0,0 +1,13 @@
+// error: E0382
+// From ownership::ownership_functions
+// s is moved into takes_ownership, so it can not be used afterwards
+
+fn takes_ownership(some_string: String) {
+    println!("Taking ownership: {}", some_string);
+}
+
+pub fn ownership_functions() {
+    let s: String = String::from("Hello");
+    takes_ownership(s);
+    println!("s: {}", s);
+}
@@ -0,0 +1,9 @@
+// error: E0382
+// From ownership::moved
+// let s2 = s1 moves the String, s1 is no longer valid
+
+pub fn moved() {
+    let s1: String = String::from("Hello");
+    let s2: String = s1;
+    println!("s1: {}, s2: {}", s1, s2);
+}
@@ -0,0 +1,125 @@
+use learn_rust::calc::complex::Complex;
+use learn_rust::calc::env::Env;
+use learn_rust::calc::{Calculator, MathError, Mode, Operation, parse_as};
+
+mod common;
+
+use common::{compute, shown};
+
+#[test]
+fn i_is_the_imaginary_unit() {
+    assert_eq!(compute::<Complex>("4i"), Ok(Complex::new(0.0, 4.0)));
+    assert_eq!(compute::<Complex>("3 + 4i"), Ok(Complex::new(3.0, 4.0)));
+    assert_eq!(compute::<Complex>("1 - 2.5i"), Ok(Complex::new(1.0, -2.5)));
+    assert_eq!(shown::<Complex>("i * i"), "-1");
+    // `4i^2` is 4 * i^2, like `3 m^2` is 3 * m^2
+    assert_eq!(shown::<Complex>("4i^2"), "-4");
+
+    let mut env: Env<Complex> = Env::new();
+    env.set("i", Complex::new(2.0, 0.0));
+    let hidden: Operation<Complex> = parse_as("3i").unwrap();
+    assert_eq!(
+        hidden.execute_in(&env, Mode::Checked),
+        Ok(Complex::new(6.0, 0.0))
+    );
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!(shown::<Complex>("(3+4i) * (1-2i)"), "11 - 2i");
+    assert_eq!(shown::<Complex>("(3 + 4i) + (1 - 4i)"), "4");
+    assert_eq!(shown::<Complex>("(3 + 4i) / (1 + 2i)"), "2.2 - 0.4i");
+    assert_eq!(shown::<Complex>("-(2 - i)"), "-2 + i");
+    assert_eq!(shown::<Complex>("(1 + i)^2"), "2i");
+    assert_eq!(shown::<Complex>("(1 + i)^-2"), "-0.5i");
+    assert_eq!(shown::<Complex>("(-4)^0.5"), "2i");
+    assert_eq!(
+        compute::<Complex>("(1 + i) / 0"),
+        Err(MathError::DivideByZero)
+    );
+    assert_eq!(shown::<Complex>("7 % 4"), "3");
+    assert_eq!(
+        compute::<Complex>("(7 + i) % 4"),
+        Err(MathError::NotReal(String::from("7 + i")))
+    );
+}
+
+#[test]
+fn conjugate_modulus_and_argument() {
+    assert_eq!(shown::<Complex>("conj(3 + 4i)"), "3 - 4i");
+    assert_eq!(shown::<Complex>("abs(3 + 4i)"), "5");
+    assert_eq!(
+        shown::<Complex>("arg(i)"),
+        (std::f64::consts::PI / 2.0).to_string()
+    );
+    assert_eq!(shown::<Complex>("re(3 - 4i) * im(3 - 4i)"), "-12");
+    assert_eq!(shown::<Complex>("(3 + 4i) * conj(3 + 4i)"), "25");
+    assert_eq!(
+        compute::<Complex>("conj(1, 2)"),
+        Err(MathError::ArgumentCount(String::from("conj"), 1))
+    );
+}
+
+#[test]
+fn polar_and_rectangular() {
+    let z: Complex = Complex::from_polar(2.0, std::f64::consts::FRAC_PI_2);
+    assert!((z.re()).abs() < 1e-15 && (z.im() - 2.0).abs() < 1e-15);
+    assert_eq!(z.to_string(), "2i");
+    assert_eq!(shown::<Complex>("polar(1, 3.141592653589793)"), "-1");
+    let back: Complex = compute::<Complex>("polar(abs(1 - i), arg(1 - i))").unwrap();
+    assert!((back.re() - 1.0).abs() < 1e-15 && (back.im() + 1.0).abs() < 1e-15);
+    assert!(matches!(
+        compute::<Complex>("polar(i, 0)"),
+        Err(MathError::NotReal(_))
+    ));
+}
+
+#[test]
+fn printed_operations_keep_sums_together() {
+    let sum: Operation<Complex> = Operation::Mul(
+        Box::new(Operation::Value(Complex::new(3.0, 4.0))),
+        Box::new(Operation::Var(String::from("x"))),
+    );
+    assert_eq!(sum.to_string(), "(3 + 4i) * x");
+    let imaginary: Operation<Complex> = Operation::Pow {
+        base: Box::new(Operation::Value(Complex::new(0.0, 4.0))),
+        exponent: Box::new(Operation::Value(Complex::new(2.0, 0.0))),
+    };
+    assert_eq!(imaginary.to_string(), "(4i)^2");
+    assert_eq!(
+        imaginary.execute_with(Mode::Checked),
+        Ok(Complex::new(-16.0, 0.0))
+    );
+}
+
+#[test]
+fn calculator_computes_with_complex_numbers() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(calculator.eval_line("type complex"), "type: complex");
+    assert_eq!(calculator.eval_line("(3+4i) * (1-2i)"), "11 - 2i");
+    assert_eq!(calculator.eval_line("z = 1 + i"), "z = 1 + i");
+    assert_eq!(calculator.eval_line("z * z"), "2i");
+    assert_eq!(
+        calculator.eval_line("z in polar"),
+        format!("polar({}, {})", 2f64.sqrt(), std::f64::consts::FRAC_PI_4)
+    );
+    calculator.eval_line("errors plain");
+    assert_eq!(
+        calculator.eval_line("z / 0 in polar"),
+        "error[E101]: Can not divide by zero\n  |\n  | z / 0 in polar\n  | ^^^^^\n  = help: \
+         no number type can divide by zero"
+    );
+    calculator.eval_line("errors short");
+    assert_eq!(
+        calculator.eval_line("z % 2"),
+        "Error: `1 + i` is not a real number"
+    );
+    assert_eq!(
+        calculator.eval_line("type f64"),
+        "type: f64 (dropped z, not a valid f64)"
+    );
+    assert_eq!(
+        compute::<f64>("4i"),
+        Err(MathError::UndefinedVariable(String::from("i")))
+    );
+}
@@ -0,0 +1,197 @@
+use learn_rust::calc::diagnostic::{self, Diagnostic, Span, Style};
+use learn_rust::calc::env::Env;
+use learn_rust::calc::vm;
+use learn_rust::calc::{self, Calculator, MathError, Mode, Operation, Statement, parse_spanned};
+
+fn span(start: usize, end: usize) -> Span {
+    Span { start, end }
+}
+
+// The text of every span, in the order the parser recorded them
+fn parts(input: &str) -> Vec<&str> {
+    let (_, spans) = parse_spanned::<i32>(input).unwrap();
+    spans.iter().map(|s| &input[s.start..s.end]).collect()
+}
+
+#[test]
+fn records_a_span_for_every_part_in_post_order() {
+    assert_eq!(parts("1 + 2 * x"), ["1", "2", "x", "2 * x", "1 + 2 * x"]);
+    assert_eq!(
+        parts("-(a - 3)^2"),
+        ["a", "3", "(a - 3)", "2", "(a - 3)^2", "-(a - 3)^2"]
+    );
+    assert_eq!(
+        parts("y = -7 % 3 km"),
+        ["-7", "3", "km", "3 km", "-7 % 3 km"]
+    );
+    assert_eq!(parts("+x << 1"), ["x", "1", "x << 1"]);
+}
+
+#[test]
+fn locates_the_part_that_fails() {
+    let input: &str = "2 * (x + 1) - 10 / (x - 3)";
+    let (statement, spans) = parse_spanned::<i32>(input).unwrap();
+    let Statement::Operation(operation) = statement else {
+        panic!("expected an operation");
+    };
+    let mut env: Env<i32> = Env::new();
+    env.set("x", 3);
+    let found: Option<Span> = diagnostic::locate(&operation, &spans, &env, Mode::Checked);
+    assert_eq!(found.map(|s| &input[s.start..s.end]), Some("10 / (x - 3)"));
+    env.set("x", 4);
+    assert_eq!(
+        diagnostic::locate(&operation, &spans, &env, Mode::Checked),
+        None
+    );
+}
+
+#[test]
+fn underlines_the_token_of_a_parse_error() {
+    let report = |input: &str| Diagnostic::parse(input, &calc::parse(input).unwrap_err());
+    assert_eq!(report("1 + * 2").span, Some(span(4, 5)));
+    assert_eq!(report("1 << 2 3").span, Some(span(7, 8)));
+    assert_eq!(report("(1 + 2").code, Some("E006"));
+    assert_eq!(report("1 +").span, Some(span(3, 3)));
+    assert_eq!(report("-99999999999").span, Some(span(0, 12)));
+    assert_eq!(report("2 ÷ 1").span, Some(span(2, 4)));
+}
+
+#[test]
+fn renders_plain_and_colored_reports() {
+    let diagnostic: Diagnostic =
+        Diagnostic::math(&MathError::DivideByZero, Some(span(4, 9)), "i32");
+    let expected: &str = "\
+error[E101]: Can not divide by zero
+  |
+  | 1 + 2 / 0
+  |     ^^^^^
+  = help: no number type can divide by zero";
+    assert_eq!(diagnostic.render("1 + 2 / 0", Style::Plain), expected);
+    assert_eq!(
+        diagnostic.render("1 + 2 / 0", Style::Short),
+        "Error: Can not divide by zero"
+    );
+
+    let colored: String = diagnostic.render("1 + 2 / 0", Style::Color);
+    assert!(colored.starts_with("\x1b[1;31merror[E101]\x1b[0m"));
+    assert!(colored.contains("\x1b[1;31m    ^^^^^\x1b[0m"));
+    let without_escapes: String = colored
+        .split('\x1b')
+        .map(|part| part.split_once('m').map_or(part, |(_, rest)| rest))
+        .collect();
+    assert_eq!(without_escapes, expected);
+
+    // Columns count characters, not bytes
+    let wide: Diagnostic = Diagnostic::math(
+        &MathError::UndefinedVariable(String::from("ç")),
+        Some(span(7, 9)),
+        "i32",
+    );
+    assert!(
+        wide.render("é + 1 ç", Style::Plain)
+            .contains("\n  | é + 1 ç\n  |       ^\n")
+    );
+}
+
+#[test]
+fn calculator_reports_in_the_chosen_style() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(
+        calculator.eval_line("x = 1 / 0"),
+        "Error: Can not divide by zero"
+    );
+    assert_eq!(calculator.eval_line("errors plain"), "errors: plain");
+    assert_eq!(
+        calculator.eval_line("x = 7 + (2 ^ 40)"),
+        "error[E102]: Result is too large\n  |\n  | x = 7 + (2 ^ 40)\n  |         ^^^^^^^^\n  = help: \
+         `mode wrapping` or `mode saturating` keeps a result, `type` picks a larger type"
+    );
+    assert_eq!(
+        calculator.eval_line("base ten"),
+        "error: unknown base `ten`, expected one of: dec, hex, oct, bin"
+    );
+    assert_eq!(
+        calculator.eval_line("errors loud"),
+        "error: unknown style `loud`, expected one of: short, plain, color"
+    );
+    // The help fits the number type
+    calculator.eval_line("type bigint");
+    assert_eq!(
+        calculator.eval_line("1 & 2"),
+        "error[E107]: Bitwise operations need an integer type\n  |\n  | 1 & 2\n  | ^^^^^\n  = help: \
+         bigint has no bitwise operators, `type i128` has them"
+    );
+    assert!(
+        calculator
+            .eval_line("(-10) ^ 1000000")
+            .ends_with("= help: a power of a bigint may have at most 900000 digits")
+    );
+    calculator.eval_line("type rational");
+    assert!(calculator.eval_line("9223372036854775807 + 1").ends_with(
+        "= help: fractions have an i64 numerator and denominator, `type f64` rounds instead"
+    ));
+    calculator.eval_line("type i128");
+    assert!(calculator.eval_line("2 ^ 127").ends_with(
+        "= help: `mode wrapping` or `mode saturating` keeps a result, `type bigint` has no limit"
+    ));
+    calculator.eval_line("type i32");
+    calculator.eval_line("notation rpn");
+    assert_eq!(
+        calculator.eval_line("1 0 /"),
+        "error[E101]: Can not divide by zero\n  = help: no number type can divide by zero"
+    );
+}
+
+#[test]
+fn vm_tells_which_instruction_failed() {
+    let operation: Operation = calc::parse("(1 + 2) * 3 / (4 - 4)").unwrap();
+    let program: vm::Program<i32> = vm::compile(&operation);
+    assert_eq!(
+        program.run_located(&Env::new(), Mode::Checked),
+        Err((8, MathError::DivideByZero))
+    );
+}
+
+#[test]
+fn command_arguments_are_underlined_in_the_line() {
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("errors plain");
+    assert!(calculator.eval_line("simplify x + * 2").starts_with(
+        "error[E002]: expected a number, a name or `(`\n  |\n  | simplify x + * 2\n  |              ^\n"
+    ));
+    assert!(calculator.eval_line("derive x (x + 1").starts_with(
+        "error[E006]: this `(` is never closed\n  |\n  | derive x (x + 1\n  |          ^\n"
+    ));
+    assert!(
+        calculator
+            .eval_line("dis 1 2")
+            .starts_with("error[E003]: expected one of")
+    );
+    calculator.eval_line("errors short");
+    assert!(calculator.eval_line("dis 1 2").ends_with(" at byte 6"));
+    assert!(calculator.eval_line("bench 1 2").ends_with(" at byte 8"));
+    assert_eq!(
+        calculator.eval_line("derive x 99999999999"),
+        "Error: number is too large for i32 at byte 9"
+    );
+}
+
+#[test]
+fn rpn_errors_underline_the_word() {
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("notation rpn");
+    calculator.eval_line("errors plain");
+    assert_eq!(
+        calculator.eval_line("1 2 ++"),
+        "error[E016]: unknown word `++`\n  |\n  | 1 2 ++\n  |     ^^\n  = help: the words \
+         are numbers, names, operators, neg, dup, swap, drop and clear"
+    );
+    assert!(calculator.eval_line("1  99999999999 +").starts_with(
+        "error[E004]: number is too large for i32\n  |\n  | 1  99999999999 +\n  |    ^^^^^^^^^^^\n"
+    ));
+    calculator.eval_line("errors short");
+    assert_eq!(
+        calculator.eval_line("1 ("),
+        "Error: unknown word `(` at byte 2"
+    );
+}
@@ -0,0 +1,88 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use learn_rust::exercises::{self, Check, Exercise};
+use learn_rust::lessons::LESSONS;
+
+mod common;
+
+use common::ScratchDir;
+
+fn exercise_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("exercises")
+}
+
+// The exercise lives in the returned directory, so keep it until the check ran
+fn scratch_exercise(lesson: &'static str, source: &str) -> (ScratchDir, Exercise) {
+    let dir: ScratchDir = common::scratch_dir(&format!("exercise_test_{}", lesson));
+    let path: PathBuf = dir.join(format!("{}.rs", lesson));
+    fs::write(&path, source).unwrap();
+    (dir, Exercise { lesson, path })
+}
+
+#[test]
+fn every_lesson_has_an_exercise() {
+    let found: Vec<Exercise> = exercises::exercises(&exercise_dir());
+    assert_eq!(found.len(), LESSONS.len());
+}
+
+#[test]
+fn exercises_compile_before_they_are_solved() {
+    let rustc: String = env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let out_dir: ScratchDir = common::scratch_dir("exercise_metadata");
+    for exercise in exercises::exercises(&exercise_dir()) {
+        let output = Command::new(&rustc)
+            .args([
+                "--edition=2024",
+                "--test",
+                "--emit=metadata",
+                "-A",
+                "warnings",
+            ])
+            .arg("--out-dir")
+            .arg(&*out_dir)
+            .arg(&exercise.path)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}:\n{}",
+            exercise.lesson,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+#[test]
+fn unsolved_exercise_fails_its_tests() {
+    let path: &Path = &exercise_dir().join("add.rs");
+    let exercise: Exercise = Exercise {
+        lesson: "add",
+        path: path.to_path_buf(),
+    };
+    assert!(matches!(
+        exercises::check(&exercise).unwrap(),
+        Check::TestsFailed(_)
+    ));
+}
+
+#[test]
+fn solved_exercise_passes() {
+    let (_dir, exercise): (ScratchDir, Exercise) = scratch_exercise(
+        "makes_copy",
+        "fn double(x: i32) -> i32 { x * 2 }\n#[test]\nfn t() { assert_eq!(double(2), 4); }\n",
+    );
+    assert_eq!(exercises::check(&exercise).unwrap(), Check::Passed);
+}
+
+#[test]
+fn compile_errors_are_reported() {
+    let (_dir, exercise): (ScratchDir, Exercise) =
+        scratch_exercise("moved", "fn broken() -> i32 { \"no\" }\n");
+    match exercises::check(&exercise).unwrap() {
+        Check::CompileError(errors) => assert!(errors.contains("E0308"), "{}", errors),
+        other => panic!("expected a compile error, got {:?}", other),
+    }
+}
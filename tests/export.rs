@@ -0,0 +1,56 @@
+use std::fs;
+use std::path::PathBuf;
+
+use learn_rust::export::{self, Format};
+use learn_rust::lessons::{self, LESSONS};
+
+mod common;
+
+use common::ScratchDir;
+
+#[test]
+fn every_lesson_has_text_and_source() {
+    for lesson in LESSONS {
+        assert!(!lesson.text().is_empty(), "{} has no text", lesson.name);
+        assert!(
+            lesson
+                .source()
+                .contains(&format!("pub fn {}(", lesson.name)),
+            "{} has no source",
+            lesson.name
+        );
+        assert!(lesson.source().ends_with("}\n"), "{}", lesson.name);
+    }
+}
+
+#[test]
+fn lesson_page_has_text_code_and_output() {
+    let lesson = lessons::find("slice_example").unwrap();
+    let page: String = export::render_lesson(lesson, None, None, Format::Markdown).unwrap();
+    assert!(page.starts_with("# slice_example\n"));
+    assert!(page.contains("```rust\n// Slices\n"));
+    assert!(page.contains("```text\nSlice: [2, 3, 4]\n```"));
+}
+
+#[test]
+fn html_is_escaped() {
+    assert_eq!(
+        export::escape_html("<a href=\"x\">&</a>"),
+        "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+    );
+    let lesson = lessons::find("borrow_example").unwrap();
+    let page: String = export::render_lesson(lesson, None, None, Format::Html).unwrap();
+    assert!(page.contains("&amp;mut dyn Write"));
+}
+
+#[test]
+fn export_writes_an_index_and_one_page_per_lesson() {
+    let scratch: ScratchDir = common::scratch_dir("export");
+    let dir: PathBuf = scratch.join("book");
+    let pages: Vec<PathBuf> = export::export(&dir, Format::Html).unwrap();
+    assert_eq!(pages.len(), LESSONS.len() + 1);
+    let index: String = fs::read_to_string(dir.join("index.html")).unwrap();
+    for lesson in LESSONS {
+        assert!(index.contains(&format!("href=\"{}.html\"", lesson.name)));
+    }
+}
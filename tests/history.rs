@@ -0,0 +1,181 @@
+use std::fs;
+use std::path::PathBuf;
+
+use learn_rust::calc::lexer::{self, TokenKind};
+use learn_rust::calc::{self, Calculator, Notation, Operation};
+
+mod common;
+
+use common::ScratchDir;
+
+#[test]
+fn results_are_numbered() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(calculator.eval_line("history"), "no history");
+    assert_eq!(calculator.eval_line("2 + 3"), "5");
+    assert_eq!(calculator.eval_line("x = $1 * 4"), "x = 20");
+    assert_eq!(calculator.eval_line("$2 - $1"), "15");
+    // Errors and commands get no number
+    calculator.eval_line("1 / 0");
+    calculator.eval_line("base hex");
+    assert_eq!(calculator.eval_line("$3 + 1"), "0x10");
+    assert_eq!(
+        calculator.eval_line("history"),
+        "$1 = 5   (2 + 3)\n$2 = 20   (x = $1 * 4)\n$3 = 15   ($2 - $1)\n$4 = 0x10   ($3 + 1)"
+    );
+    assert_eq!(calculator.eval_line("vars"), "x = 20");
+    assert_eq!(calculator.eval_line("$9"), "Error: `$9` is not defined");
+}
+
+#[test]
+fn history_references_are_tokens() {
+    let kinds: Vec<TokenKind> = lexer::tokenize("$12+x")
+        .unwrap()
+        .into_iter()
+        .map(|token| token.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            TokenKind::History("$12"),
+            TokenKind::Plus,
+            TokenKind::Ident("x")
+        ]
+    );
+    let operation: Operation = calc::parse("$1 * 2").unwrap();
+    assert_eq!(operation.to_string(), "$1 * 2");
+    assert!(calc::parse("$x").is_err());
+    assert!(calc::parse_statement_as::<i32>("$1 = 2").is_err());
+
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("6");
+    calculator.notation = Notation::Rpn;
+    assert_eq!(calculator.eval_line("$1 $1 *"), "stack: 36");
+    // The top of the stack is the result of an RPN line
+    assert_eq!(calculator.eval_line("1"), "stack: 36 1");
+    assert_eq!(
+        calculator.eval_line("history"),
+        "$1 = 6   (6)\n$2 = 36   ($1 $1 *)\n$3 = 1   (1)"
+    );
+}
+
+#[test]
+fn conversions_are_numbered() {
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("type quantity");
+    assert_eq!(calculator.eval_line("2 km in m"), "2000 m");
+    assert_eq!(calculator.eval_line("$1 * 2"), "4000 m");
+    calculator.eval_line("type complex");
+    assert_eq!(
+        calculator.eval_line("-1 in polar"),
+        format!("polar(1, {})", std::f64::consts::PI)
+    );
+    assert_eq!(calculator.eval_line("$3 * 2"), "-2");
+    assert_eq!(
+        calculator.eval_line("history"),
+        format!(
+            "$1 = 2000 m   (2 km in m, dropped by `type`)\n$2 = 4000 m   ($1 * 2, dropped by `type`)\n$3 = polar(1, {})   (-1 in polar)\n$4 = -2   ($3 * 2)",
+            std::f64::consts::PI
+        )
+    );
+}
+
+#[test]
+fn undo_reverts_assignments() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(calculator.eval_line("undo"), "Error: nothing to undo");
+    calculator.eval_line("x = 1");
+    calculator.eval_line("y = 2");
+    calculator.eval_line("x = x + y");
+    assert_eq!(calculator.eval_line("undo"), "undo: x = 1");
+    assert_eq!(calculator.eval_line("x + y"), "3");
+    assert_eq!(calculator.eval_line("undo"), "undo: removed y");
+    assert_eq!(calculator.eval_line("vars"), "x = 1");
+    // The results stay in the history
+    assert_eq!(calculator.eval_line("$3"), "3");
+}
+
+#[test]
+fn changing_the_type_keeps_undo_and_marks_dropped_results() {
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("x = 1");
+    assert_eq!(calculator.eval_line("type f64"), "type: f64");
+    assert_eq!(calculator.eval_line("undo"), "undo: removed x");
+    assert_eq!(calculator.eval_line("$1"), "1");
+
+    calculator.eval_line("y = 0.5");
+    calculator.eval_line("y = 2");
+    assert_eq!(
+        calculator.eval_line("type i32"),
+        "type: i32 (undo lost a step)"
+    );
+    assert_eq!(calculator.eval_line("undo"), "undo: removed y");
+    assert_eq!(
+        calculator.eval_line("history"),
+        "$1 = 1   (x = 1)\n$2 = 1   ($1)\n$3 = 0.5   (y = 0.5, dropped by `type`)\n$4 = 2   (y = 2)"
+    );
+}
+
+#[test]
+fn transcripts_resume_a_session() {
+    let dir: ScratchDir = common::scratch_dir("calc_transcript");
+    let path: PathBuf = dir.join("transcript.txt");
+    let path_text: String = path.display().to_string();
+
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("type rational");
+    calculator.eval_line("x = 1/3");
+    calculator.eval_line("x / 0");
+    calculator.eval_line("$1 * 2");
+    assert_eq!(
+        calculator.eval_line(&format!("transcript save {}", path_text)),
+        format!("saved 3 lines to {}", path_text)
+    );
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "> type rational\ntype: rational\n> x = 1/3\nx = 1/3\n> $1 * 2\n2/3\n"
+    );
+
+    let mut resumed: Calculator = Calculator::new();
+    assert_eq!(
+        resumed.eval_line(&format!("transcript load {}", path_text)),
+        format!("replayed 3 lines from {}", path_text)
+    );
+    assert_eq!(resumed.eval_line("$2 + x"), "1");
+    assert_eq!(resumed.eval_line("undo"), "undo: removed x");
+
+    // `$1` would point at another result in a session that has some
+    assert_eq!(
+        resumed.eval_line(&format!("transcript load {}", path_text)),
+        "Error: `transcript load` needs a session without variables and results"
+    );
+    assert_eq!(
+        resumed.eval_line("transcript"),
+        "Error: `transcript` expects `save <file>` or `load <file>`"
+    );
+}
+
+#[test]
+fn replaying_skips_transcript_and_save_commands() {
+    let dir: ScratchDir = common::scratch_dir("calc_replay");
+    let path: PathBuf = dir.join("transcript.txt");
+    let saved: PathBuf = dir.join("vars.txt");
+    fs::write(
+        &path,
+        format!(
+            "> transcripts = 4\n> transcript_total = 1\n> y / 0\n> save {}\n> transcript load x\n> save = 2\nignored\n",
+            saved.display()
+        ),
+    )
+    .unwrap();
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(
+        calculator.eval_line(&format!("transcript load {}", path.display())),
+        format!("replayed 4 lines from {}, 1 failed", path.display())
+    );
+    assert_eq!(
+        calculator.eval_line("transcripts + transcript_total + save"),
+        "7"
+    );
+    assert!(!saved.exists());
+}
@@ -0,0 +1,86 @@
+use learn_rust::export::{self, Format};
+use learn_rust::i18n::{self, Lang};
+use learn_rust::lessons::{self, LESSONS};
+
+#[test]
+fn every_catalog_covers_every_key() {
+    for lang in Lang::ALL {
+        assert_eq!(
+            i18n::missing_keys(lang),
+            Vec::<&str>::new(),
+            "{}",
+            lang.code()
+        );
+        assert_eq!(
+            i18n::unknown_keys(lang),
+            Vec::<&str>::new(),
+            "{}",
+            lang.code()
+        );
+    }
+}
+
+#[test]
+fn every_lesson_is_described_in_every_language() {
+    for lang in Lang::ALL {
+        i18n::set_lang(lang);
+        for lesson in LESSONS {
+            assert!(
+                !lesson.description().is_empty(),
+                "{} {}",
+                lang.code(),
+                lesson.name
+            );
+            assert!(!lesson.text().is_empty(), "{} {}", lang.code(), lesson.name);
+        }
+    }
+    i18n::set_lang(Lang::En);
+}
+
+#[test]
+fn lessons_print_in_the_selected_language() {
+    let lesson = lessons::find("vars").unwrap();
+
+    i18n::set_lang(Lang::PtBr);
+    let output: String = lesson.output().unwrap();
+    i18n::set_lang(Lang::En);
+
+    assert!(output.starts_with("x tem o valor 5\n"), "{}", output);
+    assert!(lesson.output().unwrap().starts_with("x has the value 5\n"));
+}
+
+#[test]
+fn exported_pages_use_the_selected_language() {
+    let lesson = lessons::find("loops").unwrap();
+
+    i18n::set_lang(Lang::PtBr);
+    let page: String = export::render_lesson(lesson, None, None, Format::Markdown).unwrap();
+    i18n::set_lang(Lang::En);
+
+    assert!(page.contains("*Básico*"), "{}", page);
+    assert!(page.contains("## Código\n"));
+    assert!(page.contains("Contador: 1\n"));
+}
+
+#[test]
+fn placeholders_are_filled_in_order() {
+    assert_eq!(
+        i18n::format("vars.has_value", &[&"y", &6]),
+        "y has the value 6"
+    );
+    // Missing arguments keep their placeholder, unknown keys are shown as is
+    assert_eq!(
+        i18n::format("vars.has_value", &[&"y"]),
+        "y has the value {}"
+    );
+    assert_eq!(i18n::text("no.such.key"), "no.such.key");
+}
+
+#[test]
+fn locale_codes() {
+    assert_eq!(Lang::from_code("en"), Some(Lang::En));
+    assert_eq!(Lang::from_code("en_US.UTF-8"), Some(Lang::En));
+    assert_eq!(Lang::from_code("pt-BR"), Some(Lang::PtBr));
+    assert_eq!(Lang::from_code("pt_BR.UTF-8"), Some(Lang::PtBr));
+    assert_eq!(Lang::from_code("fr"), None);
+}
@@ -0,0 +1,74 @@
+// Golden snapshot tests
+// Each lesson's output is compared with tests/snapshots/<lesson>.txt
+// Run with UPDATE_SNAPSHOTS=1 to (re)write the files after an intended change
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use learn_rust::lessons::LESSONS;
+
+// HashMap iteration order changes between runs, so these snapshots
+// are compared after sorting their lines and the entries of `{..}` maps
+const UNORDERED: [&str; 3] = ["hashmap_example", "hashmap_example2", "hashmap_example3"];
+
+fn normalize(lesson: &str, output: &str) -> String {
+    if !UNORDERED.contains(&lesson) {
+        return output.to_string();
+    }
+    let mut lines: Vec<String> = output
+        .lines()
+        .map(
+            |line| match line.strip_prefix('{').and_then(|l| l.strip_suffix('}')) {
+                Some(entries) => {
+                    let mut entries: Vec<&str> = entries.split(", ").collect();
+                    entries.sort();
+                    format!("{{{}}}", entries.join(", "))
+                }
+                None => line.to_string(),
+            },
+        )
+        .collect();
+    lines.sort();
+    lines.join("\n")
+}
+
+#[test]
+fn lessons_match_snapshots() {
+    let dir: PathBuf = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots");
+    let update: bool = env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let mut failures: Vec<&str> = Vec::new();
+
+    for lesson in LESSONS {
+        let mut buf: Vec<u8> = Vec::new();
+        (lesson.run)(&mut buf).unwrap();
+        let actual: String = String::from_utf8(buf).unwrap();
+        let path: PathBuf = dir.join(format!("{}.txt", lesson.name));
+
+        if update {
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(&path, &actual).unwrap();
+            continue;
+        }
+
+        let expected: String = fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("missing snapshot {}", path.display()));
+        if normalize(lesson.name, &expected) != normalize(lesson.name, &actual) {
+            eprintln!(
+                "--- {} expected\n{}--- actual\n{}",
+                lesson.name, expected, actual
+            );
+            failures.push(lesson.name);
+        }
+    }
+
+    assert!(failures.is_empty(), "snapshot mismatch: {:?}", failures);
+}
+
+#[test]
+fn every_lesson_has_a_unique_name() {
+    let mut names: Vec<&str> = LESSONS.iter().map(|l| l.name).collect();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), LESSONS.len());
+}
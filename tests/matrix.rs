@@ -0,0 +1,198 @@
+use learn_rust::calc::matrix::{Grid, Matrix};
+use learn_rust::calc::rational::Rational;
+use learn_rust::calc::{
+    self, Calculator, MathError, Mode, NumberType, Operation, ParseErrorKind, parse_as,
+};
+
+mod common;
+
+use common::{compute, shown};
+
+#[test]
+fn literals_are_vectors_and_rows() {
+    let Ok(Matrix::Grid(grid)) = compute::<Matrix>("[[1, 2, 3], [4, 5, 6]]") else {
+        panic!("expected a matrix");
+    };
+    assert_eq!((grid.rows(), grid.cols()), (2, 3));
+    assert_eq!(grid.get(1, 0), Rational::from_integer(4));
+    assert_eq!(shown::<Matrix>("[1, -2, 3/2]"), "[1, -2, 3/2]");
+    assert_eq!(shown::<Matrix>("[[1, 2]]"), "[[1, 2]]");
+    assert_eq!(shown::<Matrix>("[0.5, 1_000]"), "[1/2, 1000]");
+    assert_eq!(
+        compute::<Matrix>("3 / 4"),
+        Ok(Matrix::Scalar(Rational::new(3, 4).unwrap()))
+    );
+    assert_eq!(Grid::identity(2).unwrap().to_string(), "[[1, 0], [0, 1]]");
+    assert_eq!(Grid::identity(0), None);
+}
+
+#[test]
+fn invalid_literals_are_parse_errors() {
+    let kind = |input: &str| parse_as::<Matrix>(input).unwrap_err().kind;
+    assert_eq!(
+        kind("[[1, 2], [3]]"),
+        ParseErrorKind::InvalidMatrix("the rows must be as long")
+    );
+    assert_eq!(
+        kind("[]"),
+        ParseErrorKind::InvalidMatrix("it needs at least one entry")
+    );
+    assert_eq!(
+        kind("[1, x]"),
+        ParseErrorKind::InvalidMatrix("entries must be numbers")
+    );
+    assert_eq!(kind("[1, 2"), ParseErrorKind::UnclosedBracket);
+    assert_eq!(
+        calc::parse("[1, 2]").unwrap_err().kind,
+        ParseErrorKind::NoMatrices("i32")
+    );
+}
+
+#[test]
+fn arithmetic_follows_the_shapes() {
+    assert_eq!(
+        shown::<Matrix>("[[1, 2], [3, 4]] * [[5, 6], [7, 8]]"),
+        "[[19, 22], [43, 50]]"
+    );
+    assert_eq!(shown::<Matrix>("[[1, 2], [3, 4]] * [1, 1]"), "[3, 7]");
+    assert_eq!(shown::<Matrix>("[1, 2] + [3, 4] * 2"), "[7, 10]");
+    assert_eq!(shown::<Matrix>("[[2, 4]] / 4"), "[[1/2, 1]]");
+    assert_eq!(
+        shown::<Matrix>("emul([[1, 2], [3, 4]], [[5, 6], [7, 8]])"),
+        "[[5, 12], [21, 32]]"
+    );
+    assert_eq!(shown::<Matrix>("[[1, 1], [0, 1]] ^ 5"), "[[1, 5], [0, 1]]");
+    assert_eq!(shown::<Matrix>("[[2, 0], [0, 2]] ^ 0"), "[[1, 0], [0, 1]]");
+    assert_eq!(
+        compute::<Matrix>("[1, 2] + [1, 2, 3]"),
+        Err(MathError::ShapeMismatch(
+            String::from("2x1 matrix"),
+            String::from("3x1 matrix")
+        ))
+    );
+    assert!(matches!(
+        compute::<Matrix>("[1, 2] * [1, 2]"),
+        Err(MathError::ShapeMismatch(..))
+    ));
+    assert!(matches!(
+        compute::<Matrix>("1 + [1]"),
+        Err(MathError::ShapeMismatch(..))
+    ));
+}
+
+#[test]
+fn transpose_determinant_and_inverse() {
+    assert_eq!(
+        shown::<Matrix>("transpose([[1, 2, 3], [4, 5, 6]])"),
+        "[[1, 4], [2, 5], [3, 6]]"
+    );
+    assert_eq!(shown::<Matrix>("transpose([1, 2])"), "[[1, 2]]");
+    assert_eq!(shown::<Matrix>("det([[1, 2], [3, 4]])"), "-2");
+    assert_eq!(
+        shown::<Matrix>("det([[0, 1, 2], [1, 0, 3], [4, -3, 8]])"),
+        "-2"
+    );
+    assert_eq!(shown::<Matrix>("det([[1, 2], [2, 4]])"), "0");
+    assert_eq!(
+        shown::<Matrix>("inv([[1, 2], [3, 4]])"),
+        "[[-2, 1], [3/2, -1/2]]"
+    );
+    assert_eq!(
+        shown::<Matrix>("[[1, 2], [3, 4]] ^ -1"),
+        shown::<Matrix>("inv([[1, 2], [3, 4]])")
+    );
+    assert_eq!(
+        shown::<Matrix>("[[0, 1], [1, 0]] * inv([[0, 1], [1, 0]])"),
+        "[[1, 0], [0, 1]]"
+    );
+    assert_eq!(
+        compute::<Matrix>("inv([[1, 2], [2, 4]])"),
+        Err(MathError::SingularMatrix)
+    );
+    // Dividing by a matrix is multiplying by its inverse
+    assert_eq!(
+        compute::<Matrix>("[[1, 2], [3, 4]] / [[1, 2], [3, 4]]"),
+        Err(MathError::DivideByMatrix)
+    );
+    assert_eq!(
+        compute::<Matrix>("2 / [[1, 2], [3, 4]]"),
+        Err(MathError::DivideByMatrix)
+    );
+    assert_eq!(
+        compute::<Matrix>("det([[1, 2, 3]])"),
+        Err(MathError::NotSquare(String::from("1x3 matrix")))
+    );
+}
+
+#[test]
+fn solves_linear_systems() {
+    // x + y = 3 and 2x - y = 0
+    assert_eq!(
+        shown::<Matrix>("solve([[1, 1], [2, -1]], [3, 0])"),
+        "[1, 2]"
+    );
+    assert_eq!(
+        shown::<Matrix>("solve([[2, 0], [0, 3]], [1, 1])"),
+        "[1/2, 1/3]"
+    );
+    assert_eq!(shown::<Matrix>("solve(4, 2)"), "1/2");
+    assert_eq!(
+        compute::<Matrix>("solve([[1, 2], [2, 4]], [1, 1])"),
+        Err(MathError::SingularMatrix)
+    );
+    assert_eq!(
+        compute::<Matrix>("solve([[1, 0], [0, 1]])"),
+        Err(MathError::ArgumentCount(String::from("solve"), 2))
+    );
+    assert_eq!(
+        compute::<Matrix>("trace([1])"),
+        Err(MathError::UnknownFunction(String::from("trace")))
+    );
+}
+
+#[test]
+fn functions_are_calls_for_every_type() {
+    assert_eq!(calc::parse("f(1, x)").unwrap().to_string(), "f(1, x)");
+    let call: Operation = calc::parse("det(2)").unwrap();
+    assert_eq!(
+        call.execute_with(Mode::Checked),
+        Err(MathError::UnknownFunction(String::from("det")))
+    );
+}
+
+#[test]
+fn calculator_keeps_matrices_in_variables() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(calculator.eval_line("type matrix"), "type: matrix");
+    assert_eq!(calculator.number(), NumberType::Matrix);
+    assert_eq!(
+        calculator.eval_line("a = [[2, 1], [1, 1]]"),
+        "a = [[2, 1], [1, 1]]"
+    );
+    assert_eq!(calculator.eval_line("b = [3, 2]"), "b = [3, 2]");
+    assert_eq!(calculator.eval_line("solve(a, b)"), "[1, 1]");
+    assert_eq!(calculator.eval_line("inv(a) * b"), "[1, 1]");
+    assert_eq!(calculator.eval_line("det(a) + 1"), "2");
+    assert_eq!(
+        calculator.eval_line("inv(a - a)"),
+        "Error: The matrix is singular and has no inverse"
+    );
+}
+
+#[test]
+fn simplify_keeps_the_order_of_products() {
+    let simplified =
+        |input: &str| calc::algebra::simplify(&parse_as::<Matrix>(input).unwrap()).to_string();
+    assert_eq!(simplified("b * a"), "b * a");
+    assert_eq!(
+        simplified("[[1, 2], [3, 4]] * x * [[0, 1], [1, 0]]"),
+        "[[1, 2], [3, 4]] * x * [[0, 1], [1, 0]]"
+    );
+    assert_eq!(
+        simplified("[[1, 2], [3, 4]] * [[0, 1], [1, 0]] * x"),
+        "[[2, 1], [4, 3]] * x"
+    );
+    assert_eq!(simplified("0 * m"), "0 * m");
+    assert_eq!(simplified("x - x"), "x - x");
+    assert_eq!(simplified("b + a"), "a + b");
+}
@@ -0,0 +1,190 @@
+use learn_rust::calc::number::{Base, Number};
+use learn_rust::calc::vm;
+use learn_rust::calc::{
+    self, Calculator, MathError, Mode, NumberType, Operation, ParseError, ParseErrorKind, parse_as,
+};
+
+fn eval(calculator: &mut Calculator, lines: &[&str]) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| calculator.eval_line(line))
+        .collect()
+}
+
+#[test]
+fn reads_the_literals_of_data_types() {
+    assert_eq!(parse_as::<i32>("0xff").unwrap().execute(), Ok(255));
+    assert_eq!(parse_as::<i32>("0o77").unwrap().execute(), Ok(63));
+    assert_eq!(parse_as::<i32>("0b1111_0000").unwrap().execute(), Ok(240));
+    assert_eq!(
+        parse_as::<i64>("1_000_000").unwrap().execute(),
+        Ok(1_000_000)
+    );
+    // The bits of two's complement, like `0xffu8 as i8`
+    assert_eq!(parse_as::<i8>("0xff").unwrap().execute(), Ok(-1));
+    assert_eq!(parse_as::<i8>("-0x80").unwrap().execute(), Ok(i8::MIN));
+    assert_eq!(
+        parse_as::<i8>("0x100"),
+        Err(ParseError {
+            kind: ParseErrorKind::NumberTooLarge("i8"),
+            offset: 0
+        })
+    );
+    assert_eq!(
+        parse_as::<i32>("1 + 0b102"),
+        Err(ParseError {
+            kind: ParseErrorKind::InvalidDigit("bin"),
+            offset: 4
+        })
+    );
+    assert_eq!(
+        parse_as::<f64>("0x10"),
+        Err(ParseError {
+            kind: ParseErrorKind::NotDecimal("f64"),
+            offset: 0
+        })
+    );
+}
+
+#[test]
+fn bitwise_operators_bind_like_in_rust() {
+    let cases: [(&str, i32); 7] = [
+        ("0b1100 & 0b1010", 0b1000),
+        ("0b1100 | 0b1010", 0b1110),
+        ("0b1100 xor 0b1010", 0b0110),
+        ("1 << 2 + 1", 8),
+        ("-16 >> 2", -4),
+        ("1 | 2 xor 5 & 6", 1 | (2 ^ (5 & 6))),
+        ("(1 | 2) << 4", 48),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(
+            parse_as::<i32>(input).unwrap().execute(),
+            Ok(expected),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn prints_with_minimal_parentheses() {
+    for input in [
+        "1 | 2 xor 3 & 4",
+        "(1 | 2) & 3",
+        "x << y + 1",
+        "(x << 1) + 1",
+        "-(a & b)",
+    ] {
+        assert_eq!(calc::parse(input).unwrap().to_string(), input);
+    }
+}
+
+#[test]
+fn shifts_follow_the_mode() {
+    let shift = |input: &str, mode: Mode| parse_as::<i8>(input).unwrap().execute_with(mode);
+    assert_eq!(shift("1 << 7", Mode::Checked), Ok(i8::MIN));
+    assert_eq!(shift("1 << 8", Mode::Checked), Err(MathError::InvalidShift));
+    assert_eq!(
+        shift("1 << 8", Mode::Saturating),
+        Err(MathError::InvalidShift)
+    );
+    assert_eq!(shift("1 << 9", Mode::Wrapping), Ok(2));
+    assert_eq!(
+        shift("1 >> -1", Mode::Wrapping),
+        Err(MathError::InvalidShift)
+    );
+}
+
+#[test]
+fn only_integers_have_bits() {
+    assert_eq!(
+        parse_as::<f64>("1 & 2").unwrap().execute(),
+        Err(MathError::NotAnInteger)
+    );
+    assert_eq!(
+        2.5f64.bit_or(&1.0, Mode::Checked),
+        Err(MathError::NotAnInteger)
+    );
+    assert_eq!(2.5f64.to_base(Base::Hex), "2.5");
+}
+
+#[test]
+fn writes_every_width_in_every_base() {
+    assert_eq!((-42i8).to_base(Base::Hex), "0xd6");
+    assert_eq!((-1i16).to_base(Base::Octal), "0o177777");
+    assert_eq!(240i32.to_base(Base::Binary), "0b11110000");
+    assert_eq!(i64::MIN.to_base(Base::Hex), "0x8000000000000000");
+    assert_eq!(
+        (1i128 << 100).to_base(Base::Decimal),
+        (1i128 << 100).to_string()
+    );
+    assert_eq!(Base::from_name("oct"), Some(Base::Octal));
+}
+
+#[test]
+fn vm_runs_bitwise_operations() {
+    let operation: Operation<i64> = parse_as("(0xf0 | x) >> 4 xor 1").unwrap();
+    let program: vm::Program<i64> = vm::compile(&operation);
+    let mut env = calc::env::Env::new();
+    env.set("x", 0x0f);
+    assert_eq!(
+        program.run(&env, Mode::Checked),
+        operation.execute_in(&env, Mode::Checked)
+    );
+    assert!(program.to_string().contains("or\n"));
+}
+
+#[test]
+fn calculator_in_programmer_mode() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(
+        eval(
+            &mut calculator,
+            &[
+                "type i8",
+                "base hex",
+                "0x7f + 1",
+                "mode wrapping",
+                "0x7f + 1",
+                "-42"
+            ]
+        ),
+        [
+            "type: i8",
+            "base: hex",
+            "Error: Result is too large",
+            "mode: wrapping",
+            "0x80",
+            "0xd6"
+        ]
+    );
+    assert_eq!(calculator.number(), NumberType::I8);
+    assert_eq!(
+        eval(
+            &mut calculator,
+            &[
+                "x = 0b1111_0000",
+                "base bin",
+                "x >> 4",
+                "vars",
+                "base dec",
+                "type i128",
+                "1 << 100"
+            ]
+        ),
+        [
+            "x = 0xf0",
+            "base: bin",
+            "0b11111111",
+            "x = -16",
+            "base: dec",
+            "type: i128",
+            "1267650600228229401496703205376"
+        ]
+    );
+    assert_eq!(
+        calculator.eval_line("base 7"),
+        "Error: unknown base `7`, expected one of: dec, hex, oct, bin"
+    );
+}
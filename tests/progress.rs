@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use learn_rust::lessons::Topic;
+use learn_rust::progress::{Progress, Status, TopicSummary};
+use learn_rust::quiz::Score;
+
+mod common;
+
+use common::ScratchDir;
+
+#[test]
+fn a_lesson_is_completed_after_running_it_and_a_perfect_quiz() {
+    let mut progress: Progress = Progress::default();
+    progress.record_run("moved", 100);
+    assert_eq!(progress.get("moved").unwrap().status, Status::Started);
+
+    progress.record_quiz(
+        "moved",
+        Score {
+            correct: 1,
+            total: 1,
+        },
+        200,
+    );
+    let entry = progress.get("moved").unwrap();
+    assert_eq!(entry.status, Status::Completed);
+    assert_eq!(entry.attempts, 2);
+    assert_eq!((entry.first_seen, entry.last_seen), (100, 200));
+}
+
+#[test]
+fn summary_counts_per_topic() {
+    let mut progress: Progress = Progress::default();
+    progress.record_run("vars", 1);
+    progress.record_run("not_a_lesson", 1);
+
+    let summary: Vec<TopicSummary> = progress.summary();
+    let basics: &TopicSummary = summary.iter().find(|s| s.topic == Topic::Basics).unwrap();
+    assert_eq!((basics.completed, basics.started, basics.total), (0, 1, 6));
+}
+
+#[test]
+fn save_and_load_round_trip() {
+    let dir: ScratchDir = common::scratch_dir("progress");
+    let path: PathBuf = dir.join("round_trip.tsv");
+    let mut progress: Progress = Progress::default();
+    progress.record_run("loops", 5);
+    progress.record_quiz(
+        "vars",
+        Score {
+            correct: 1,
+            total: 2,
+        },
+        6,
+    );
+
+    progress.save(&path).unwrap();
+    assert_eq!(Progress::load(&path).unwrap(), progress);
+}
@@ -0,0 +1,64 @@
+use learn_rust::lessons;
+use learn_rust::quiz::{self, Answer, QUESTIONS, Question, Score};
+
+#[test]
+fn every_question_belongs_to_a_lesson() {
+    for question in QUESTIONS {
+        assert!(
+            lessons::find(question.lesson).is_some(),
+            "unknown lesson {}",
+            question.lesson
+        );
+        if let Answer::Choice { options, correct } = question.answer {
+            assert!(correct < options.len(), "{}", question.prompt);
+        }
+    }
+}
+
+#[test]
+fn every_lesson_has_a_question() {
+    for lesson in lessons::LESSONS {
+        assert!(
+            QUESTIONS.iter().any(|q| q.lesson == lesson.name),
+            "no question for {}",
+            lesson.name
+        );
+    }
+}
+
+#[test]
+fn output_questions_use_the_lesson_output() {
+    let question: &Question = quiz::questions_for("moved")[0];
+    assert_eq!(question.expected().unwrap(), "s2: Hello, s3: Hello");
+    assert!(question.check("  s2: Hello, s3: Hello\n").unwrap());
+}
+
+#[test]
+fn ask_scores_answers() {
+    let questions: Vec<&Question> = quiz::questions_for("calc_area_rect2");
+    let mut input: &[u8] = b"1\nArea of square: 100\n";
+    let mut out: Vec<u8> = Vec::new();
+    let score: Score = Score::of(&quiz::ask(&questions, &mut input, &mut out).unwrap());
+    assert_eq!(
+        score,
+        Score {
+            correct: 2,
+            total: 2
+        }
+    );
+}
+
+#[test]
+fn ask_stops_when_input_ends() {
+    let questions: Vec<&Question> = quiz::questions_for("enums");
+    let mut input: &[u8] = b"wrong\n";
+    let mut out: Vec<u8> = Vec::new();
+    let score: Score = Score::of(&quiz::ask(&questions, &mut input, &mut out).unwrap());
+    assert_eq!(
+        score,
+        Score {
+            correct: 0,
+            total: 1
+        }
+    );
+}
@@ -0,0 +1,73 @@
+use learn_rust::calc::rational::Rational;
+use learn_rust::calc::{Calculator, FractionStyle};
+
+fn r(numer: i64, denom: i64) -> Rational {
+    Rational::new(numer, denom).unwrap()
+}
+
+#[test]
+fn compares_exactly() {
+    assert!(r(1, 3) < r(1, 2));
+    assert!(r(-1, 2) < r(-1, 3));
+    assert!(r(2, 4) == r(1, 2));
+    assert!(r(i64::MAX, 3) > r(i64::MAX - 1, 3));
+    let mut fractions: Vec<Rational> = vec![r(3, 4), r(-5, 2), r(2, 3), r(0, 1)];
+    fractions.sort();
+    assert_eq!(fractions, vec![r(-5, 2), r(0, 1), r(2, 3), r(3, 4)]);
+    assert_eq!(fractions.iter().max(), Some(&r(3, 4)));
+}
+
+#[test]
+fn writes_rounded_decimals() {
+    assert_eq!(r(2, 3).to_decimal(3), "0.667");
+    assert_eq!(r(20, 3).to_decimal(0), "7");
+    assert_eq!(r(1, 8).to_decimal(2), "0.13");
+    assert_eq!(r(-1, 8).to_decimal(2), "-0.13");
+    assert_eq!(r(-1, 1000).to_decimal(2), "0.00");
+    assert_eq!(r(999, 1000).to_decimal(2), "1.00");
+    assert_eq!(r(5, 1).to_decimal(3), "5.000");
+    assert_eq!(r(1, 7).to_decimal(12), "0.142857142857");
+}
+
+#[test]
+fn writes_repeating_decimals() {
+    assert_eq!(r(1, 3).to_repeating(), "0.(3)");
+    assert_eq!(r(1, 6).to_repeating(), "0.1(6)");
+    assert_eq!(r(22, 7).to_repeating(), "3.(142857)");
+    assert_eq!(r(-7, 12).to_repeating(), "-0.58(3)");
+    assert_eq!(r(1, 4).to_repeating(), "0.25");
+    assert_eq!(r(-3, 1).to_repeating(), "-3");
+    // 1/7919 repeats after 3959 digits, more than are written
+    assert!(r(1, 7919).to_repeating().ends_with("..."));
+}
+
+#[test]
+fn calculator_shows_fractions_as_asked() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(calculator.eval_line("20 / 3"), "6");
+    calculator.eval_line("type rational");
+    assert_eq!(calculator.eval_line("20 / 3"), "20/3");
+    assert_eq!(calculator.eval_line("show decimal 4"), "show: decimal 4");
+    assert_eq!(calculator.fractions, FractionStyle::Decimal(4));
+    assert_eq!(calculator.eval_line("x = 20 / 3"), "x = 6.6667");
+    assert_eq!(calculator.eval_line("show repeating"), "show: repeating");
+    assert_eq!(calculator.eval_line("x"), "6.(6)");
+    // Variables keep their exact value whatever the style
+    assert_eq!(calculator.eval_line("vars"), "x = 20/3");
+    assert_eq!(
+        calculator.eval_line("show decimal"),
+        "Error: unknown style `decimal`, expected fraction, decimal <digits> or repeating, \
+         with at most 1000 digits"
+    );
+    // Rounding to more digits than that would hang the calculator
+    assert_eq!(
+        calculator.eval_line("show decimal 100000000000"),
+        "Error: unknown style `decimal 100000000000`, expected fraction, decimal <digits> or \
+         repeating, with at most 1000 digits"
+    );
+    assert_eq!(calculator.fractions, FractionStyle::Repeating);
+    assert_eq!(
+        FractionStyle::from_name("decimal 2"),
+        Some(FractionStyle::Decimal(2))
+    );
+}
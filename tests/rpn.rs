@@ -0,0 +1,130 @@
+use learn_rust::calc::complex::Complex;
+use learn_rust::calc::env::Env;
+use learn_rust::calc::matrix::Matrix;
+use learn_rust::calc::number::Number;
+use learn_rust::calc::rpn::{self, RpnError};
+use learn_rust::calc::units::Quantity;
+use learn_rust::calc::{Calculator, MathError, Mode, Notation, ParseErrorKind};
+
+fn run(line: &str) -> Result<Vec<i32>, RpnError> {
+    let mut stack: Vec<i32> = Vec::new();
+    rpn::eval(line, &mut stack, &Env::new(), Mode::Checked).map(|()| stack)
+}
+
+#[test]
+fn evaluates_operators_after_operands() {
+    assert_eq!(run("3 4 + 2 *"), Ok(vec![14]));
+    assert_eq!(run("20 3 / 20 3 %"), Ok(vec![6, 2]));
+    assert_eq!(run("2 3 2 ^ ^"), Ok(vec![512]));
+    assert_eq!(run("-7 neg 1 -"), Ok(vec![6]));
+    assert_eq!(run("0b1100 0xa and 1 shl"), Ok(vec![16]));
+    assert_eq!(run("5 3 xor 1 |"), Ok(vec![7]));
+}
+
+#[test]
+fn rearranges_the_stack() {
+    assert_eq!(run("1 2 dup"), Ok(vec![1, 2, 2]));
+    assert_eq!(run("1 2 swap"), Ok(vec![2, 1]));
+    assert_eq!(run("1 2 drop"), Ok(vec![1]));
+    assert_eq!(run("1 2 clear"), Ok(vec![]));
+    assert_eq!(run("5 dup *"), Ok(vec![25]));
+}
+
+#[test]
+fn reports_errors() {
+    assert_eq!(
+        run("1 +"),
+        Err(RpnError::StackUnderflow {
+            word: String::from("+"),
+            needed: 2
+        })
+    );
+    assert_eq!(run("1 0 /"), Err(RpnError::Math(MathError::DivideByZero)));
+    assert_eq!(run("1 (").unwrap_err().to_string(), "unknown word `(`");
+    assert_eq!(run("x").unwrap_err().to_string(), "`x` is not defined");
+    assert_eq!(
+        run("drop").unwrap_err().to_string(),
+        "`drop` needs a value on the stack"
+    );
+}
+
+// The stack after `line`, printed, for any number type
+fn shown<T: Number>(line: &str) -> Result<Vec<String>, RpnError> {
+    let mut stack: Vec<T> = Vec::new();
+    rpn::eval(line, &mut stack, &Env::new(), Mode::Checked)?;
+    Ok(stack.iter().map(|v| v.to_string()).collect())
+}
+
+#[test]
+fn reads_words_like_infix_literals() {
+    assert_eq!(run("1_000 0x1f -0b11"), Ok(vec![1000, 31, -3]));
+    assert_eq!(shown::<Complex>("4i 1 +"), Ok(vec![String::from("1 + 4i")]));
+    assert_eq!(shown::<Quantity>("2km 500m +"), shown::<Quantity>("2.5km"));
+    assert_eq!(
+        shown::<Matrix>("[[1,2]] [[3,4]] +"),
+        shown::<Matrix>("[[4,6]]")
+    );
+}
+
+#[test]
+fn words_that_are_no_numbers_are_unknown() {
+    let unknown = |word: &str| Err(RpnError::UnknownWord(word.to_string()));
+    assert_eq!(run("12abc"), unknown("12abc"));
+    assert_eq!(run("4i"), unknown("4i"));
+    assert_eq!(run("3m"), unknown("3m"));
+    assert_eq!(run("1+2"), unknown("1+2"));
+    assert_eq!(
+        shown::<Quantity>("3km^2"),
+        Err(RpnError::UnknownWord(String::from("3km^2")))
+    );
+    assert_eq!(
+        run("99999999999"),
+        Err(RpnError::Number(ParseErrorKind::NumberTooLarge("i32")))
+    );
+    assert_eq!(
+        run("1e5"),
+        Err(RpnError::Number(ParseErrorKind::Exponent("i32")))
+    );
+    assert_eq!(
+        run("[1,2]"),
+        Err(RpnError::Number(ParseErrorKind::NoMatrices("i32")))
+    );
+    // A space ends the word, so a matrix has none
+    assert_eq!(
+        shown::<Matrix>("[1, 2]"),
+        Err(RpnError::Number(ParseErrorKind::UnclosedBracket))
+    );
+}
+
+#[test]
+fn keeps_the_stack_when_a_line_fails() {
+    let mut stack: Vec<i64> = vec![1, 2];
+    let env: Env<i64> = Env::new();
+    assert!(rpn::eval("3 * 0 /", &mut stack, &env, Mode::Checked).is_err());
+    assert_eq!(stack, [1, 2]);
+}
+
+#[test]
+fn calculator_shows_the_stack() {
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("x = 10");
+    assert_eq!(calculator.eval_line("notation rpn"), "notation: rpn");
+    assert_eq!(calculator.notation, Notation::Rpn);
+    assert_eq!(calculator.eval_line("3 4 +"), "stack: 7");
+    assert_eq!(calculator.eval_line("x"), "stack: 7 10");
+    assert_eq!(calculator.eval_line("swap -"), "stack: 3");
+    assert_eq!(
+        calculator.eval_line("+"),
+        "Error: `+` needs 2 values on the stack"
+    );
+    assert_eq!(calculator.eval_line("type f64"), "type: f64");
+    assert_eq!(calculator.eval_line("2 /"), "stack: 1.5");
+    assert_eq!(calculator.eval_line("2 *"), "stack: 3");
+    assert_eq!(calculator.eval_line("type i32"), "type: i32");
+    assert_eq!(calculator.eval_line("drop"), "stack: empty");
+    assert_eq!(calculator.eval_line("notation infix"), "notation: infix");
+    assert_eq!(
+        calculator.eval_line("3 4 +"),
+        "Error: expected one of `+`, `-`, `*`, `/`, `%`, `^`, `&`, `|`, `xor`, `<<`, `>>` or `)` at byte 2"
+    );
+}
@@ -0,0 +1,88 @@
+use learn_rust::tui::{self, App, Key};
+
+fn press(app: &mut App, keys: &[Key]) {
+    for &key in keys {
+        app.handle_key(key).unwrap();
+    }
+}
+
+#[test]
+fn keys_are_decoded_from_terminal_bytes() {
+    assert_eq!(
+        tui::parse_keys(b"\x1b[A\x1b[B\x1b[5~\x1b[6~\x1bOH\r/\x7f\x03"),
+        [
+            Key::Up,
+            Key::Down,
+            Key::PageUp,
+            Key::PageDown,
+            Key::Home,
+            Key::Enter,
+            Key::Char('/'),
+            Key::Backspace,
+            Key::Interrupt,
+        ]
+    );
+    assert_eq!(tui::parse_keys(b"\x1b"), [Key::Esc]);
+    assert_eq!(tui::parse_keys("é".as_bytes()), [Key::Char('é')]);
+}
+
+#[test]
+fn arrows_move_the_selection() {
+    let mut app: App = App::new();
+    assert_eq!(app.current().unwrap().name, "vars");
+    press(&mut app, &[Key::Down, Key::Down, Key::Char('k')]);
+    assert_eq!(app.current().unwrap().name, "data_types");
+    press(&mut app, &[Key::Up, Key::Up]);
+    assert_eq!(app.current().unwrap().name, "vars");
+    press(&mut app, &[Key::End]);
+    assert_eq!(app.current().unwrap().name, "hashmap_example3");
+}
+
+#[test]
+fn search_filters_lesson_names() {
+    let mut app: App = App::new();
+    press(&mut app, &[Key::Char('/')]);
+    for c in "Hash".chars() {
+        press(&mut app, &[Key::Char(c)]);
+    }
+    let names: Vec<&str> = app.visible().iter().map(|l| l.name).collect();
+    assert_eq!(
+        names,
+        ["hashmap_example", "hashmap_example2", "hashmap_example3"]
+    );
+
+    // While searching, `q` is typed instead of quitting
+    press(&mut app, &[Key::Char('q')]);
+    assert!(!app.quit);
+    assert!(app.current().is_none());
+
+    press(&mut app, &[Key::Esc]);
+    assert_eq!(app.visible().len(), learn_rust::lessons::LESSONS.len());
+    press(&mut app, &[Key::Char('q')]);
+    assert!(app.quit);
+}
+
+#[test]
+fn running_shows_the_output() {
+    let mut app: App = App::new();
+    press(&mut app, &[Key::Char('/')]);
+    for c in "slice".chars() {
+        press(&mut app, &[Key::Char(c)]);
+    }
+    press(&mut app, &[Key::Enter, Key::Enter]);
+
+    let screen: Vec<String> = app.render(100, 40);
+    assert_eq!(screen.len(), 40);
+    assert!(screen.iter().all(|line| line.chars().count() == 100));
+    let text: String = screen.join("\n");
+    assert!(text.contains("▶ slice_example"));
+    assert!(text.contains("pub fn slice_example("));
+    assert!(text.contains("Slice: [2, 3, 4]"));
+}
+
+#[test]
+fn tiny_terminals_get_a_message() {
+    let screen: Vec<String> = App::new().render(20, 5);
+    assert_eq!(screen.len(), 5);
+    assert!(screen[0].starts_with("The terminal"));
+}
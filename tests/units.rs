@@ -0,0 +1,209 @@
+use learn_rust::calc::complex::Complex;
+use learn_rust::calc::rational::Rational;
+use learn_rust::calc::units::{Dimension, Quantity};
+use learn_rust::calc::{self, Calculator, MathError, ParseError, ParseErrorKind, parse_as};
+
+mod common;
+
+use common::{compute, shown};
+
+#[test]
+fn units_are_stored_in_base_units() {
+    assert_eq!(
+        compute::<Quantity>("3 km"),
+        Ok(Quantity::new(3000.0, Dimension::LENGTH))
+    );
+    assert_eq!(shown::<Quantity>("2 h"), "7200 s");
+    assert_eq!(shown::<Quantity>("500 g"), "0.5 kg");
+    assert_eq!(shown::<Quantity>("3 km + 200 m"), "3200 m");
+    assert_eq!(shown::<Quantity>("-2.5 cm"), "-0.025 m");
+}
+
+#[test]
+fn derived_units_combine_dimensions() {
+    let speed: Quantity = compute::<Quantity>("20 m/s").unwrap();
+    assert_eq!(
+        speed.dimension(),
+        Dimension {
+            mass: 0,
+            length: 1,
+            time: -1
+        }
+    );
+    assert_eq!(shown::<Quantity>("9.81 m/s^2 * 70 kg"), "686.7 kg*m/s^2");
+    assert_eq!(shown::<Quantity>("2 m * 3 m"), "6 m^2");
+    assert_eq!(shown::<Quantity>("(2 m)^2"), "4 m^2");
+    assert_eq!(shown::<Quantity>("1 / 4 s"), "0.25 s^-1");
+    assert_eq!(shown::<Quantity>("6 km / 2 km"), "3");
+}
+
+#[test]
+fn reports_dimension_mismatches() {
+    assert_eq!(
+        compute::<Quantity>("1 m + 1 s"),
+        Err(MathError::DimensionMismatch(
+            String::from("m"),
+            String::from("s")
+        ))
+    );
+    assert_eq!(
+        compute::<Quantity>("2 m/s - 3"),
+        Err(MathError::DimensionMismatch(
+            String::from("m/s"),
+            String::from("1")
+        ))
+    );
+    assert_eq!(
+        compute::<Quantity>("4 m^0.5"),
+        Err(MathError::FractionalUnit(String::from("m")))
+    );
+    assert_eq!(
+        compute::<Quantity>("2^(3 s)"),
+        Err(MathError::DimensionMismatch(
+            String::from("s"),
+            String::from("1")
+        ))
+    );
+}
+
+#[test]
+fn converts_between_units() {
+    let distance: Quantity = compute::<Quantity>("3000 m").unwrap();
+    assert_eq!(distance.in_unit(&Quantity::unit("km").unwrap()), Ok(3.0));
+    assert_eq!(
+        compute::<Quantity>("3 cm")
+            .unwrap()
+            .in_unit(&Quantity::unit("cm").unwrap()),
+        Ok(3.0)
+    );
+    assert!(distance.in_unit(&Quantity::unit("s").unwrap()).is_err());
+}
+
+#[test]
+fn a_number_before_a_name_multiplies() {
+    assert_eq!(calc::parse("3 x").unwrap().to_string(), "3 * x");
+    assert_eq!(calc::parse("2^3 x^2").unwrap().to_string(), "2^3 * x^2");
+    assert_eq!(
+        calc::parse("20 m / 4 s").unwrap().to_string(),
+        "20 * m / (4 * s)"
+    );
+    assert!(calc::parse("x y").is_err());
+    assert_eq!(parse_as::<i32>("6 xor 3").unwrap().execute(), Ok(5));
+}
+
+#[test]
+fn an_exponent_after_the_digits_is_scientific_notation() {
+    assert_eq!(compute::<f64>("1e5"), Ok(100000.0));
+    assert_eq!(compute::<f64>("2.5E-3 * 2"), Ok(0.005));
+    assert_eq!(compute::<Complex>("1e2i"), Ok(Complex::new(0.0, 100.0)));
+    assert_eq!(shown::<Quantity>("1.5e3 m"), "1500 m");
+    assert_eq!(shown::<Rational>("1.5e-3"), "3/2000");
+    // A space keeps a name like `e5` apart
+    assert_eq!(
+        compute::<f64>("1 e5"),
+        Err(MathError::UndefinedVariable(String::from("e5")))
+    );
+    assert_eq!(shown::<i32>("0x1e5"), "485");
+    assert_eq!(
+        parse_as::<i64>("1e5").unwrap_err(),
+        ParseError {
+            kind: ParseErrorKind::Exponent("i64"),
+            offset: 0
+        }
+    );
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("type complex");
+    assert_eq!(calculator.eval_line("1e5 * i"), "100000i");
+}
+
+#[test]
+fn calculator_with_quantities() {
+    let mut calculator: Calculator = Calculator::new();
+    let lines = [
+        "type quantity",
+        "d = 100 km",
+        "t = 2 h",
+        "v = d / t",
+        "v in km/h",
+        "d + t",
+        "v in s",
+        "d in mm",
+        "m = 2",
+        "3 m",
+        "type f64",
+    ];
+    let replies: Vec<String> = lines
+        .iter()
+        .map(|line| calculator.eval_line(line))
+        .collect();
+    assert_eq!(
+        replies,
+        [
+            "type: quantity",
+            "d = 100000 m",
+            "t = 7200 s",
+            "v = 13.8888888888889 m/s",
+            "50 km/h",
+            "Error: Can not combine `m` with `s`",
+            "Error: Can not combine `m/s` with `s`",
+            "100000000 mm",
+            "m = 2",
+            "6",
+            "type: f64 (dropped d, t, v, not a valid f64)",
+        ]
+    );
+}
+
+#[test]
+fn conversion_errors_point_into_the_line() {
+    let mut calculator: Calculator = Calculator::new();
+    calculator.eval_line("type quantity");
+    calculator.eval_line("errors plain");
+    assert_eq!(
+        calculator.eval_line("2 km in m/0"),
+        "error[E101]: Can not divide by zero\n  |\n  | 2 km in m/0\n  |         ^^^\n  = help: \
+         no number type can divide by zero"
+    );
+    assert_eq!(
+        calculator.eval_line("q in m"),
+        "error[E106]: `q` is not defined\n  |\n  | q in m\n  | ^\n  = help: \
+         assign it first, like `q = 1`, or list the variables with `vars`"
+    );
+    assert!(calculator.eval_line("2 km in s").starts_with(
+        "error[E109]: Can not combine `m` with `s`\n  |\n  | 2 km in s\n  | ^^^^^^^^^\n"
+    ));
+    calculator.eval_line("errors short");
+    assert_eq!(
+        calculator.eval_line("2 km in m )"),
+        "Error: this `)` has no matching `(` at byte 10"
+    );
+}
+
+#[test]
+fn unit_powers_that_overflow_are_errors() {
+    assert_eq!(
+        compute::<Quantity>("(m^2)^2000000000"),
+        Err(MathError::Overflow)
+    );
+    assert_eq!(
+        compute::<Quantity>("m^2000000000 * m^2000000000"),
+        Err(MathError::Overflow)
+    );
+    assert_eq!(Dimension::LENGTH.pow(i32::MAX).unwrap().length, i32::MAX);
+    assert_eq!(
+        Dimension::TIME.pow(i32::MIN).unwrap().per(Dimension::TIME),
+        None
+    );
+}
+
+#[test]
+fn simplify_keeps_what_checks_dimensions() {
+    let simplified =
+        |input: &str| calc::algebra::simplify(&parse_as::<Quantity>(input).unwrap()).to_string();
+    assert_eq!(simplified("x + 0"), "x + 0");
+    assert_eq!(simplified("x - x"), "x - x");
+    assert_eq!(simplified("0 * x"), "0 * x");
+    assert_eq!(simplified("x^0"), "x^0");
+    assert_eq!(simplified("2 * x * 3 + 1 + 2"), "6 * x + 3");
+    assert_eq!(simplified("x * 1"), "x");
+}
@@ -0,0 +1,151 @@
+use learn_rust::calc::env::Env;
+use learn_rust::calc::number::Number;
+use learn_rust::calc::rational::Rational;
+use learn_rust::calc::vm::{self, Instr, Program};
+use learn_rust::calc::{Calculator, MathError, Mode, Operation, parse_as};
+
+const FORMULAS: [&str; 12] = [
+    "1 + 2 * 3",
+    "(x + 1) * (y - 2) / 3",
+    "-x ^ 2",
+    "x % y - -y",
+    "2 ^ 62 * 4",
+    "-2 ^ 63 - 1",
+    "x / (y - y)",
+    "7 % 0",
+    "2 ^ -1",
+    "z + 1",
+    "1 / 0 + z",
+    "x ^ 0.5",
+];
+
+// The VM must return what walking the tree returns, errors included
+fn same_as_tree<T: Number>(env: &Env<T>) {
+    for formula in FORMULAS {
+        let Ok(operation) = parse_as::<T>(formula) else {
+            continue;
+        };
+        let program: Program<T> = vm::compile(&operation);
+        for mode in Mode::ALL {
+            assert_eq!(
+                program.run(env, mode),
+                operation.execute_in(env, mode),
+                "{} as {} in {} mode",
+                formula,
+                T::NAME,
+                mode.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn runs_like_execute() {
+    let mut ints: Env<i64> = Env::new();
+    ints.set("x", 7);
+    ints.set("y", -3);
+    same_as_tree(&ints);
+
+    let mut small: Env<i32> = Env::new();
+    small.set("x", i32::MAX);
+    small.set("y", i32::MIN);
+    same_as_tree(&small);
+
+    let mut floats: Env<f64> = Env::new();
+    floats.set("x", 2.5);
+    floats.set("y", -0.5);
+    same_as_tree(&floats);
+
+    let mut fractions: Env<Rational> = Env::new();
+    fractions.set("x", Rational::new(2, 3).unwrap());
+    fractions.set("y", Rational::from_integer(-4));
+    same_as_tree(&fractions);
+}
+
+#[test]
+fn runs_again_with_bound_variables() {
+    let operation: Operation<i64> = parse_as("(x + 1) * (y - 2) / z").unwrap();
+    let program: Program<i64> = vm::compile(&operation);
+    let mut env: Env<i64> = Env::new();
+    env.set("x", 7);
+    env.set("y", -3);
+    let vars: Vec<Option<i64>> = program.bind(&env);
+    assert_eq!(vars, [Some(7), Some(-3), None]);
+    let mut stack: Vec<i64> = vec![99];
+    assert_eq!(
+        program.run_with(&vars, &mut stack, Mode::Checked),
+        Err(MathError::UndefinedVariable(String::from("z")))
+    );
+    let vars: Vec<Option<i64>> = vec![Some(7), Some(-3), Some(4)];
+    for _ in 0..3 {
+        assert_eq!(program.run_with(&vars, &mut stack, Mode::Checked), Ok(-10));
+    }
+}
+
+#[test]
+fn reports_the_first_error() {
+    let operation: Operation<i32> = parse_as("1 / 0 + z").unwrap();
+    let program: Program<i32> = vm::compile(&operation);
+    assert_eq!(
+        program.run(&Env::new(), Mode::Checked),
+        Err(MathError::DivideByZero)
+    );
+
+    let operation: Operation<i32> = parse_as("z + 1 / 0").unwrap();
+    let program: Program<i32> = vm::compile(&operation);
+    assert_eq!(
+        program.run(&Env::new(), Mode::Checked),
+        Err(MathError::UndefinedVariable(String::from("z")))
+    );
+}
+
+#[test]
+fn compiles_to_postfix() {
+    let program: Program<i32> = vm::compile(&parse_as("(1 + x) * x").unwrap());
+    assert_eq!(
+        program.code(),
+        [
+            Instr::Push(1),
+            Instr::Load(0),
+            Instr::Add,
+            Instr::Load(0),
+            Instr::Mul
+        ]
+    );
+    assert_eq!(program.names(), [String::from("x")]);
+    assert_eq!(program.max_stack(), 2);
+}
+
+#[test]
+fn disassembles() {
+    let program: Program<i32> = vm::compile(&parse_as("-(a - 2) ^ 3").unwrap());
+    assert_eq!(
+        program.to_string(),
+        "0000  load a\n0001  push 2\n0002  sub\n0003  push 3\n0004  pow\n0005  neg\n"
+    );
+}
+
+#[test]
+fn calculator_commands() {
+    let mut calculator: Calculator = Calculator::new();
+    assert_eq!(
+        calculator.eval_line("dis 1 + 2"),
+        "0000  push 1\n0001  push 2\n0002  add"
+    );
+    assert_eq!(
+        calculator.eval_line("dis"),
+        "Error: `dis` expects an operation"
+    );
+    assert!(
+        calculator
+            .eval_line("bench 1 + 2")
+            .starts_with("100000 runs: tree ")
+    );
+}
+
+#[test]
+fn benchmark_counts_runs() {
+    let operation: Operation<i64> = parse_as("2 * 3").unwrap();
+    let comparison: vm::Comparison = vm::benchmark(&operation, &Env::new(), Mode::Checked, 10);
+    assert_eq!(comparison.iterations, 10);
+}